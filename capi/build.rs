@@ -0,0 +1,23 @@
+//! Generates the C header from the library's exports into `OUT_DIR`. The copy in
+//! `include/aoc2024.h` is only rewritten when asked for with `AOC2024_UPDATE_HEADER=1`;
+//! `tests/header.rs` fails if it's gone stale.
+
+use std::{env, fs, path::Path};
+
+fn main() {
+    let dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let header = cbindgen::Builder::new()
+        .with_crate(&dir)
+        .with_config(cbindgen::Config::from_file(format!("{}/cbindgen.toml", dir)).unwrap())
+        .generate()
+        .expect("couldn't generate the C header");
+    let generated = Path::new(&env::var("OUT_DIR").unwrap()).join("aoc2024.h");
+    header.write_to_file(&generated);
+    if env::var_os("AOC2024_UPDATE_HEADER").is_some_and(|update| update != "0") {
+        fs::copy(&generated, format!("{}/include/aoc2024.h", dir))
+            .expect("couldn't update include/aoc2024.h");
+    }
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+    println!("cargo:rerun-if-env-changed=AOC2024_UPDATE_HEADER");
+}
@@ -0,0 +1,69 @@
+/* Advent of Code 2024 solvers. Link against libaoc2024_capi. */
+
+#ifndef AOC2024_H
+#define AOC2024_H
+
+/* Generated by cbindgen from capi/src/lib.rs; regenerate it with AOC2024_UPDATE_HEADER=1 cargo build -p aoc2024-capi rather than editing it by hand. */
+
+#include <stddef.h>
+#include <stdint.h>
+
+// The answer was written to the output buffer.
+#define AOC2024_OK 0
+
+// The solver failed, e.g. because it couldn't parse the input; the message was written to the
+// output buffer.
+#define AOC2024_ERROR 1
+
+// The output buffer is too small; `*out_len` has the size needed and nothing was written.
+#define AOC2024_BUFFER_TOO_SMALL 2
+
+// The input isn't valid UTF-8.
+#define AOC2024_INVALID_INPUT 3
+
+// The solver panicked; what it said, and where, was written to the output buffer.
+#define AOC2024_PANICKED 4
+
+// That day or part isn't solved here.
+#define AOC2024_UNKNOWN_PART 5
+
+// `out_len` was null, or `input` or `out_buf` was null with a non-zero length.
+#define AOC2024_NULL_POINTER 6
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Solves `part` (1 or 2) of `day` with its default implementation, on the `input_len` bytes of
+// UTF-8 at `input`.
+//
+// On entry `*out_len` is the size of `out_buf`. On return it's the length of the UTF-8 text
+// written there (not NUL-terminated): the answer for `AOC2024_OK`, a message for the other
+// codes. If the buffer is too small, nothing is written, `AOC2024_BUFFER_TOO_SMALL` is returned
+// and `*out_len` is the size needed, so the call can be made again with a bigger one.
+//
+// Panics never cross the boundary; they come back as `AOC2024_PANICKED`. Safe to call from
+// several threads at once.
+//
+// To say where a panic happened, the first call installs a panic hook. It's the hook of the Rust
+// runtime this library carries, which a host written in Rust shares if it links the library
+// statically. Panics the library didn't catch are passed on to whatever hook was there before;
+// a hook set later replaces it, and panics are then still caught but reported without their
+// location.
+//
+// # Safety
+//
+// `input` must point at `input_len` readable bytes, `out_buf` at `*out_len` writable bytes, and
+// `out_len` at a `size_t`.
+int32_t aoc2024_solve(uint8_t day,
+                      uint8_t part,
+                      const uint8_t *input,
+                      size_t input_len,
+                      uint8_t *out_buf,
+                      size_t *out_len);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* AOC2024_H */
@@ -0,0 +1,69 @@
+//! Every day's solver as a C library, for calling in-process from other languages. Build it with
+//! `cargo build --release -p aoc2024-capi` and include `include/aoc2024.h`, which is generated
+//! from this file (`AOC2024_UPDATE_HEADER=1` on the build rewrites it).
+
+use aoc2024::{ffi, progress};
+
+/// The answer was written to the output buffer.
+pub const AOC2024_OK: i32 = 0;
+/// The solver failed, e.g. because it couldn't parse the input; the message was written to the
+/// output buffer.
+pub const AOC2024_ERROR: i32 = 1;
+/// The output buffer is too small; `*out_len` has the size needed and nothing was written.
+pub const AOC2024_BUFFER_TOO_SMALL: i32 = 2;
+/// The input isn't valid UTF-8.
+pub const AOC2024_INVALID_INPUT: i32 = 3;
+/// The solver panicked; what it said, and where, was written to the output buffer.
+pub const AOC2024_PANICKED: i32 = 4;
+/// That day or part isn't solved here.
+pub const AOC2024_UNKNOWN_PART: i32 = 5;
+/// `out_len` was null, or `input` or `out_buf` was null with a non-zero length.
+pub const AOC2024_NULL_POINTER: i32 = 6;
+
+// The header is generated from the literals above, so they're checked against the real codes.
+const _: () = assert!(
+    AOC2024_OK == ffi::OK
+        && AOC2024_ERROR == ffi::ERROR
+        && AOC2024_BUFFER_TOO_SMALL == ffi::BUFFER_TOO_SMALL
+        && AOC2024_INVALID_INPUT == ffi::INVALID_INPUT
+        && AOC2024_PANICKED == ffi::PANICKED
+        && AOC2024_UNKNOWN_PART == ffi::UNKNOWN_PART
+        && AOC2024_NULL_POINTER == ffi::NULL_POINTER
+);
+
+/// Solves `part` (1 or 2) of `day` with its default implementation, on the `input_len` bytes of
+/// UTF-8 at `input`.
+///
+/// On entry `*out_len` is the size of `out_buf`. On return it's the length of the UTF-8 text
+/// written there (not NUL-terminated): the answer for `AOC2024_OK`, a message for the other
+/// codes. If the buffer is too small, nothing is written, `AOC2024_BUFFER_TOO_SMALL` is returned
+/// and `*out_len` is the size needed, so the call can be made again with a bigger one.
+///
+/// Panics never cross the boundary; they come back as `AOC2024_PANICKED`. Safe to call from
+/// several threads at once.
+///
+/// To say where a panic happened, the first call installs a panic hook. It's the hook of the Rust
+/// runtime this library carries, which a host written in Rust shares if it links the library
+/// statically. Panics the library didn't catch are passed on to whatever hook was there before;
+/// a hook set later replaces it, and panics are then still caught but reported without their
+/// location.
+///
+/// # Safety
+///
+/// `input` must point at `input_len` readable bytes, `out_buf` at `*out_len` writable bytes, and
+/// `out_len` at a `size_t`.
+#[no_mangle]
+pub unsafe extern "C" fn aoc2024_solve(
+    day: u8,
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    out_buf: *mut u8,
+    out_len: *mut usize,
+) -> i32 {
+    // There's no terminal of ours to draw progress bars on.
+    progress::set_enabled(false);
+    ffi::solve_into(input, input_len, out_buf, out_len, |input| {
+        ffi::solve(day, part, input)
+    })
+}
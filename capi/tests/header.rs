@@ -0,0 +1,11 @@
+/// The header in `include/` is the one the build generates from `src/lib.rs`.
+#[test]
+fn checked_in_header_is_up_to_date() {
+    let generated = include_str!(concat!(env!("OUT_DIR"), "/aoc2024.h"));
+    let checked_in = include_str!("../include/aoc2024.h");
+    assert!(
+        generated == checked_in,
+        "include/aoc2024.h is out of date; regenerate it with \
+         `AOC2024_UPDATE_HEADER=1 cargo build -p aoc2024-capi`"
+    );
+}
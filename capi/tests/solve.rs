@@ -0,0 +1,71 @@
+use aoc2024::ffi;
+use aoc2024_capi::*;
+
+/// Calls `aoc2024_solve` with a `capacity`-byte buffer, returning the status and what was written
+/// (or, for `AOC2024_BUFFER_TOO_SMALL`, how big the buffer needed to be).
+fn solve(day: u8, part: u8, input: &[u8], capacity: usize) -> (i32, Result<String, usize>) {
+    let mut out = vec![0; capacity];
+    let mut len = out.len();
+    let status = unsafe {
+        aoc2024_solve(
+            day,
+            part,
+            input.as_ptr(),
+            input.len(),
+            out.as_mut_ptr(),
+            &mut len,
+        )
+    };
+    if status == AOC2024_BUFFER_TOO_SMALL {
+        return (status, Err(len));
+    }
+    out.truncate(len);
+    (status, Ok(String::from_utf8(out).unwrap()))
+}
+
+#[test]
+fn solves_both_parts() {
+    let input = include_bytes!("../../input/day6/test.txt");
+    assert_eq!(solve(6, 1, input, 64), (AOC2024_OK, Ok("17".to_string())));
+    assert_eq!(solve(6, 2, input, 64), (AOC2024_OK, Ok("2".to_string())));
+}
+
+#[test]
+fn says_how_big_the_buffer_needs_to_be() {
+    let input = include_bytes!("../../input/day6/test.txt");
+    assert_eq!(solve(6, 1, input, 1), (AOC2024_BUFFER_TOO_SMALL, Err(2)));
+    assert_eq!(solve(6, 1, input, 2), (AOC2024_OK, Ok("17".to_string())));
+}
+
+#[test]
+fn reports_failures_as_status_codes() {
+    let (status, message) = solve(6, 1, b"..?\n.^.\n", 256);
+    assert_eq!(status, AOC2024_ERROR, "{:?}", message);
+    assert_eq!(solve(6, 3, b"", 256).0, AOC2024_UNKNOWN_PART);
+    assert_eq!(solve(99, 1, b"", 256).0, AOC2024_UNKNOWN_PART);
+    assert_eq!(solve(6, 1, b"\xff\n", 256).0, AOC2024_INVALID_INPUT);
+    let status =
+        unsafe { aoc2024_solve(6, 1, [].as_ptr(), 0, [].as_mut_ptr(), std::ptr::null_mut()) };
+    assert_eq!(status, AOC2024_NULL_POINTER);
+}
+
+#[test]
+fn catches_panics() {
+    // Every day's parser turns bad input away, so panic on purpose through the same path
+    // `aoc2024_solve` takes.
+    let mut out = vec![0; 256];
+    let mut len = out.len();
+    let status = unsafe {
+        ffi::solve_into([].as_ptr(), 0, out.as_mut_ptr(), &mut len, |_| {
+            panic!("deliberately")
+        })
+    };
+    out.truncate(len);
+    let message = String::from_utf8(out).unwrap();
+    assert_eq!(status, AOC2024_PANICKED, "{:?}", message);
+    assert!(message.contains("deliberately"), "{:?}", message);
+    assert!(message.contains("tests/solve.rs"), "{:?}", message);
+    // A day that used to panic on it now says what's wrong.
+    let (status, message) = solve(5, 1, b"1|99999999999999999999999\n\n1,2\n", 256);
+    assert_eq!(status, AOC2024_ERROR, "{:?}", message);
+}
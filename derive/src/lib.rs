@@ -0,0 +1,171 @@
+//! Derive macros for the `aoc2024` crate.
+
+use proc_macro::TokenStream;
+use proc_macro2::Span;
+use quote::quote;
+use syn::{
+    parse::{Parse, ParseStream},
+    parse_macro_input, Data, DeriveInput, Fields, Ident, LitChar,
+};
+
+/// Implements `aoc2024::tile::Tile` and `Display` for an enum of unit variants, each marked with
+/// the character it's written as:
+///
+/// ```ignore
+/// #[derive(Clone, Copy, Tile)]
+/// enum TileType {
+///     #[tile('#')]
+///     Wall,
+///     #[tile('.')]
+///     Free,
+/// }
+/// ```
+///
+/// One variant holding a number can be marked `#[tile(digit)]` instead: it's read from any digit
+/// and written as the last digit of its number.
+///
+/// Both directions come from the one table, so they can't drift apart.
+#[proc_macro_derive(Tile, attributes(tile))]
+pub fn derive_tile(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match tile(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(e) => e.to_compile_error().into(),
+    }
+}
+
+fn tile(input: &DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let Data::Enum(data) = &input.data else {
+        return Err(syn::Error::new(
+            Span::call_site(),
+            "Tile can only be derived for enums",
+        ));
+    };
+
+    let mut table: Vec<(&Ident, LitChar)> = Vec::new();
+    let mut digit: Option<&Ident> = None;
+    for variant in &data.variants {
+        let mut found = None;
+        for attr in variant.attrs.iter().filter(|a| a.path().is_ident("tile")) {
+            if found.is_some() {
+                return Err(syn::Error::new_spanned(attr, "more than one #[tile]"));
+            }
+            found = Some(attr.parse_args::<Written>()?);
+        }
+        let c = match found {
+            None => {
+                return Err(syn::Error::new_spanned(
+                    variant,
+                    "missing #[tile('c')] saying how this variant is written",
+                ));
+            }
+            Some(Written::Digit(span)) => {
+                if !matches!(&variant.fields, Fields::Unnamed(fields) if fields.unnamed.len() == 1)
+                {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "a #[tile(digit)] variant holds exactly one number",
+                    ));
+                }
+                if let Some(other) = digit {
+                    return Err(syn::Error::new(
+                        span,
+                        format!("digits are already used for {}", other),
+                    ));
+                }
+                digit = Some(&variant.ident);
+                continue;
+            }
+            Some(Written::Char(c)) => c,
+        };
+        if !matches!(variant.fields, Fields::Unit) {
+            return Err(syn::Error::new_spanned(
+                variant,
+                "Tile variants can't hold data, as each has to be a single character",
+            ));
+        }
+        if let Some((other, _)) = table.iter().find(|(_, seen)| seen.value() == c.value()) {
+            return Err(syn::Error::new_spanned(
+                &c,
+                format!("{:?} is already used for {}", c.value(), other),
+            ));
+        }
+        table.push((&variant.ident, c));
+    }
+    if let (Some(variant), Some((_, c))) = (
+        digit,
+        table.iter().find(|(_, c)| c.value().is_ascii_digit()),
+    ) {
+        return Err(syn::Error::new_spanned(
+            c,
+            format!("{:?} is already used for {}", c.value(), variant),
+        ));
+    }
+
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+    let (variants, chars): (Vec<_>, Vec<_>) = table.into_iter().unzip();
+    let type_name = name.to_string();
+    let (from_digit, to_digit) = match digit {
+        Some(variant) => (
+            quote! {
+                '0'..='9' => ::std::result::Result::Ok(Self::#variant((c as u8 - b'0').into())),
+            },
+            quote! {
+                Self::#variant(n) => (b'0' + (n % 10) as u8) as char,
+            },
+        ),
+        None => (quote! {}, quote! {}),
+    };
+    Ok(quote! {
+        impl #impl_generics ::aoc2024::tile::Tile for #name #type_generics #where_clause {
+            const NAME: &'static str = #type_name;
+
+            fn from_char(c: char) -> ::std::result::Result<Self, ::aoc2024::tile::UnknownTile> {
+                match c {
+                    #(#chars => ::std::result::Result::Ok(Self::#variants),)*
+                    #from_digit
+                    found => ::std::result::Result::Err(::aoc2024::tile::UnknownTile {
+                        tile: #type_name,
+                        found,
+                    }),
+                }
+            }
+
+            fn to_char(self) -> char {
+                match self {
+                    #(Self::#variants => #chars,)*
+                    #to_digit
+                }
+            }
+        }
+
+        impl #impl_generics ::std::fmt::Display for #name #type_generics #where_clause {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+                ::std::fmt::Write::write_char(f, ::aoc2024::tile::Tile::to_char(*self))
+            }
+        }
+    })
+}
+
+/// What goes in a `#[tile(...)]`: the character a variant is written as, or `digit`.
+enum Written {
+    Char(LitChar),
+    Digit(Span),
+}
+
+impl Parse for Written {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        if input.peek(LitChar) {
+            return Ok(Written::Char(input.parse()?));
+        }
+        let ident = input.parse::<Ident>()?;
+        if ident != "digit" {
+            return Err(syn::Error::new_spanned(
+                ident,
+                "expected a character, like #[tile('#')], or #[tile(digit)]",
+            ));
+        }
+        Ok(Written::Digit(ident.span()))
+    }
+}
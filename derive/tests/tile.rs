@@ -0,0 +1,49 @@
+use aoc2024::tile::{Tile, UnknownTile};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Tile)]
+enum Cell {
+    #[tile('#')]
+    Wall,
+    #[tile('.')]
+    Free,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Tile)]
+enum Block {
+    #[tile('.')]
+    Free,
+    #[tile(digit)]
+    File(usize),
+}
+
+#[test]
+fn maps_both_ways() {
+    for (c, cell) in [('#', Cell::Wall), ('.', Cell::Free)] {
+        assert_eq!(Cell::from_char(c), Ok(cell));
+        assert_eq!(cell.to_char(), c);
+        assert_eq!(cell.to_string(), c.to_string());
+    }
+    assert_eq!(Cell::NAME, "Cell");
+    assert_eq!(
+        Cell::from_char('x'),
+        Err(UnknownTile {
+            tile: "Cell",
+            found: 'x'
+        })
+    );
+}
+
+#[test]
+fn reads_and_writes_digits() {
+    assert_eq!(Block::from_char('7'), Ok(Block::File(7)));
+    assert_eq!(Block::from_char('.'), Ok(Block::Free));
+    assert!(Block::from_char('x').is_err());
+    assert_eq!(Block::File(3).to_char(), '3');
+    // Only the last digit fits.
+    assert_eq!(Block::File(42).to_string(), "2");
+}
+
+#[test]
+fn rejects_bad_tables() {
+    trybuild::TestCases::new().compile_fail("tests/ui/*.rs");
+}
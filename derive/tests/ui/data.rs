@@ -0,0 +1,9 @@
+use aoc2024::tile::Tile;
+
+#[derive(Clone, Copy, Tile)]
+enum Cell {
+    #[tile('#')]
+    Wall(u8),
+}
+
+fn main() {}
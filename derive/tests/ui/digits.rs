@@ -0,0 +1,19 @@
+use aoc2024::tile::Tile;
+
+#[derive(Clone, Copy, Tile)]
+enum Block {
+    #[tile('0')]
+    Zero,
+    #[tile(digit)]
+    File(usize),
+}
+
+#[derive(Clone, Copy, Tile)]
+enum Two {
+    #[tile(digit)]
+    First(usize),
+    #[tile(digit)]
+    Second(usize),
+}
+
+fn main() {}
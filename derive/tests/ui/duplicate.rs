@@ -0,0 +1,11 @@
+use aoc2024::tile::Tile;
+
+#[derive(Clone, Copy, Tile)]
+enum Cell {
+    #[tile('#')]
+    Wall,
+    #[tile('#')]
+    Rock,
+}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use aoc2024::tile::Tile;
+
+#[derive(Clone, Copy, Tile)]
+enum Cell {
+    #[tile('#')]
+    Wall,
+    Free,
+}
+
+fn main() {}
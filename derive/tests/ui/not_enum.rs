@@ -0,0 +1,6 @@
+use aoc2024::tile::Tile;
+
+#[derive(Clone, Copy, Tile)]
+struct Cell(char);
+
+fn main() {}
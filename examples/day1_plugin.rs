@@ -0,0 +1,62 @@
+//! An alternative day 1 solver built as a plugin:
+//!
+//! ```text
+//! cargo build --release --example day1_plugin
+//! mkdir -p plugins && cp target/release/examples/libday1_plugin.so plugins/
+//! cargo run --release --bin aoc -- run 1 --compare
+//! ```
+//!
+//! It counts the right-hand list once for part 2 instead of searching it for every number.
+
+use aoc2024::answer::Answer;
+use std::collections::HashMap;
+
+/// Both lists, as the two columns of the input.
+fn lists(input: &str) -> Result<(Vec<u64>, Vec<u64>), String> {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+    for (i, line) in input.lines().enumerate() {
+        let numbers = line
+            .split_whitespace()
+            .map(|n| n.parse::<u64>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("line {}: {}", i + 1, e))?;
+        let [l, r] = numbers[..] else {
+            return Err(format!("line {} doesn't have two numbers", i + 1));
+        };
+        left.push(l);
+        right.push(r);
+    }
+    Ok((left, right))
+}
+
+fn part1(input: &str) -> Result<Answer, String> {
+    let (mut left, mut right) = lists(input)?;
+    left.sort_unstable();
+    right.sort_unstable();
+    Ok(left
+        .iter()
+        .zip(&right)
+        .map(|(l, r)| l.abs_diff(*r))
+        .sum::<u64>()
+        .into())
+}
+
+fn part2(input: &str) -> Result<Answer, String> {
+    let (left, right) = lists(input)?;
+    let mut counts = HashMap::<u64, u64>::new();
+    for r in right {
+        *counts.entry(r).or_default() += 1;
+    }
+    Ok(left
+        .iter()
+        .map(|l| l * counts.get(l).unwrap_or(&0))
+        .sum::<u64>()
+        .into())
+}
+
+aoc2024::export_plugin! {
+    day: 1,
+    name: "counting",
+    parts: { 1 => part1, 2 => part2 },
+}
@@ -0,0 +1,250 @@
+#[cfg(feature = "bigmath")]
+use num_bigint::{BigInt, BigUint};
+#[cfg(feature = "bigmath")]
+use rust_decimal::{prelude::ToPrimitive, Decimal};
+use serde::{de, Deserialize, Deserializer, Serialize, Serializer};
+use std::{convert::Infallible, fmt, str::FromStr};
+
+/// What a part produces. Integers that fit in an `i128` are always `Int`, so two answers with the
+/// same value compare equal however they were worked out. Without `bigmath`, integers too big for
+/// an `i128` are kept as text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Int(i128),
+    #[cfg(feature = "bigmath")]
+    Big(BigInt),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Int(n) => write!(f, "{}", n),
+            #[cfg(feature = "bigmath")]
+            Answer::Big(n) => write!(f, "{}", n),
+            Answer::Text(s) => write!(f, "{}", s),
+        }
+    }
+}
+
+/// Reads an answer back from how it's displayed (a manifest, the cache, the site's form...).
+/// Anything that isn't an integer is kept as text.
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        #[cfg(feature = "bigmath")]
+        if let Ok(n) = s.parse::<BigInt>() {
+            return Ok(Answer::from(n));
+        }
+        Ok(match s.parse::<i128>() {
+            Ok(n) => Answer::Int(n),
+            Err(_) => Answer::Text(s.to_string()),
+        })
+    }
+}
+
+impl PartialEq<str> for Answer {
+    fn eq(&self, other: &str) -> bool {
+        let Ok(other) = other.parse::<Answer>();
+        *self == other
+    }
+}
+
+impl PartialEq<&str> for Answer {
+    fn eq(&self, other: &&str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<String> for Answer {
+    fn eq(&self, other: &String) -> bool {
+        self == other.as_str()
+    }
+}
+
+macro_rules! answer_from_int {
+    ($($t:ty),*) => {
+        $(
+            impl From<$t> for Answer {
+                fn from(n: $t) -> Self {
+                    match i128::try_from(n) {
+                        Ok(n) => Answer::Int(n),
+                        #[cfg(feature = "bigmath")]
+                        Err(_) => Answer::Big(BigInt::from(n)),
+                        #[cfg(not(feature = "bigmath"))]
+                        Err(_) => Answer::Text(n.to_string()),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+answer_from_int!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+#[cfg(feature = "bigmath")]
+impl From<BigInt> for Answer {
+    fn from(n: BigInt) -> Self {
+        match n.to_i128() {
+            Some(n) => Answer::Int(n),
+            None => Answer::Big(n),
+        }
+    }
+}
+
+#[cfg(feature = "bigmath")]
+impl From<BigUint> for Answer {
+    fn from(n: BigUint) -> Self {
+        Answer::from(BigInt::from(n))
+    }
+}
+
+/// Whole decimals are integers, anything with a fractional part is kept as text.
+#[cfg(feature = "bigmath")]
+impl From<Decimal> for Answer {
+    fn from(n: Decimal) -> Self {
+        match n.to_i128() {
+            Some(whole) if n.fract().is_zero() => Answer::Int(whole),
+            _ => Answer::Text(n.normalize().to_string()),
+        }
+    }
+}
+
+/// Read the same way as `parse`, so `"42"` is `Int(42)` and only non-integers are `Text`.
+impl From<String> for Answer {
+    fn from(s: String) -> Self {
+        Answer::from(s.as_str())
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(s: &str) -> Self {
+        let Ok(answer) = s.parse();
+        answer
+    }
+}
+
+/// Serialized as a string, so big integers survive JSON.
+impl Serialize for Answer {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Answer {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(AnswerVisitor)
+    }
+}
+
+struct AnswerVisitor;
+
+impl de::Visitor<'_> for AnswerVisitor {
+    type Value = Answer;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "an integer or a string")
+    }
+
+    fn visit_i64<E: de::Error>(self, n: i64) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_u64<E: de::Error>(self, n: u64) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_i128<E: de::Error>(self, n: i128) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_u128<E: de::Error>(self, n: u128) -> Result<Answer, E> {
+        Ok(Answer::from(n))
+    }
+
+    fn visit_str<E: de::Error>(self, s: &str) -> Result<Answer, E> {
+        Ok(Answer::from(s))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BIG: &str = "123456789012345678901234567890123456789012345";
+
+    #[test]
+    fn reads_back_what_it_displays() {
+        for answer in [
+            Answer::Int(42),
+            Answer::Int(-7),
+            BIG.parse::<Answer>().unwrap(),
+            Answer::from("6,0"),
+        ] {
+            assert_eq!(answer.to_string().parse::<Answer>(), Ok(answer.clone()));
+        }
+        assert_eq!(" 42\n".parse::<Answer>(), Ok(Answer::Int(42)));
+        assert_eq!("6,0".parse::<Answer>(), Ok(Answer::Text("6,0".to_string())));
+    }
+
+    #[test]
+    fn integers_too_big_for_an_i128_keep_their_digits() {
+        let big = BIG.parse::<Answer>().unwrap();
+        #[cfg(feature = "bigmath")]
+        assert!(matches!(big, Answer::Big(_)));
+        #[cfg(not(feature = "bigmath"))]
+        assert_eq!(big, Answer::Text(BIG.to_string()));
+        assert_eq!(big.to_string(), BIG);
+        assert_eq!(Answer::from(u128::MAX).to_string(), u128::MAX.to_string());
+    }
+
+    #[test]
+    fn compares_the_same_however_it_was_worked_out() {
+        assert_eq!(Answer::from(42u8), Answer::from(42i128));
+        assert_eq!(Answer::from(42usize), Answer::from(42u128));
+        assert_eq!(Answer::from(42u64), "42");
+        assert_eq!(Answer::from(42u64), *"42");
+        assert_eq!(Answer::from(42u64), "42".to_string());
+        assert_eq!(Answer::from(u128::MAX), u128::MAX.to_string());
+        assert_ne!(Answer::from(42u64), "43");
+        assert_eq!(Answer::from("6,0"), "6,0");
+        assert_eq!(Answer::from("6,0"), Answer::Text("6,0".to_string()));
+        assert_eq!(Answer::from("42"), Answer::Int(42));
+        assert_eq!(Answer::from("42".to_string()), Answer::from(42u64));
+        assert_eq!(Answer::from(BIG), BIG.parse::<Answer>().unwrap());
+        #[cfg(feature = "bigmath")]
+        {
+            assert_eq!(Answer::from(Decimal::new(4200, 2)), Answer::Int(42));
+            assert_eq!(Answer::from(Decimal::new(4250, 2)), "42.5");
+            assert_eq!(
+                Answer::from(BigUint::from(42u8)),
+                Answer::from(BigInt::from(42))
+            );
+        }
+    }
+
+    #[test]
+    fn goes_through_json_as_a_string() {
+        let json = serde_json::to_string(&Answer::Int(42)).unwrap();
+        assert_eq!(json, "\"42\"");
+        assert_eq!(
+            serde_json::from_str::<Answer>(&json).unwrap(),
+            Answer::Int(42)
+        );
+        // Manifests can give answers as plain numbers too.
+        assert_eq!(
+            serde_json::from_str::<Answer>("42").unwrap(),
+            Answer::Int(42)
+        );
+        assert_eq!(
+            serde_json::from_str::<Answer>(&u64::MAX.to_string()).unwrap(),
+            Answer::from(u64::MAX)
+        );
+        let big = BIG.parse::<Answer>().unwrap();
+        let json = serde_json::to_string(&big).unwrap();
+        assert_eq!(serde_json::from_str::<Answer>(&json).unwrap(), big);
+        assert!(serde_json::from_str::<Answer>("[]").is_err());
+    }
+}
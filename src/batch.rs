@@ -0,0 +1,265 @@
+use crate::answer::Answer;
+use crate::manifest::{self, Manifest};
+use crate::memory;
+use crate::parallel;
+use crate::runner::{PartResult, Runner};
+use crate::solution::DynSolution;
+use crate::stats;
+use std::{
+    collections::{btree_map, BTreeMap},
+    fs,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
+};
+
+/// How one input file fared.
+#[derive(Debug, Clone)]
+pub struct FileResult {
+    pub path: PathBuf,
+    pub size: u64,
+    /// Both parts, or why the file couldn't be run at all (unreadable, didn't parse...).
+    pub outcome: Result<Vec<Checked>, String>,
+}
+
+/// A part's result, alongside what the manifest says it should have been.
+#[derive(Debug, Clone)]
+pub struct Checked {
+    pub result: PartResult,
+    pub expected: Option<Answer>,
+}
+
+impl Checked {
+    /// Whether the answer was the expected one, or `None` if nothing was expected.
+    pub fn correct(&self) -> Option<bool> {
+        let expected = self.expected.as_ref()?;
+        Some(
+            self.result
+                .answer
+                .as_ref()
+                .is_ok_and(|answer| answer == expected),
+        )
+    }
+}
+
+impl FileResult {
+    /// Whether the file ran, every part finished and none of them got a wrong answer.
+    pub fn ok(&self) -> bool {
+        self.outcome.as_ref().is_ok_and(|parts| {
+            parts
+                .iter()
+                .all(|part| part.result.answer.is_ok() && part.correct() != Some(false))
+        })
+    }
+}
+
+/// The files `spec` names: every file in a directory (apart from its manifest), a single file, or
+/// the files matching a pattern like `input/day9/test*.txt` (`*` and `?`, in the file name only).
+pub fn find_inputs(spec: &str) -> Result<Vec<PathBuf>, String> {
+    let path = Path::new(spec);
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    let (dir, pattern) = if path.is_dir() {
+        (path, "*")
+    } else if name.contains(['*', '?']) {
+        (path.parent().unwrap_or(Path::new("")), name)
+    } else if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    } else {
+        return Err(format!("{} doesn't exist", spec));
+    };
+    let dir = if dir.as_os_str().is_empty() {
+        Path::new(".")
+    } else {
+        dir
+    };
+
+    let entries =
+        fs::read_dir(dir).map_err(|e| format!("couldn't read {}: {}", dir.display(), e))?;
+    let mut inputs = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("couldn't read {}: {}", dir.display(), e))?
+            .path();
+        let Some(name) = path.file_name().and_then(|name| name.to_str()) else {
+            continue;
+        };
+        if path.is_file() && name != manifest::FILE_NAME && matches(pattern, name) {
+            inputs.push(path);
+        }
+    }
+    if inputs.is_empty() {
+        return Err(format!("no inputs match {}", spec));
+    }
+    inputs.sort();
+    Ok(inputs)
+}
+
+/// Runs both parts on every one of `inputs` in parallel, or one at a time while work or
+/// allocations are being counted, as the counters are shared by every thread. With `check`, each
+/// answer is compared against the manifest in the input's directory.
+///
+/// The files get threads of their own, as many as the global pool has, rather than running on the
+/// pool: a file waits for its parts, and a part that loops in parallel needs the pool's threads
+/// free.
+pub fn run(
+    runner: &Runner,
+    solution: &'static dyn DynSolution,
+    inputs: &[PathBuf],
+    check: bool,
+) -> Result<Vec<FileResult>, String> {
+    let mut manifests = BTreeMap::new();
+    if check {
+        for input in inputs {
+            let dir = input.parent().unwrap_or(Path::new("")).to_path_buf();
+            if let btree_map::Entry::Vacant(entry) = manifests.entry(dir) {
+                let manifest = Manifest::load(&entry.key().join(manifest::FILE_NAME))?;
+                entry.insert(manifest);
+            }
+        }
+    }
+
+    let run_one = |path: &PathBuf| {
+        let manifest = path.parent().and_then(|dir| manifests.get(dir));
+        run_file(runner, solution, path, manifest)
+    };
+    let workers = if stats::enabled() || memory::enabled() {
+        1
+    } else {
+        parallel::threads().min(inputs.len())
+    };
+    if workers <= 1 {
+        return Ok(inputs.iter().map(run_one).collect());
+    }
+    let next = AtomicUsize::new(0);
+    let results = Mutex::new(vec![None; inputs.len()]);
+    thread::scope(|scope| {
+        for _ in 0..workers {
+            scope.spawn(|| {
+                let mut i = next.fetch_add(1, Ordering::Relaxed);
+                while let Some(path) = inputs.get(i) {
+                    let result = run_one(path);
+                    results.lock().unwrap()[i] = Some(result);
+                    i = next.fetch_add(1, Ordering::Relaxed);
+                }
+            });
+        }
+    });
+    Ok(results
+        .into_inner()
+        .unwrap()
+        .into_iter()
+        .map(|result| result.expect("every input is run"))
+        .collect())
+}
+
+fn run_file(
+    runner: &Runner,
+    solution: &'static dyn DynSolution,
+    path: &Path,
+    manifest: Option<&Manifest>,
+) -> FileResult {
+    let content = match fs::read_to_string(path) {
+        Ok(content) => content,
+        Err(e) => {
+            return FileResult {
+                path: path.to_path_buf(),
+                size: 0,
+                outcome: Err(format!("couldn't read it: {}", e)),
+            }
+        }
+    };
+    let report = runner.run(solution, &content, &[1, 2], None);
+
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+    FileResult {
+        path: path.to_path_buf(),
+        size: content.len() as u64,
+        outcome: report.map(|report| {
+            report
+                .parts
+                .into_iter()
+                .map(|result| Checked {
+                    expected: manifest
+                        .and_then(|manifest| manifest.expected(name, result.part))
+                        .cloned(),
+                    result,
+                })
+                .collect()
+        }),
+    }
+}
+
+/// Whether `name` matches `pattern`, where `*` is any run of characters and `?` any one.
+fn matches(pattern: &str, name: &str) -> bool {
+    let pattern = pattern.chars().collect::<Vec<_>>();
+    let name = name.chars().collect::<Vec<_>>();
+    // Where the last `*` was, and how much of `name` it has swallowed so far.
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+    while n < name.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, n));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == name[n] => {
+                p += 1;
+                n += 1;
+            }
+            _ => match backtrack {
+                Some((star, swallowed)) => {
+                    p = star + 1;
+                    n = swallowed + 1;
+                    backtrack = Some((star, swallowed + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_globs() {
+        let cases = [
+            ("test*.txt", "test.txt", true),
+            ("test*.txt", "test2.txt", true),
+            ("test*.txt", "input.txt", false),
+            ("*", "", true),
+            ("*", "anything", true),
+            ("*.txt", "a.txt.bak", false),
+            ("*a*b", "xaybzab", true),
+            ("test?.txt", "test2.txt", true),
+            ("test?.txt", "test.txt", false),
+            ("test?.txt", "test12.txt", false),
+            ("??", "ab", true),
+            ("input.txt", "input.txt", true),
+            ("input.txt", "input.txts", false),
+            ("input.txt", "Input.txt", false),
+            ("", "", true),
+            ("", "a", false),
+        ];
+        for (pattern, name, expected) in cases {
+            assert_eq!(
+                matches(pattern, name),
+                expected,
+                "{:?} on {:?}",
+                pattern,
+                name
+            );
+        }
+    }
+}
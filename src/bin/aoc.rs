@@ -0,0 +1,1131 @@
+#[cfg(feature = "net")]
+use aoc2024::answer::Answer;
+use aoc2024::cache::{self, Cache};
+use aoc2024::config::{Config, Layer, Source};
+#[cfg(feature = "net")]
+use aoc2024::net::{self, Client, NetError};
+#[cfg(feature = "plugins")]
+use aoc2024::plugin;
+#[cfg(feature = "serve")]
+use aoc2024::serve::{self, Limits, Server};
+use aoc2024::{
+    batch::{self, FileResult},
+    input::{self, InputArgs},
+    parallel,
+    params::{self, Params},
+    progress,
+    repl::Repl,
+    report::{self, DayReport},
+    runner::{self, Failure, Report, Runner},
+    scale,
+    snapshot::{self, Pending},
+    solution, stats,
+};
+use clap::{Parser, Subcommand, ValueEnum};
+use itertools::Itertools;
+use serde_json::json;
+#[cfg(feature = "net")]
+use std::path::Path;
+use std::{
+    fs,
+    io::{self, BufRead, Write},
+    path::PathBuf,
+    process,
+    time::Duration,
+};
+
+#[cfg(feature = "net")]
+const STATE_DIR: &str = ".aoc";
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about=None)]
+struct Cli {
+    /// Where to fetch inputs and submit answers, e.g. a local stub server
+    #[cfg(feature = "net")]
+    #[arg(long, env = "AOC_BASE_URL", default_value = net::DEFAULT_BASE_URL, global = true)]
+    base_url: String,
+
+    /// Where the dayN directories of inputs live (or AOC_INPUT_DIR, default input/)
+    #[arg(long, global = true)]
+    input_dir: Option<PathBuf>,
+
+    /// How to print results (or AOC_FORMAT, default text)
+    #[arg(long, value_enum, global = true)]
+    format: Option<Format>,
+
+    /// Don't show progress
+    #[arg(short, long, global = true)]
+    quiet: bool,
+
+    /// How many threads the parallel parts of solutions use (or AOC_THREADS, default one per core)
+    #[arg(long, global = true, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+
+    /// Count the work each part does and show the totals (implies --no-cache)
+    #[arg(long, global = true)]
+    stats: bool,
+
+    /// Flag parts that take longer than this, e.g. 1s (or AOC_SLOW)
+    #[arg(long, global = true, value_parser = runner::parse_duration)]
+    slow: Option<Duration>,
+
+    /// Where to load plugin libraries from (or AOC_PLUGIN_DIR, default plugins/)
+    #[cfg(feature = "plugins")]
+    #[arg(long, global = true)]
+    plugin_dir: Option<PathBuf>,
+
+    #[command(subcommand)]
+    command: AocCommand,
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    Json,
+}
+
+impl Format {
+    fn name(self) -> &'static str {
+        match self {
+            Format::Text => "text",
+            Format::Json => "json",
+        }
+    }
+}
+
+#[derive(Subcommand, Debug)]
+enum AocCommand {
+    /// Download a day's puzzle input into dayN/input.txt in the input directory (only once)
+    #[cfg(feature = "net")]
+    Fetch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// Submit an answer, running the day's solution to get it if none is given
+    #[cfg(feature = "net")]
+    Submit {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        answer: Option<String>,
+    },
+    /// Run a day's solution, reusing cached answers if neither the code nor the input changed
+    Run {
+        /// One or more days; a panic or failure in one doesn't stop the rest
+        #[arg(required = true, value_parser = clap::value_parser!(u8).range(1..=25))]
+        days: Vec<u8>,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Only run this part
+        #[arg(short, long, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: Option<u8>,
+        /// Which implementation to run, if there's more than one
+        #[arg(long = "impl")]
+        variant: Option<String>,
+        /// Run every implementation of each part (plugins too) and compare them with the default,
+        /// never from the cache
+        #[arg(long, conflicts_with = "variant")]
+        compare: bool,
+        /// Work the answers out again even if they're cached
+        #[arg(long)]
+        no_cache: bool,
+        /// Give up on a part after this long, e.g. 30s
+        #[arg(long, value_parser = runner::parse_duration)]
+        timeout: Option<Duration>,
+        /// Override one of the day's parameters, e.g. --param max_x=11
+        #[arg(long = "param", value_parser = params::parse_assignment)]
+        params: Vec<(String, String)>,
+    },
+    /// Run both parts on a directory of inputs, or the files matching a pattern, in parallel
+    Batch {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        /// A directory, a file, or a pattern such as 'input/day9/test*.txt' (defaults to the
+        /// day's directory in the input directory)
+        inputs: Option<String>,
+        /// Compare the answers against the answers.json next to each input
+        #[arg(long)]
+        check: bool,
+        /// Work the answers out again even if they're cached
+        #[arg(long)]
+        no_cache: bool,
+        /// Give up on a part after this long, e.g. 30s
+        #[arg(long, value_parser = runner::parse_duration)]
+        timeout: Option<Duration>,
+        /// Override one of the day's parameters, e.g. --param max_x=11
+        #[arg(long = "param", value_parser = params::parse_assignment)]
+        params: Vec<(String, String)>,
+    },
+    /// Run every implementation of every day and write the answers, timings and pictures to a
+    /// single HTML page
+    Report {
+        /// Where to write the page
+        #[arg(long)]
+        html: PathBuf,
+        /// Which days to run (defaults to every solved day)
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        days: Vec<u8>,
+        /// Run on each day's example N instead, i.e. dayN/testN.txt (example 1 is test.txt)
+        #[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+        example: Option<u8>,
+        /// Give up on a part after this long, e.g. 30s
+        #[arg(long, value_parser = runner::parse_duration)]
+        timeout: Option<Duration>,
+    },
+    /// Time a part on bigger and bigger copies of its input and fit how its running time grows
+    Scale {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Which part to time
+        #[arg(short, long, default_value_t = 2, value_parser = clap::value_parser!(u8).range(1..=2))]
+        part: u8,
+        /// How many times bigger than the input to make each run's input
+        #[arg(long, value_delimiter = ',', default_values_t = [1, 2, 4, 8], value_parser = clap::value_parser!(u64).range(1..))]
+        factors: Vec<u64>,
+        /// Time each size this many times and keep the fastest
+        #[arg(long, default_value_t = 3, value_parser = clap::value_parser!(u64).range(1..))]
+        runs: u64,
+        /// Which implementation to time, if there's more than one
+        #[arg(long = "impl")]
+        variant: Option<String>,
+        /// Give up on a size after this long, e.g. 30s
+        #[arg(long, value_parser = runner::parse_duration)]
+        timeout: Option<Duration>,
+        /// Override one of the day's parameters, e.g. --param max_x=11
+        #[arg(long = "param", value_parser = params::parse_assignment)]
+        params: Vec<(String, String)>,
+    },
+    /// List the parameters a day can be run with, and their defaults
+    Params {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+    },
+    /// List the plugins loaded from the plugin directory
+    #[cfg(feature = "plugins")]
+    Plugins,
+    /// Answer puzzles over HTTP on localhost: POST an input to /day/N/part/P, or GET /days to see
+    /// what's solved
+    #[cfg(feature = "serve")]
+    Serve {
+        /// The port to listen on
+        #[arg(long, default_value_t = serve::DEFAULT_PORT)]
+        port: u16,
+        /// Give up on a part after this long, e.g. 30s (default 30s)
+        #[arg(long, value_parser = runner::parse_duration)]
+        timeout: Option<Duration>,
+        /// The biggest input accepted, in bytes
+        #[arg(long, default_value_t = serve::DEFAULT_MAX_BODY)]
+        max_body: usize,
+        /// How many parts can be solved at once; any more requests are turned away (default: one
+        /// per CPU)
+        #[arg(long, value_parser = clap::value_parser!(u64).range(1..))]
+        max_concurrent: Option<u64>,
+    },
+    /// Manage the cache of answers
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+    /// Inspect the settings from aoc.toml, ~/.config/aoc/config.toml, the environment and flags
+    Config {
+        #[command(subcommand)]
+        command: ConfigCommand,
+    },
+    /// Look through the grid snapshots tests saved because they didn't match, and accept or
+    /// reject them
+    Snapshots {
+        /// Where the golden files live (or AOC_SNAPSHOT_DIR, default tests/snapshots/)
+        #[arg(long)]
+        dir: Option<PathBuf>,
+        #[command(subcommand)]
+        command: SnapshotCommand,
+    },
+    /// Parse a day's input once, then run parts, inspect the grid and tweak parameters
+    Repl {
+        #[arg(value_parser = clap::value_parser!(u8).range(1..=25))]
+        day: u8,
+        #[command(flatten)]
+        input: InputArgs,
+        /// Start with one of the day's parameters overridden, e.g. --param max_x=11
+        #[arg(long = "param", value_parser = params::parse_assignment)]
+        params: Vec<(String, String)>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum CacheCommand {
+    /// Delete every cached answer
+    Clear,
+}
+
+#[derive(Subcommand, Debug)]
+enum SnapshotCommand {
+    /// List the snapshots waiting for review
+    List,
+    /// Show how each waiting snapshot differs from its golden file, and ask what to do with it
+    Review,
+    /// Make waiting snapshots (all of them, or the ones named) the golden files
+    Accept { names: Vec<String> },
+    /// Throw waiting snapshots (all of them, or the ones named) away
+    Reject { names: Vec<String> },
+}
+
+#[derive(Subcommand, Debug)]
+enum ConfigCommand {
+    /// Print every setting after merging all the layers, and where each one came from
+    Show,
+}
+
+#[cfg(feature = "net")]
+fn client(base_url: &str, config: &Config) -> Result<Client, NetError> {
+    let session = config
+        .session
+        .clone()
+        .or_else(net::find_session)
+        .ok_or(NetError::NoSession)?;
+    Ok(Client::new(base_url, &session, STATE_DIR))
+}
+
+/// Works out an answer with the day's default implementation, through the same cache as `run`.
+#[cfg(feature = "net")]
+fn solve(day: u8, part: u8, input: &Path) -> Result<Answer, String> {
+    let solution = solution::get(day).ok_or_else(|| format!("day {} isn't solved yet", day))?;
+    let content = input::read(input, day)?;
+    let report = Runner::new()
+        .with_cache(Cache::new(cache::DEFAULT_DIR), true)
+        .run(solution, &content, &[part], None)?;
+    report.parts[0].answer.clone().map_err(|e| e.to_string())
+}
+
+fn human_size(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else {
+        format!("{:.1} KiB", bytes as f64 / 1024.0)
+    }
+}
+
+/// Prints one day's results for `run`, under a heading if there are several days.
+fn print_run(day: u8, report: &Report, heading: bool, format: Format, slow: Option<Duration>) {
+    match format {
+        Format::Text => {
+            if heading {
+                println!("Day {}:", day);
+            }
+            for result in report.parts.iter() {
+                match &result.answer {
+                    Ok(answer) if result.cached => {
+                        println!("Part {}: {} (cached)", result.part, answer)
+                    }
+                    Ok(answer) => {
+                        let mut notes = vec![format!("{:?}", result.elapsed)];
+                        if let Some(slow) = slow.filter(|&s| result.elapsed > s) {
+                            notes.push(format!("slower than {:?}", slow));
+                        }
+                        if let Some(memory) = result.memory {
+                            notes.push(memory.to_string());
+                        }
+                        println!("Part {}: {} ({})", result.part, answer, notes.join(", "))
+                    }
+                    Err(failure) => println!("Part {}: {}", result.part, failure),
+                }
+                solution::print_counts(result.counts.as_ref());
+            }
+        }
+        Format::Json => {
+            let parts = report
+                .parts
+                .iter()
+                .map(|result| {
+                    json!({
+                        "part": result.part,
+                        "impl": result.variant,
+                        "answer": result.answer.as_ref().ok(),
+                        "error": result.answer.as_ref().err().map(|e| e.to_string()),
+                        "panic": match &result.answer {
+                            Err(Failure::Panicked { message, location }) => {
+                                json!({ "message": message, "location": location })
+                            }
+                            _ => json!(null),
+                        },
+                        "seconds": result.elapsed.as_secs_f64(),
+                        "memory": result.memory,
+                        "counts": result.counts,
+                        "cached": result.cached,
+                    })
+                })
+                .collect::<Vec<_>>();
+            let output = json!({
+                "day": day,
+                "parse_seconds": report.parse.map(|parse| parse.as_secs_f64()),
+                "parse_memory": report.parse_memory,
+                "parts": parts,
+            });
+            println!("{}", output);
+        }
+    }
+}
+
+/// Runs every implementation of each of `parts` in turn, defaults first, as one report.
+fn run_every_variant(
+    runner: &Runner,
+    solution: &'static dyn solution::DynSolution,
+    content: &str,
+    parts: &[u8],
+) -> Result<Report, String> {
+    let mut runs = Vec::new();
+    for &part in parts {
+        let variants = solution
+            .variants()
+            .into_iter()
+            .filter(|&(p, _)| p == part)
+            .map(|(_, name)| (part, Some(name)))
+            .collect::<Vec<_>>();
+        if variants.is_empty() {
+            return Err(format!(
+                "day {} part {} isn't implemented",
+                solution.day(),
+                part
+            ));
+        }
+        runs.extend(variants);
+    }
+    runner.run_each(solution, content, &runs)
+}
+
+/// Whether every implementation of each part came up with the same answer as its default.
+fn agrees(report: &Report) -> bool {
+    report.parts.iter().all(|result| {
+        let default = report.parts.iter().find(|other| other.part == result.part);
+        default.is_none_or(|default| default.answer.as_ref().ok() == result.answer.as_ref().ok())
+    })
+}
+
+/// `run --compare`'s table: every implementation's answer and time next to its part's default.
+fn print_comparison(day: u8, report: &Report, heading: bool) {
+    if heading {
+        println!("Day {}:", day);
+    }
+    let rows = report
+        .parts
+        .iter()
+        .map(|result| {
+            let default = report
+                .parts
+                .iter()
+                .find(|other| other.part == result.part)
+                .expect("a part's results include its default");
+            let answer = match &result.answer {
+                Ok(answer) => answer.to_string(),
+                Err(failure) => failure.to_string(),
+            };
+            // A failure's already in the answer column, and there's no time to compare.
+            let versus = if std::ptr::eq(default, result)
+                || default.answer.is_err()
+                || result.answer.is_err()
+            {
+                String::new()
+            } else if default.answer.as_ref().ok() != result.answer.as_ref().ok() {
+                format!("DIFFERENT from {}", default.variant)
+            } else {
+                let ratio = default.elapsed.as_secs_f64() / result.elapsed.as_secs_f64();
+                if ratio >= 1.0 {
+                    format!("{:.2}x faster", ratio)
+                } else {
+                    format!("{:.2}x slower", 1.0 / ratio)
+                }
+            };
+            vec![
+                result.part.to_string(),
+                result.variant.to_string(),
+                answer,
+                format!("{:.2?}", result.elapsed),
+                versus,
+            ]
+        })
+        .collect::<Vec<_>>();
+    print_table(&["part", "impl", "answer", "time", "vs default"], &rows);
+}
+
+/// One row of `batch`'s table: file, size, then the answer and timing of each part.
+fn batch_row(file: &FileResult, check: bool) -> Vec<String> {
+    let mut row = vec![file.path.display().to_string(), human_size(file.size)];
+    let parts = match &file.outcome {
+        Ok(parts) => parts,
+        Err(e) => {
+            row.push(format!("error: {}", e));
+            return row;
+        }
+    };
+    for part in parts.iter() {
+        match &part.result.answer {
+            Ok(answer) => row.push(answer.to_string()),
+            Err(failure) => row.push(failure.to_string()),
+        }
+        row.push(if part.result.cached {
+            "cached".to_string()
+        } else {
+            format!("{:.2?}", part.result.elapsed)
+        });
+    }
+    if check {
+        let wrong = parts
+            .iter()
+            .filter(|part| part.correct() == Some(false))
+            .map(|part| {
+                format!(
+                    "part {} should be {}",
+                    part.result.part,
+                    part.expected.as_ref().unwrap()
+                )
+            })
+            .collect::<Vec<_>>();
+        row.push(if !wrong.is_empty() {
+            wrong.join(", ")
+        } else if parts.iter().all(|part| part.expected.is_none()) {
+            "unknown".to_string()
+        } else {
+            "ok".to_string()
+        });
+    }
+    row
+}
+
+/// One row of `report`'s summary: the day, how many implementations ran, and how they checked out.
+fn report_row(report: &DayReport) -> Vec<String> {
+    let mut row = vec![report.day.to_string()];
+    let run = match &report.outcome {
+        Ok(run) => run,
+        Err(e) => {
+            row.push(format!("error: {}", e));
+            return row;
+        }
+    };
+    let count = |correct: Option<bool>| {
+        run.parts
+            .iter()
+            .filter(|part| part.correct() == correct)
+            .count()
+    };
+    row.push(run.parts.len().to_string());
+    row.push(format!(
+        "{} correct, {} wrong, {} unchecked",
+        count(Some(true)),
+        count(Some(false)),
+        count(None)
+    ));
+    row
+}
+
+/// The waiting snapshots called `names`, or all of them if there are no names.
+fn named<'a>(pending: &'a [Pending], names: &[String]) -> Result<Vec<&'a Pending>, String> {
+    if names.is_empty() {
+        return Ok(pending.iter().collect());
+    }
+    names
+        .iter()
+        .map(|name| {
+            pending
+                .iter()
+                .find(|snapshot| &snapshot.name == name)
+                .ok_or_else(|| format!("no snapshot called {:?} is waiting for review", name))
+        })
+        .collect()
+}
+
+/// Shows each waiting snapshot's diff and asks whether to accept it, reject it or leave it.
+fn review_snapshots(pending: &[Pending]) -> Result<(), String> {
+    if pending.is_empty() {
+        println!("No snapshots waiting for review");
+        return Ok(());
+    }
+    let colour = snapshot::colour(&io::stdout());
+    let mut answer = String::new();
+    for (i, snapshot) in pending.iter().enumerate() {
+        println!("[{}/{}] {}", i + 1, pending.len(), snapshot.name);
+        println!("{}", snapshot.describe(colour)?);
+        loop {
+            print!("[a]ccept, [r]eject, [s]kip or [q]uit? ");
+            io::stdout().flush().map_err(|e| e.to_string())?;
+            answer.clear();
+            if io::stdin()
+                .lock()
+                .read_line(&mut answer)
+                .map_err(|e| e.to_string())?
+                == 0
+            {
+                println!();
+                return Ok(());
+            }
+            match answer.trim() {
+                "a" | "accept" => snapshot.accept()?,
+                "r" | "reject" => snapshot.reject()?,
+                "s" | "skip" | "" => (),
+                "q" | "quit" => return Ok(()),
+                _ => continue,
+            }
+            break;
+        }
+    }
+    Ok(())
+}
+
+fn print_table(header: &[&str], rows: &[Vec<String>]) {
+    let mut widths = header.iter().map(|h| h.len()).collect::<Vec<_>>();
+    // Short rows (files that didn't run) let their last cell spill over the columns they skip.
+    for row in rows {
+        let measured = if row.len() < header.len() {
+            row.len() - 1
+        } else {
+            header.len()
+        };
+        for (i, cell) in row.iter().enumerate().take(measured) {
+            widths[i] = widths[i].max(cell.chars().count());
+        }
+    }
+    let line = |cells: Vec<&str>| {
+        let padded = cells
+            .iter()
+            .enumerate()
+            .map(|(i, cell)| match widths.get(i) {
+                Some(&width) => format!("{:width$}", cell, width = width),
+                None => cell.to_string(),
+            })
+            .collect::<Vec<_>>();
+        println!("{}", padded.join("  ").trim_end());
+    };
+    line(header.to_vec());
+    for row in rows {
+        line(row.iter().map(String::as_str).collect());
+    }
+}
+
+fn run(cli: Cli) -> Result<(), String> {
+    let mut config = Config::load()?;
+    config.apply(
+        Layer {
+            input_dir: cli.input_dir,
+            threads: cli.threads,
+            format: cli.format.map(|format| format.name().to_string()),
+            slow: cli.slow,
+            #[cfg(feature = "plugins")]
+            plugin_dir: cli.plugin_dir,
+            ..Layer::default()
+        },
+        Source::Flag,
+    );
+    #[cfg(feature = "plugins")]
+    {
+        let dir = config
+            .plugin_dir
+            .clone()
+            .unwrap_or_else(|| PathBuf::from(plugin::DEFAULT_DIR));
+        // SAFETY: the plugin directory holds code the user has chosen to run.
+        plugin::install(unsafe { plugin::load_dir(&dir)? });
+    }
+    let format = match config.format.as_deref() {
+        Some("json") => Format::Json,
+        _ => Format::Text,
+    };
+    if cli.quiet || format == Format::Json {
+        progress::set_enabled(false);
+    }
+    stats::set_enabled(cli.stats);
+    if let Some(threads) = config.threads {
+        parallel::set_threads(threads as usize)?;
+    }
+    match cli.command {
+        #[cfg(feature = "net")]
+        AocCommand::Fetch { day } => {
+            let path = input::real_path(&config.input_dir, day);
+            let (_, cached) = client(&cli.base_url, &config)
+                .and_then(|client| client.fetch_input_to(day, &path))
+                .map_err(|e| e.to_string())?;
+            if cached {
+                println!("Input for day {} already at {}", day, path.display());
+            } else {
+                println!("Downloaded input for day {} to {}", day, path.display());
+            }
+        }
+        #[cfg(feature = "net")]
+        AocCommand::Submit { day, part, answer } => {
+            let client = client(&cli.base_url, &config).map_err(|e| e.to_string())?;
+            let answer = match answer {
+                Some(answer) => Answer::from(answer),
+                None => {
+                    let path = input::real_path(&config.input_dir, day);
+                    client
+                        .fetch_input_to(day, &path)
+                        .map_err(|e| e.to_string())?;
+                    solve(day, part, &path)?
+                }
+            };
+            let outcome = client
+                .submit(day, part, &answer)
+                .map_err(|e| e.to_string())?;
+            println!("Day {} part {}: {} is {}", day, part, answer, outcome);
+        }
+        AocCommand::Run {
+            days,
+            input,
+            part,
+            variant,
+            compare,
+            no_cache,
+            timeout,
+            params,
+        } => {
+            if input.input.is_some() && days.len() > 1 {
+                return Err("--input can only be used with a single day".to_string());
+            }
+            let parts = match part {
+                Some(part) => vec![part],
+                None => vec![1, 2],
+            };
+            let params = params.into_iter().collect::<Params>();
+            let mut failed = Vec::new();
+            let mut disagree = Vec::new();
+            for &day in days.iter() {
+                let report = solution::get(day)
+                    .ok_or_else(|| format!("day {} isn't solved yet", day))
+                    .and_then(|solution| {
+                        let content = input::read(&input.resolve(&config.input_dir, day), day)?;
+                        let runner = Runner::new()
+                            .with_params(config.params(day, params.clone()))
+                            .with_cache(
+                                Cache::new(cache::DEFAULT_DIR),
+                                !no_cache && !cli.stats && !compare,
+                            )
+                            .with_timeout(timeout.or(config.timeout));
+                        if compare {
+                            run_every_variant(&runner, solution, &content, &parts)
+                        } else {
+                            runner.run(solution, &content, &parts, variant.as_deref())
+                        }
+                    });
+                let report = match report {
+                    Ok(report) => report,
+                    // A single day's error is the whole command's error, as before.
+                    Err(e) if days.len() == 1 => return Err(e),
+                    Err(e) => {
+                        match format {
+                            Format::Text => println!("Day {}: {}", day, e),
+                            Format::Json => println!("{}", json!({ "day": day, "error": e })),
+                        }
+                        failed.push(day);
+                        continue;
+                    }
+                };
+                if compare && format == Format::Text {
+                    print_comparison(day, &report, days.len() > 1);
+                } else {
+                    print_run(day, &report, days.len() > 1, format, config.slow);
+                }
+                if report.parts.iter().any(|result| result.answer.is_err()) {
+                    failed.push(day);
+                } else if compare && !agrees(&report) {
+                    disagree.push(day);
+                }
+            }
+            match failed.as_slice() {
+                [] => (),
+                [day] if days.len() == 1 => {
+                    return Err(format!("not every part of day {} finished", day))
+                }
+                failed => {
+                    return Err(format!(
+                        "{} of {} days didn't finish: {}",
+                        failed.len(),
+                        days.len(),
+                        failed.iter().map(|day| day.to_string()).join(", ")
+                    ))
+                }
+            }
+            if !disagree.is_empty() {
+                return Err(format!(
+                    "the implementations of day {} don't agree",
+                    disagree.iter().map(|day| day.to_string()).join(", ")
+                ));
+            }
+        }
+        AocCommand::Batch {
+            day,
+            inputs,
+            check,
+            no_cache,
+            timeout,
+            params,
+        } => {
+            let solution =
+                solution::get(day).ok_or_else(|| format!("day {} isn't solved yet", day))?;
+            let inputs = match inputs {
+                Some(inputs) => batch::find_inputs(&inputs)?,
+                None => batch::find_inputs(
+                    &config
+                        .input_dir
+                        .join(format!("day{}", day))
+                        .to_string_lossy(),
+                )?,
+            };
+            let runner = Runner::new()
+                .with_params(config.params(day, params.into_iter().collect()))
+                .with_cache(Cache::new(cache::DEFAULT_DIR), !no_cache && !cli.stats)
+                .with_timeout(timeout.or(config.timeout));
+            let results = batch::run(&runner, solution, &inputs, check)?;
+            match format {
+                Format::Text => {
+                    let mut header = vec!["file", "size", "part 1", "time", "part 2", "time"];
+                    if check {
+                        header.push("check");
+                    }
+                    let rows = results
+                        .iter()
+                        .map(|file| batch_row(file, check))
+                        .collect::<Vec<_>>();
+                    print_table(&header, &rows);
+                }
+                Format::Json => {
+                    let files = results
+                        .iter()
+                        .map(|file| {
+                            let parts = file.outcome.as_ref().ok().map(|parts| {
+                                parts
+                                    .iter()
+                                    .map(|part| {
+                                        json!({
+                                            "part": part.result.part,
+                                            "answer": part.result.answer.as_ref().ok(),
+                                            "error": part.result.answer.as_ref().err().map(|e| e.to_string()),
+                                            "seconds": part.result.elapsed.as_secs_f64(),
+                                            "memory": part.result.memory,
+                                            "counts": part.result.counts,
+                                            "cached": part.result.cached,
+                                            "expected": part.expected,
+                                            "correct": part.correct(),
+                                        })
+                                    })
+                                    .collect::<Vec<_>>()
+                            });
+                            json!({
+                                "file": file.path.display().to_string(),
+                                "size": file.size,
+                                "error": file.outcome.as_ref().err(),
+                                "parts": parts,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    println!("{}", json!({ "day": day, "files": files }));
+                }
+            }
+            let failed = results.iter().filter(|file| !file.ok()).count();
+            if failed > 0 {
+                return Err(format!("{} of {} inputs failed", failed, results.len()));
+            }
+        }
+        AocCommand::Report {
+            html,
+            days,
+            example,
+            timeout,
+        } => {
+            let solutions = if days.is_empty() {
+                solution::all()
+            } else {
+                let mut days = days;
+                days.sort();
+                days.dedup();
+                days.iter()
+                    .map(|&day| {
+                        solution::get(day).ok_or_else(|| format!("day {} isn't solved yet", day))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?
+            };
+            // One day at a time, and never from the cache, so the timings mean something.
+            let reports = solutions
+                .iter()
+                .map(|&solution| {
+                    let day = solution.day();
+                    let input = match example {
+                        Some(n) => input::example_path(&config.input_dir, day, n),
+                        None => input::real_path(&config.input_dir, day),
+                    };
+                    let runner = Runner::new()
+                        .with_params(config.params(day, Params::new()))
+                        .with_timeout(timeout.or(config.timeout));
+                    report::run_day(&runner, solution, &input)
+                })
+                .collect::<Vec<_>>();
+            fs::write(&html, report::to_html(&reports))
+                .map_err(|e| format!("couldn't write {}: {}", html.display(), e))?;
+            match format {
+                Format::Text => {
+                    let rows = reports.iter().map(report_row).collect::<Vec<_>>();
+                    print_table(&["day", "runs", "check"], &rows);
+                    println!("Wrote {}", html.display());
+                }
+                Format::Json => println!("{}", report::to_json(&reports)),
+            }
+            let checked = reports
+                .iter()
+                .filter_map(|report| report.outcome.as_ref().ok())
+                .flat_map(|run| run.parts.iter())
+                .filter_map(|part| part.correct())
+                .collect::<Vec<_>>();
+            let wrong = checked.iter().filter(|&&correct| !correct).count();
+            if wrong > 0 {
+                return Err(format!("{} of {} answers were wrong", wrong, checked.len()));
+            }
+        }
+        AocCommand::Scale {
+            day,
+            input,
+            part,
+            factors,
+            runs,
+            variant,
+            timeout,
+            params,
+        } => {
+            let solution =
+                solution::get(day).ok_or_else(|| format!("day {} isn't solved yet", day))?;
+            let input = input.resolve(&config.input_dir, day);
+            let content = input::read(&input, day)?;
+            let runner = Runner::new()
+                .with_params(config.params(day, params.into_iter().collect()))
+                .with_timeout(timeout.or(config.timeout));
+            let factors = factors.iter().map(|&f| f as usize).collect::<Vec<_>>();
+            let samples = scale::run(
+                &runner,
+                solution,
+                &content,
+                part,
+                variant.as_deref(),
+                &factors,
+                runs as usize,
+            )?;
+            let fit = scale::fit(&samples);
+            match format {
+                Format::Text => {
+                    let rows = samples
+                        .iter()
+                        .map(|sample| {
+                            vec![
+                                format!("{}×", sample.factor),
+                                human_size(sample.size as u64),
+                                format!("{:.2?}", sample.elapsed),
+                                sample.answer.to_string(),
+                            ]
+                        })
+                        .collect::<Vec<_>>();
+                    print_table(&["factor", "size", "time", "answer"], &rows);
+                    match fit {
+                        Some(fit) => println!(
+                            "Day {} part {} grows like n^{:.2}, {} (R² {:.3})",
+                            day,
+                            part,
+                            fit.exponent,
+                            fit.describe(),
+                            fit.r_squared
+                        ),
+                        None => println!("Not enough sizes to fit anything"),
+                    }
+                }
+                Format::Json => {
+                    let samples = samples
+                        .iter()
+                        .map(|sample| {
+                            json!({
+                                "factor": sample.factor,
+                                "size": sample.size,
+                                "seconds": sample.elapsed.as_secs_f64(),
+                                "answer": sample.answer,
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    let fit = fit.map(|fit| {
+                        json!({
+                            "exponent": fit.exponent,
+                            "coefficient_seconds": fit.coefficient,
+                            "r_squared": fit.r_squared,
+                        })
+                    });
+                    println!(
+                        "{}",
+                        json!({ "day": day, "part": part, "samples": samples, "fit": fit })
+                    );
+                }
+            }
+        }
+        AocCommand::Params { day } => {
+            let solution =
+                solution::get(day).ok_or_else(|| format!("day {} isn't solved yet", day))?;
+            let specs = solution.params();
+            match format {
+                Format::Text => {
+                    if specs.is_empty() {
+                        println!("Day {} has no parameters", day);
+                    }
+                    for spec in specs.iter() {
+                        let range = match &spec.range {
+                            Some((min, max)) => format!(", {} to {}", min, max),
+                            None => String::new(),
+                        };
+                        println!(
+                            "{} ({}, default {}{}): {}",
+                            spec.name, spec.kind, spec.default, range, spec.doc
+                        );
+                    }
+                }
+                Format::Json => {
+                    println!("{}", json!({ "day": day, "params": specs }));
+                }
+            }
+        }
+        #[cfg(feature = "plugins")]
+        AocCommand::Plugins => {
+            let plugins = plugin::installed();
+            match format {
+                Format::Text if plugins.is_empty() => println!("No plugins loaded"),
+                Format::Text => {
+                    let rows = plugins
+                        .iter()
+                        .map(|plugin| {
+                            vec![
+                                plugin.day.to_string(),
+                                plugin.name.to_string(),
+                                plugin.parts().iter().join(", "),
+                                plugin.path.display().to_string(),
+                            ]
+                        })
+                        .collect::<Vec<_>>();
+                    print_table(&["day", "impl", "parts", "library"], &rows);
+                }
+                Format::Json => {
+                    let plugins = plugins
+                        .iter()
+                        .map(|plugin| {
+                            json!({
+                                "day": plugin.day,
+                                "impl": plugin.name,
+                                "parts": plugin.parts(),
+                                "library": plugin.path.display().to_string(),
+                            })
+                        })
+                        .collect::<Vec<_>>();
+                    println!("{}", json!(plugins));
+                }
+            }
+        }
+        #[cfg(feature = "serve")]
+        AocCommand::Serve {
+            port,
+            timeout,
+            max_body,
+            max_concurrent,
+        } => {
+            let defaults = Limits::default();
+            let limits = Limits {
+                timeout: timeout.or(config.timeout).unwrap_or(defaults.timeout),
+                max_body,
+                max_concurrent: max_concurrent.map_or(defaults.max_concurrent, |n| n as usize),
+            };
+            // Requests are solved side by side, so their bars would only get in each other's way.
+            progress::set_enabled(false);
+            let server = Server::bind(port, limits)?
+                .with_config(config.clone())
+                .with_log(!cli.quiet);
+            println!("Listening on http://{}", server.addr());
+            server.run();
+        }
+        AocCommand::Cache {
+            command: CacheCommand::Clear,
+        } => {
+            let removed = Cache::new(cache::DEFAULT_DIR)
+                .clear()
+                .map_err(|e| format!("couldn't clear the cache: {}", e))?;
+            println!("Removed {} cached answers", removed);
+        }
+        AocCommand::Config {
+            command: ConfigCommand::Show,
+        } => match format {
+            Format::Text => {
+                let rows = config
+                    .entries()
+                    .into_iter()
+                    .map(|(key, value, source)| {
+                        let source = source.describe(&key);
+                        vec![key, value, source]
+                    })
+                    .collect::<Vec<_>>();
+                print_table(&["setting", "value", "from"], &rows);
+            }
+            Format::Json => {
+                let entries = config
+                    .entries()
+                    .into_iter()
+                    .map(|(key, value, source)| {
+                        let source = source.describe(&key);
+                        json!({ "setting": key, "value": value, "from": source })
+                    })
+                    .collect::<Vec<_>>();
+                println!("{}", json!(entries));
+            }
+        },
+        AocCommand::Snapshots { dir, command } => {
+            let dir = dir.unwrap_or_else(|| config.snapshot_dir.clone());
+            let pending = snapshot::pending(&dir)?;
+            match command {
+                SnapshotCommand::List => {
+                    if pending.is_empty() {
+                        println!("No snapshots waiting for review");
+                    }
+                    for snapshot in &pending {
+                        let status = if snapshot.golden.exists() {
+                            "changed"
+                        } else {
+                            "new"
+                        };
+                        println!("{} ({})", snapshot.name, status);
+                    }
+                }
+                SnapshotCommand::Review => review_snapshots(&pending)?,
+                SnapshotCommand::Accept { names } => {
+                    for snapshot in named(&pending, &names)? {
+                        snapshot.accept()?;
+                        println!("Accepted {}", snapshot.name);
+                    }
+                }
+                SnapshotCommand::Reject { names } => {
+                    for snapshot in named(&pending, &names)? {
+                        snapshot.reject()?;
+                        println!("Rejected {}", snapshot.name);
+                    }
+                }
+            }
+        }
+        AocCommand::Repl { day, input, params } => {
+            let solution =
+                solution::get(day).ok_or_else(|| format!("day {} isn't solved yet", day))?;
+            let input = input.resolve(&config.input_dir, day);
+            let content = input::read(&input, day)?;
+            let mut repl = Repl::new(solution, &content)
+                .map_err(|e| format!("couldn't parse {}: {}", input.display(), e))?
+                .with_params(config.params(day, params.into_iter().collect::<Params>()))?;
+            println!("Parsed day {} input, `help` for commands", day);
+            repl.run(io::stdin().lock(), io::stdout())
+                .map_err(|e| e.to_string())?;
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let cli = Cli::parse();
+    if let Err(e) = run(cli) {
+        eprintln!("error: {}", e);
+        process::exit(1);
+    }
+}
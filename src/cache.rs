@@ -0,0 +1,122 @@
+use crate::answer::Answer;
+use crate::params::Params;
+use std::{
+    collections::hash_map::DefaultHasher,
+    env, fs,
+    hash::{Hash, Hasher},
+    io,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        OnceLock,
+    },
+};
+
+pub const DEFAULT_DIR: &str = ".aoc/cache";
+
+/// What an answer being written is called until it's renamed, followed by the writer's pid and
+/// which of its writes it is.
+const TMP: &str = "tmp";
+
+/// Counts writes, so threads saving the same answer at once each have a file of their own.
+static WRITES: AtomicU64 = AtomicU64::new(0);
+
+/// Everything an answer depends on. Changing any of it means the answer has to be worked out again.
+#[derive(Debug, Hash)]
+pub struct Key<'a> {
+    pub day: u8,
+    pub part: u8,
+    pub variant: &'a str,
+    pub input: &'a str,
+    pub params: &'a Params,
+}
+
+/// Answers saved on disk, one file per key, under `dir/dayN/`.
+#[derive(Debug, Clone)]
+pub struct Cache {
+    dir: PathBuf,
+}
+
+impl Cache {
+    pub fn new(dir: impl AsRef<Path>) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+        }
+    }
+
+    pub fn get(&self, key: &Key) -> Option<Answer> {
+        let answer = fs::read_to_string(self.path_of(key)).ok()?;
+        Some(Answer::from(answer))
+    }
+
+    pub fn put(&self, key: &Key, answer: &Answer) -> io::Result<()> {
+        let path = self.path_of(key);
+        fs::create_dir_all(path.parent().unwrap())?;
+        // Written to the side and renamed so a reader never sees half an answer.
+        let tmp = path.with_extension(format!(
+            "{}{}-{}",
+            TMP,
+            std::process::id(),
+            WRITES.fetch_add(1, Ordering::Relaxed)
+        ));
+        fs::write(&tmp, answer.to_string())?;
+        fs::rename(&tmp, &path)
+    }
+
+    /// Deletes every saved answer, returning how many there were. Answers still being written
+    /// (or left half-written) are deleted too, but don't count.
+    pub fn clear(&self) -> io::Result<usize> {
+        let mut removed = 0;
+        let days = match fs::read_dir(&self.dir) {
+            Ok(days) => days,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(e) => return Err(e),
+        };
+        for day in days {
+            let day = day?.path();
+            if day.is_dir() {
+                for entry in fs::read_dir(&day)? {
+                    if !is_being_written(&entry?.path()) {
+                        removed += 1;
+                    }
+                }
+            }
+        }
+        fs::remove_dir_all(&self.dir)?;
+        Ok(removed)
+    }
+
+    fn path_of(&self, key: &Key) -> PathBuf {
+        let mut hasher = DefaultHasher::new();
+        key.hash(&mut hasher);
+        code_fingerprint().hash(&mut hasher);
+        self.dir.join(format!("day{}", key.day)).join(format!(
+            "part{}-{}-{:016x}",
+            key.part,
+            key.variant,
+            hasher.finish()
+        ))
+    }
+}
+
+fn is_being_written(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str()?.strip_prefix(TMP)?.split_once('-'))
+        .is_some_and(|(pid, write)| pid.parse::<u32>().is_ok() && write.parse::<u64>().is_ok())
+}
+
+/// Identifies the running executable by its path, size and when it was built, so rebuilding with
+/// different code invalidates old answers without reading the whole binary every run.
+fn code_fingerprint() -> u64 {
+    static FINGERPRINT: OnceLock<u64> = OnceLock::new();
+    *FINGERPRINT.get_or_init(|| {
+        let mut hasher = DefaultHasher::new();
+        let exe = env::current_exe().and_then(|exe| Ok((fs::metadata(&exe)?, exe)));
+        match exe.and_then(|(metadata, exe)| Ok((metadata.len(), metadata.modified()?, exe))) {
+            Ok(identity) => identity.hash(&mut hasher),
+            // Without the binary to go on, never match a previous run.
+            Err(_) => std::process::id().hash(&mut hasher),
+        }
+        hasher.finish()
+    })
+}
@@ -0,0 +1,74 @@
+use std::{
+    cell::RefCell,
+    panic,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Once,
+    },
+};
+
+/// Set by the runner when a part has run out of time. Long loops check it and give up.
+#[derive(Debug, Clone, Default)]
+pub struct CancelToken {
+    cancelled: Arc<AtomicBool>,
+}
+
+/// The panic payload that unwinds a cancelled part back to the runner.
+#[derive(Debug)]
+pub struct Cancelled;
+
+thread_local! {
+    static CURRENT: RefCell<CancelToken> = RefCell::new(CancelToken::default());
+}
+
+impl CancelToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.cancelled.load(Ordering::Relaxed)
+    }
+
+    /// Stops the part (by unwinding with `Cancelled`) if it's been cancelled.
+    pub fn check(&self) {
+        if self.is_cancelled() {
+            panic::panic_any(Cancelled);
+        }
+    }
+}
+
+/// Makes `token` the one `check` looks at on this thread.
+pub fn set_current(token: CancelToken) {
+    silence_cancellations();
+    CURRENT.with(|current| *current.borrow_mut() = token);
+}
+
+/// The token for the part running on this thread. Hand it to any threads the part starts itself
+/// (e.g. inside a rayon `par_iter`), as they won't see it otherwise.
+pub fn current() -> CancelToken {
+    CURRENT.with(|current| current.borrow().clone())
+}
+
+/// Stops the part running on this thread if it's been cancelled. Cheap enough to call every
+/// iteration of a long loop.
+pub fn check() {
+    CURRENT.with(|current| current.borrow().check());
+}
+
+/// Cancelling isn't an error, so keep the panic hook from printing anything for it.
+fn silence_cancellations() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if !info.payload().is::<Cancelled>() {
+                hook(info);
+            }
+        }));
+    });
+}
@@ -0,0 +1,64 @@
+//! `HashMap` and `HashSet` with a fixed hasher, used by the solutions instead of std's. Std's
+//! are seeded randomly, so they iterate in a different order every run, and so do the traces and
+//! any "first one found" answers that come from iterating them. These iterate in the same order
+//! every time they're built the same way. They hash just as fast.
+//!
+//! Build them with `default()` (or `collect`/`from_iter`), as `new()` only exists for std's hasher.
+
+use std::{collections, hash::BuildHasherDefault, hash::DefaultHasher};
+
+/// A hasher that's the same every run.
+pub type FixedState = BuildHasherDefault<DefaultHasher>;
+
+pub type HashMap<K, V> = collections::HashMap<K, V, FixedState>;
+
+pub type HashSet<T> = collections::HashSet<T, FixedState>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::hash::BuildHasher;
+
+    #[test]
+    fn hashes_the_same_every_run() {
+        // A randomly seeded hasher would give something else every run (every map, in fact).
+        // This only changes if std changes `DefaultHasher`'s algorithm, and with it the order.
+        assert_eq!(FixedState::default().hash_one(42u64), 8880661182590738257);
+        assert_eq!(
+            FixedState::default().hash_one("day6"),
+            FixedState::default().hash_one("day6")
+        );
+    }
+
+    #[test]
+    fn iterates_in_the_same_order_when_built_the_same_way() {
+        let build = || {
+            (0..1000u32)
+                .map(|n| (n.wrapping_mul(2654435761), n))
+                .collect::<HashMap<_, _>>()
+        };
+        let (first, second) = (build(), build());
+        assert_eq!(
+            first.iter().collect::<Vec<_>>(),
+            second.iter().collect::<Vec<_>>()
+        );
+        // It isn't just insertion order (or sorted order) coming back out.
+        let keys = first.keys().copied().collect::<Vec<_>>();
+        assert_ne!(
+            keys,
+            (0..1000u32)
+                .map(|n| n.wrapping_mul(2654435761))
+                .collect::<Vec<_>>()
+        );
+        let mut sorted = keys.clone();
+        sorted.sort();
+        assert_ne!(keys, sorted);
+
+        let set = |words: &[&'static str]| words.iter().copied().collect::<HashSet<_>>();
+        let words = ["north", "east", "south", "west", "up", "down"];
+        assert_eq!(
+            set(&words).into_iter().collect::<Vec<_>>(),
+            set(&words).into_iter().collect::<Vec<_>>()
+        );
+    }
+}
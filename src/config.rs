@@ -0,0 +1,490 @@
+use crate::input;
+use crate::params::Params;
+use crate::runner;
+use crate::snapshot;
+use std::{
+    collections::BTreeMap,
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// The config file in the repo, read from the directory `aoc` is run in.
+pub const REPO_FILE: &str = "aoc.toml";
+
+/// The settings that can be given in config files, the environment and flags. Each layer only
+/// overrides what it mentions:
+///
+/// ```toml
+/// input_dir = "~/aoc/inputs"
+/// threads = 4
+/// timeout = "30s"
+///
+/// [day14]
+/// max_x = 11
+/// max_y = 7
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Layer {
+    pub session: Option<String>,
+    pub input_dir: Option<PathBuf>,
+    pub threads: Option<u16>,
+    pub format: Option<String>,
+    pub timeout: Option<Duration>,
+    /// Parts that take longer than this are flagged as slow.
+    pub slow: Option<Duration>,
+    /// Where plugins are loaded from (default `plugins/`).
+    pub plugin_dir: Option<PathBuf>,
+    /// Where the golden snapshot files live (default `tests/snapshots/`).
+    pub snapshot_dir: Option<PathBuf>,
+    pub params: BTreeMap<u8, Params>,
+}
+
+/// Which layer a setting's value came from.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Source {
+    Default,
+    File(PathBuf),
+    Env,
+    Flag,
+}
+
+impl Source {
+    /// Where exactly `key` was set, e.g. `$AOC_THREADS` or `--input-dir`.
+    pub fn describe(&self, key: &str) -> String {
+        match self {
+            Source::Default => "default".to_string(),
+            Source::File(path) => path.display().to_string(),
+            Source::Env => format!("$AOC_{}", key.to_uppercase()),
+            Source::Flag if key.contains('.') => "--param".to_string(),
+            Source::Flag => format!("--{}", key.replace('_', "-")),
+        }
+    }
+}
+
+/// Every layer merged: `aoc.toml`, then `~/.config/aoc/config.toml`, then the environment, then
+/// flags, each overriding the ones before it.
+#[derive(Debug, Clone)]
+pub struct Config {
+    pub session: Option<String>,
+    pub input_dir: PathBuf,
+    pub threads: Option<u16>,
+    pub format: Option<String>,
+    pub timeout: Option<Duration>,
+    pub slow: Option<Duration>,
+    pub plugin_dir: Option<PathBuf>,
+    pub snapshot_dir: PathBuf,
+    params: BTreeMap<u8, Params>,
+    /// Which layer each setting was last set by, keyed like `threads` or `day14.max_x`.
+    sources: BTreeMap<String, Source>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            session: None,
+            input_dir: PathBuf::from(input::DEFAULT_DIR),
+            threads: None,
+            format: None,
+            timeout: None,
+            slow: None,
+            plugin_dir: None,
+            snapshot_dir: PathBuf::from(snapshot::DEFAULT_DIR),
+            params: BTreeMap::new(),
+            sources: BTreeMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// The config files and environment merged, ready for flags to be applied on top.
+    pub fn load() -> Result<Self, String> {
+        Self::load_from(&Self::files(), Layer::from_env()?)
+    }
+
+    /// `files` (lowest priority first, skipping any that aren't there) merged, then `env`.
+    pub fn load_from(files: &[PathBuf], env: Layer) -> Result<Self, String> {
+        let mut config = Self::default();
+        for path in files {
+            if let Some(layer) = Layer::from_file(path)? {
+                config.apply(layer, Source::File(path.clone()));
+            }
+        }
+        config.apply(env, Source::Env);
+        Ok(config)
+    }
+
+    /// The files that are read, lowest priority first.
+    pub fn files() -> Vec<PathBuf> {
+        let mut files = vec![PathBuf::from(REPO_FILE)];
+        if let Some(home) = env::var_os("HOME") {
+            files.push(Path::new(&home).join(".config/aoc/config.toml"));
+        }
+        files
+    }
+
+    /// Overrides whatever `layer` sets.
+    pub fn apply(&mut self, layer: Layer, source: Source) {
+        let mut set = |key: &str| {
+            self.sources.insert(key.to_string(), source.clone());
+        };
+        if let Some(session) = layer.session {
+            self.session = Some(session);
+            set("session");
+        }
+        if let Some(input_dir) = layer.input_dir {
+            self.input_dir = input_dir;
+            set("input_dir");
+        }
+        if let Some(threads) = layer.threads {
+            self.threads = Some(threads);
+            set("threads");
+        }
+        if let Some(format) = layer.format {
+            self.format = Some(format);
+            set("format");
+        }
+        if let Some(timeout) = layer.timeout {
+            self.timeout = Some(timeout);
+            set("timeout");
+        }
+        if let Some(slow) = layer.slow {
+            self.slow = Some(slow);
+            set("slow");
+        }
+        if let Some(plugin_dir) = layer.plugin_dir {
+            self.plugin_dir = Some(plugin_dir);
+            set("plugin_dir");
+        }
+        if let Some(snapshot_dir) = layer.snapshot_dir {
+            self.snapshot_dir = snapshot_dir;
+            set("snapshot_dir");
+        }
+        for (day, params) in layer.params {
+            let merged = self.params.entry(day).or_default();
+            for (key, value) in params.iter() {
+                merged.set(key, value);
+                set(&format!("day{}.{}", day, key));
+            }
+        }
+    }
+
+    /// The parameter overrides for `day`, with `overrides` (e.g. from `--param`) on top.
+    pub fn params(&self, day: u8, overrides: Params) -> Params {
+        let mut params = self.params.get(&day).cloned().unwrap_or_default();
+        for (key, value) in overrides.iter() {
+            params.set(key, value);
+        }
+        params
+    }
+
+    pub fn source(&self, key: &str) -> Source {
+        self.sources.get(key).cloned().unwrap_or(Source::Default)
+    }
+
+    /// Every setting as `(key, value, source)`, days' parameters last. The session is masked.
+    pub fn entries(&self) -> Vec<(String, String, Source)> {
+        let show_duration =
+            |d: Option<Duration>| d.map_or("none".to_string(), |d| format!("{:?}", d));
+        let mut entries = vec![
+            (
+                "session",
+                self.session
+                    .as_ref()
+                    .map_or("none".to_string(), |_| "(set)".to_string()),
+            ),
+            ("input_dir", self.input_dir.display().to_string()),
+            (
+                "threads",
+                self.threads
+                    .map_or("one per core".to_string(), |threads| threads.to_string()),
+            ),
+            (
+                "format",
+                self.format.clone().unwrap_or_else(|| "text".to_string()),
+            ),
+            ("timeout", show_duration(self.timeout)),
+            ("slow", show_duration(self.slow)),
+            (
+                "plugin_dir",
+                self.plugin_dir
+                    .as_ref()
+                    .map_or("plugins".to_string(), |dir| dir.display().to_string()),
+            ),
+            ("snapshot_dir", self.snapshot_dir.display().to_string()),
+        ]
+        .into_iter()
+        .map(|(key, value)| (key.to_string(), value, self.source(key)))
+        .collect::<Vec<_>>();
+        for (day, params) in self.params.iter() {
+            for (key, value) in params.iter() {
+                let key = format!("day{}.{}", day, key);
+                let source = self.source(&key);
+                entries.push((key, value.to_string(), source));
+            }
+        }
+        entries
+    }
+}
+
+impl Layer {
+    /// The settings in the TOML file at `path`, or `None` if there isn't one.
+    pub fn from_file(path: &Path) -> Result<Option<Self>, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e)),
+        };
+        Self::from_toml(&content)
+            .map(Some)
+            .map_err(|e| format!("{}: {}", path.display(), e))
+    }
+
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table = content.parse::<toml::Table>().map_err(|e| e.to_string())?;
+        let mut layer = Self::default();
+        for (key, value) in table {
+            match (key.as_str(), value) {
+                ("session", toml::Value::String(s)) => layer.session = Some(s),
+                ("input_dir", toml::Value::String(s)) => layer.input_dir = Some(expand_home(&s)),
+                ("threads", toml::Value::Integer(n)) => {
+                    layer.threads = Some(parse_threads(&n.to_string())?)
+                }
+                ("format", toml::Value::String(s)) => layer.format = Some(parse_format(&s)?),
+                ("timeout", value) => layer.timeout = Some(parse_duration_value(&key, value)?),
+                ("slow", value) => layer.slow = Some(parse_duration_value(&key, value)?),
+                ("plugin_dir", toml::Value::String(s)) => layer.plugin_dir = Some(expand_home(&s)),
+                ("snapshot_dir", toml::Value::String(s)) => {
+                    layer.snapshot_dir = Some(expand_home(&s))
+                }
+                (day, toml::Value::Table(table)) if day.starts_with("day") => {
+                    let day = day[3..]
+                        .parse::<u8>()
+                        .map_err(|_| format!("[{}] isn't a day", day))?;
+                    let params = layer.params.entry(day).or_default();
+                    for (key, value) in table {
+                        let value = match value {
+                            toml::Value::String(s) => s,
+                            value => value.to_string(),
+                        };
+                        params.set(&key, &value);
+                    }
+                }
+                (key, _) => return Err(format!("unknown setting or wrong type for {}", key)),
+            }
+        }
+        Ok(layer)
+    }
+
+    /// The settings given as `AOC_*` environment variables.
+    pub fn from_env() -> Result<Self, String> {
+        Self::from_vars(|name| env::var(name).ok())
+    }
+
+    /// The settings given as `AOC_*` variables, looked up with `var`.
+    pub fn from_vars(var: impl Fn(&str) -> Option<String>) -> Result<Self, String> {
+        let var = |name: &str| var(name).filter(|value| !value.trim().is_empty());
+        Ok(Self {
+            session: var("AOC_SESSION").map(|s| s.trim().to_string()),
+            input_dir: var("AOC_INPUT_DIR").map(PathBuf::from),
+            threads: var("AOC_THREADS")
+                .map(|s| parse_threads(&s))
+                .transpose()
+                .map_err(|e| format!("AOC_THREADS: {}", e))?,
+            format: var("AOC_FORMAT")
+                .map(|s| parse_format(&s))
+                .transpose()
+                .map_err(|e| format!("AOC_FORMAT: {}", e))?,
+            timeout: var("AOC_TIMEOUT")
+                .map(|s| runner::parse_duration(&s))
+                .transpose()
+                .map_err(|e| format!("AOC_TIMEOUT: {}", e))?,
+            slow: var("AOC_SLOW")
+                .map(|s| runner::parse_duration(&s))
+                .transpose()
+                .map_err(|e| format!("AOC_SLOW: {}", e))?,
+            plugin_dir: var("AOC_PLUGIN_DIR").map(PathBuf::from),
+            snapshot_dir: var("AOC_SNAPSHOT_DIR").map(PathBuf::from),
+            params: BTreeMap::new(),
+        })
+    }
+}
+
+fn parse_threads(s: &str) -> Result<u16, String> {
+    match s.trim().parse::<u16>() {
+        Ok(threads) if threads > 0 => Ok(threads),
+        _ => Err(format!("threads has to be a number from 1 up, not {:?}", s)),
+    }
+}
+
+fn parse_format(s: &str) -> Result<String, String> {
+    match s.trim() {
+        "text" | "json" => Ok(s.trim().to_string()),
+        _ => Err(format!("format has to be text or json, not {:?}", s)),
+    }
+}
+
+/// A duration given as a string like `"30s"`, or a number of seconds.
+fn parse_duration_value(key: &str, value: toml::Value) -> Result<Duration, String> {
+    match value {
+        toml::Value::String(s) => runner::parse_duration(&s),
+        toml::Value::Integer(n) if n >= 0 => Ok(Duration::from_secs(n as u64)),
+        toml::Value::Float(n) if n >= 0.0 => Ok(Duration::from_secs_f64(n)),
+        _ => Err(format!("{} has to be a duration like \"30s\"", key)),
+    }
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match (path.strip_prefix("~/"), env::var_os("HOME")) {
+        (Some(rest), Some(home)) => Path::new(&home).join(rest),
+        _ => PathBuf::from(path),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_common::temp_dir;
+
+    fn vars(pairs: &[(&str, &str)]) -> Result<Layer, String> {
+        let vars = pairs
+            .iter()
+            .map(|&(key, value)| (key.to_string(), value.to_string()))
+            .collect::<BTreeMap<_, _>>();
+        Layer::from_vars(|name| vars.get(name).cloned())
+    }
+
+    #[test]
+    fn reads_every_setting() {
+        let layer = Layer::from_toml(
+            r#"
+            session = "abc"
+            input_dir = "inputs"
+            threads = 4
+            format = "json"
+            timeout = "30s"
+            slow = 2
+            plugin_dir = "libs"
+            snapshot_dir = "golden"
+
+            [day14]
+            max_x = 11
+            name = "seven"
+            "#,
+        )
+        .unwrap();
+        assert_eq!(layer.session.as_deref(), Some("abc"));
+        assert_eq!(layer.input_dir, Some(PathBuf::from("inputs")));
+        assert_eq!(layer.threads, Some(4));
+        assert_eq!(layer.format.as_deref(), Some("json"));
+        assert_eq!(layer.timeout, Some(Duration::from_secs(30)));
+        assert_eq!(layer.slow, Some(Duration::from_secs(2)));
+        assert_eq!(layer.plugin_dir, Some(PathBuf::from("libs")));
+        assert_eq!(layer.snapshot_dir, Some(PathBuf::from("golden")));
+        let day14 = &layer.params[&14];
+        assert_eq!(day14.get("max_x", 0), 11);
+        assert_eq!(day14.get("name", String::new()), "seven");
+    }
+
+    #[test]
+    fn rejects_unknown_settings_and_wrong_types() {
+        for (toml, error) in [
+            ("colour = true", "unknown setting or wrong type for colour"),
+            (
+                "threads = \"four\"",
+                "unknown setting or wrong type for threads",
+            ),
+            ("threads = 0", "threads has to be a number from 1 up"),
+            ("format = \"xml\"", "format has to be text or json"),
+            ("timeout = -1", "timeout has to be a duration"),
+            ("[dayfour]\nx = 1", "[dayfour] isn't a day"),
+            ("threads = ", "TOML parse error"),
+        ] {
+            let err = Layer::from_toml(toml).unwrap_err();
+            assert!(err.contains(error), "{:?}: {}", toml, err);
+        }
+        assert!(vars(&[("AOC_THREADS", "lots")])
+            .unwrap_err()
+            .starts_with("AOC_THREADS: "));
+        // Blank variables count as unset.
+        assert_eq!(vars(&[("AOC_TIMEOUT", " ")]).unwrap().timeout, None);
+    }
+
+    #[test]
+    fn later_layers_win() {
+        let dir = temp_dir("config-layers");
+        let (repo, home) = (dir.join("aoc.toml"), dir.join("home.toml"));
+        fs::write(
+            &repo,
+            "threads = 2\nformat = \"json\"\ntimeout = 5\nslow = 1",
+        )
+        .unwrap();
+        fs::write(&home, "threads = 3\ntimeout = 6").unwrap();
+        let env = vars(&[("AOC_TIMEOUT", "7s"), ("AOC_INPUT_DIR", "env-inputs")]).unwrap();
+        let missing = dir.join("missing.toml");
+        let mut config = Config::load_from(&[repo.clone(), missing, home.clone()], env).unwrap();
+        config.apply(
+            Layer {
+                input_dir: Some(PathBuf::from("flag-inputs")),
+                ..Layer::default()
+            },
+            Source::Flag,
+        );
+
+        assert_eq!(config.format.as_deref(), Some("json"));
+        assert_eq!(config.source("format"), Source::File(repo));
+        assert_eq!(config.threads, Some(3));
+        assert_eq!(config.source("threads"), Source::File(home));
+        assert_eq!(config.timeout, Some(Duration::from_secs(7)));
+        assert_eq!(config.source("timeout"), Source::Env);
+        assert_eq!(config.input_dir, PathBuf::from("flag-inputs"));
+        assert_eq!(config.source("input_dir"), Source::Flag);
+        assert_eq!(config.session, None);
+        assert_eq!(config.source("session"), Source::Default);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn day_params_merge_key_by_key() {
+        let dir = temp_dir("config-params");
+        let (repo, home) = (dir.join("aoc.toml"), dir.join("home.toml"));
+        fs::write(&repo, "[day14]\nmax_x = 11\nmax_y = 7").unwrap();
+        fs::write(&home, "[day14]\nmax_x = 12\n[day11]\nblinks = 6").unwrap();
+        let config = Config::load_from(&[repo.clone(), home.clone()], Layer::default()).unwrap();
+
+        let overrides = [("max_y".to_string(), "3".to_string())]
+            .into_iter()
+            .collect::<Params>();
+        let day14 = config.params(14, overrides);
+        assert_eq!(day14.get("max_x", 0), 12);
+        assert_eq!(day14.get("max_y", 0), 3);
+        assert_eq!(config.params(14, Params::new()).get("max_y", 0), 7);
+        assert_eq!(config.params(11, Params::new()).get("blinks", 0), 6);
+        assert!(config.params(1, Params::new()).is_empty());
+        assert_eq!(config.source("day14.max_x"), Source::File(home));
+        assert_eq!(config.source("day14.max_y"), Source::File(repo));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn says_where_settings_came_from() {
+        assert_eq!(Source::Default.describe("threads"), "default");
+        assert_eq!(
+            Source::File(PathBuf::from("aoc.toml")).describe("threads"),
+            "aoc.toml"
+        );
+        assert_eq!(Source::Env.describe("input_dir"), "$AOC_INPUT_DIR");
+        assert_eq!(Source::Flag.describe("input_dir"), "--input-dir");
+        assert_eq!(Source::Flag.describe("day14.max_x"), "--param");
+    }
+
+    #[test]
+    fn expands_a_leading_tilde() {
+        assert_eq!(expand_home("/srv/inputs"), PathBuf::from("/srv/inputs"));
+        assert_eq!(expand_home("~user/inputs"), PathBuf::from("~user/inputs"));
+        match env::var_os("HOME") {
+            Some(home) => assert_eq!(expand_home("~/inputs"), Path::new(&home).join("inputs")),
+            None => assert_eq!(expand_home("~/inputs"), PathBuf::from("~/inputs")),
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use crate::answer::Answer;
+use crate::params::Params;
+use crate::scale;
+use crate::solution::{Solution, Variant};
+
+#[derive(Debug, Clone)]
+pub struct Args {
+    first: Vec<usize>,
+    second: Vec<usize>,
+}
+
+pub fn parse(content: &str) -> Result<Args, String> {
+    let mut first_v = Vec::new();
+    let mut second_v = Vec::new();
+
+    for (i, line) in content.trim().lines().enumerate() {
+        let numbers = line
+            .split_whitespace()
+            .map(|n| n.parse::<usize>())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| format!("line {}: {}", i + 1, e))?;
+        let [first, second] = numbers[..] else {
+            return Err(format!("line {} doesn't have two numbers", i + 1));
+        };
+        first_v.push(first);
+        second_v.push(second);
+    }
+    Ok(Args {
+        first: first_v,
+        second: second_v,
+    })
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    const DAY: u8 = 1;
+    type Input = Args;
+
+    fn parse(input: &str) -> Result<Args, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<Args>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |args: &Args, _: &Params| solve_part1(args).into(),
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |args: &Args, _: &Params| solve_part2(args).into(),
+            },
+        ]
+    }
+
+    fn scale(input: &str, factor: usize) -> Option<String> {
+        Some(scale::repeat_lines(input, factor))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?).into())
+}
+
+pub fn solve_part1(args: &Args) -> usize {
+    let mut val_args = args.clone();
+    let first = {
+        val_args.first.sort_unstable();
+        val_args.first
+    };
+    let second = {
+        val_args.second.sort_unstable();
+        val_args.second
+    };
+    first
+        .into_iter()
+        .zip(second)
+        .map(|(x, y)| x.abs_diff(y))
+        .sum()
+}
+
+pub fn solve_part2(args: &Args) -> usize {
+    let val_args = args.clone();
+    let mut result = 0;
+    for l in val_args.first.into_iter() {
+        for &r in &val_args.second {
+            if l == r {
+                result += l;
+            }
+        }
+    }
+    result
+}
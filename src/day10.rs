@@ -0,0 +1,158 @@
+use crate::answer::Answer;
+use crate::collections::HashSet;
+use crate::grid::CharGrid;
+use crate::params::Params;
+use crate::solution::{Solution, Variant};
+
+pub struct HikingMap {
+    tiles: Vec<Vec<usize>>,
+}
+
+impl HikingMap {
+    fn get_trailheads(&self) -> Vec<(usize, usize)> {
+        let mut v = Vec::new();
+        for (i, line) in self.tiles.iter().enumerate() {
+            for (j, &n) in line.iter().enumerate() {
+                if n == 0 {
+                    v.push((i, j));
+                }
+            }
+        }
+        v
+    }
+
+    fn get_tile(&self, i: usize, j: usize) -> Option<&usize> {
+        self.tiles.get(i)?.get(j)
+    }
+
+    fn find_tiles_from(&self, i: usize, j: usize) -> Vec<(usize, usize)> {
+        let mut paths = Vec::new();
+
+        if let Some(&n) = self.get_tile(i, j) {
+            let directions = [
+                (i + 1, j),
+                (i, j + 1),
+                (i.wrapping_sub(1), j),
+                (i, j.wrapping_sub(1)),
+            ];
+
+            for (row, col) in directions {
+                if let Some(&n2) = self.get_tile(row, col) {
+                    if n2 == n + 1 {
+                        paths.push((row, col));
+                    }
+                }
+            }
+        }
+
+        paths
+    }
+
+    fn find_peaks_reachable_from(&self, head_i: usize, head_j: usize) -> HashSet<(usize, usize)> {
+        let mut peaks = HashSet::default();
+        if self.get_tile(head_i, head_j) == Some(&9) {
+            peaks.insert((head_i, head_j));
+            return peaks;
+        }
+        for (i, j) in self.find_tiles_from(head_i, head_j) {
+            let peaks_from_here = self.find_peaks_reachable_from(i, j);
+            peaks.extend(peaks_from_here);
+        }
+        peaks
+    }
+
+    fn find_distinct_trails(&self, head_i: usize, head_j: usize) -> usize {
+        if self.get_tile(head_i, head_j) == Some(&9) {
+            return 1;
+        }
+        let mut peaks = 0;
+        for (i, j) in self.find_tiles_from(head_i, head_j) {
+            let peaks_from_here = self.find_distinct_trails(i, j);
+            peaks += peaks_from_here;
+        }
+        peaks
+    }
+}
+
+pub fn parse(content: &str) -> Result<HikingMap, String> {
+    Ok(HikingMap {
+        tiles: content
+            .trim()
+            .lines()
+            .enumerate()
+            .map(|(y, line)| {
+                line.chars()
+                    .enumerate()
+                    .map(|(x, c)| {
+                        c.to_digit(10).map(|n| n as usize).ok_or_else(|| {
+                            format!("line {}, column {}: {:?} isn't a digit", y + 1, x + 1, c)
+                        })
+                    })
+                    .collect::<Result<Vec<_>, _>>()
+            })
+            .collect::<Result<Vec<_>, _>>()?,
+    })
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    type Input = HikingMap;
+
+    fn parse(input: &str) -> Result<HikingMap, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<HikingMap>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |map: &HikingMap, _: &Params| solve_part1(map).into(),
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |map: &HikingMap, _: &Params| solve_part2(map).into(),
+            },
+        ]
+    }
+
+    fn grid(map: &HikingMap, _: &Params) -> Option<CharGrid> {
+        Some(CharGrid::new(
+            map.tiles
+                .iter()
+                .map(|row| {
+                    row.iter()
+                        .map(|&n| char::from_digit(n as u32, 10).unwrap_or('?'))
+                        .collect()
+                })
+                .collect(),
+        ))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?).into())
+}
+
+pub fn solve_part1(map: &HikingMap) -> usize {
+    let mut result = 0;
+    for (i, j) in map.get_trailheads() {
+        result += map.find_peaks_reachable_from(i, j).len()
+    }
+    result
+}
+
+pub fn solve_part2(map: &HikingMap) -> usize {
+    let mut result = 0;
+    for (i, j) in map.get_trailheads() {
+        result += map.find_distinct_trails(i, j);
+    }
+    result
+}
@@ -0,0 +1,298 @@
+use crate::answer::Answer;
+use crate::collections::HashMap;
+use crate::count;
+use crate::parallel::prelude::*;
+use crate::params::{ParamSpec, Params};
+use crate::solution::{Solution, Variant};
+use crate::trace;
+use itertools::Itertools;
+use std::cell::RefCell;
+use std::fmt;
+use std::sync::Arc;
+
+#[derive(Debug, Clone)]
+pub struct StoneLine {
+    stones: Vec<Stone>,
+}
+
+#[allow(dead_code)]
+#[derive(Debug, Clone, Hash, PartialEq, Eq)]
+struct StoneNode {
+    stone: Stone,
+    children: Vec<StoneNode>,
+}
+
+#[allow(dead_code)]
+impl StoneNode {
+    fn populate_until_cycles_or_limit(
+        mut self,
+        map: Arc<RefCell<HashMap<Stone, StoneNode>>>,
+        depth: usize,
+        limit: usize,
+    ) -> Vec<StoneNode> {
+        let children = self.stone.do_iteration();
+        if depth >= limit {
+            return self.children;
+        }
+        for child in children.into_iter() {
+            if let Some(existing_stone) = map.borrow().get(&child) {
+                self.children.push(existing_stone.clone());
+            } else {
+                let new_node = StoneNode {
+                    stone: child,
+                    children: Vec::new(),
+                };
+                self.children
+                    .extend(new_node.populate_until_cycles_or_limit(map.clone(), depth + 1, limit));
+            }
+        }
+        map.clone().borrow_mut().insert(self.stone, self.clone());
+        self.children
+    }
+}
+
+impl StoneLine {
+    fn do_iteration(&mut self) {
+        self.stones = self
+            .stones
+            .iter()
+            .flat_map(|s| s.do_iteration())
+            .collect::<Vec<Stone>>();
+    }
+
+    #[allow(dead_code)]
+    fn do_iteration_pt2(&mut self) {
+        let mut hm = HashMap::default();
+        self.stones = self
+            .stones
+            .iter()
+            .flat_map(|s| s.do_iteration_pt2(&mut hm))
+            .collect::<Vec<Stone>>();
+    }
+}
+
+impl fmt::Display for StoneLine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}",
+            self.stones
+                .iter()
+                .map(|stone| stone.n.to_string())
+                .join(" ")
+        )
+    }
+}
+
+#[derive(Debug, Clone, Copy, Hash, PartialEq, Eq)]
+struct Stone {
+    n: usize,
+}
+
+impl Stone {
+    fn do_iteration(self) -> Vec<Self> {
+        count!("stones_blinked");
+        match self.n {
+            0 => vec![Stone { n: 1 }],
+            _ => match self.n.to_string().len() % 2 {
+                0 => {
+                    let s = self.n.to_string();
+                    let (x, y) = s.split_at(s.len() / 2);
+                    vec![
+                        Stone {
+                            n: x.parse::<usize>().unwrap(),
+                        },
+                        Stone {
+                            n: y.parse::<usize>().unwrap(),
+                        },
+                    ]
+                }
+                1 => vec![Stone { n: self.n * 2024 }],
+                _ => panic!("mod 2 returned value not 0 or 1????"),
+            },
+        }
+    }
+
+    #[allow(dead_code)]
+    fn do_iteration_pt2(self, hashmap: &mut HashMap<usize, Vec<Stone>>) -> Vec<Self> {
+        hashmap
+            .entry(self.n)
+            .or_insert_with(|| match self.n {
+                0 => vec![Stone { n: 1 }],
+                _ => match self.n.to_string().len() % 2 {
+                    0 => {
+                        let s = self.n.to_string();
+                        let (x, y) = s.split_at(s.len() / 2);
+                        vec![
+                            Stone {
+                                n: x.parse::<usize>().unwrap(),
+                            },
+                            Stone {
+                                n: y.parse::<usize>().unwrap(),
+                            },
+                        ]
+                    }
+                    1 => vec![Stone { n: self.n * 2024 }],
+                    _ => panic!("mod 2 returned value not 0 or 1????"),
+                },
+            })
+            .to_vec()
+    }
+}
+
+pub fn parse(content: &str) -> Result<StoneLine, String> {
+    Ok(StoneLine {
+        stones: content
+            .split_whitespace()
+            .map(|s| {
+                s.parse::<usize>()
+                    .map(|n| Stone { n })
+                    .map_err(|e| format!("{:?}: {}", s, e))
+            })
+            .collect::<Result<Vec<Stone>, _>>()?,
+    })
+}
+
+pub const PART1_BLINKS: usize = 25;
+pub const PART2_BLINKS: usize = 75;
+
+/// The most blinks part 2 takes: as many as it can recurse through without running out of stack.
+#[cfg(feature = "bigmath")]
+pub const MAX_PART2_BLINKS: usize = 1000;
+/// The most blinks part 2 takes: as many as a `u128` can count the stones after.
+#[cfg(not(feature = "bigmath"))]
+pub const MAX_PART2_BLINKS: usize = 150;
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    type Input = StoneLine;
+
+    fn parse(input: &str) -> Result<StoneLine, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<StoneLine>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |stones: &StoneLine, params: &Params| {
+                    solve_part1(stones, blinks(params, "part1_blinks", PART1_BLINKS)).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |stones: &StoneLine, params: &Params| {
+                    solve_part2(stones, blinks(params, "part2_blinks", PART2_BLINKS)).into()
+                },
+            },
+        ]
+    }
+
+    fn params() -> Vec<ParamSpec> {
+        vec![
+            // Part 1 keeps every stone: 40 blinks is already ten million of them.
+            ParamSpec::new("part1_blinks", PART1_BLINKS, "how many times part 1 blinks")
+                .between(0, 40),
+            // Part 2 recurses once per blink, so too many overflow the stack (or the count).
+            ParamSpec::new("part2_blinks", PART2_BLINKS, "how many times part 2 blinks")
+                .between(0, MAX_PART2_BLINKS),
+            // Sets both parts at once, so it can't go further than part 1 can. Its default is
+            // only part 1's; left unset, each part keeps its own.
+            ParamSpec::new(
+                "blinks",
+                PART1_BLINKS,
+                "how many times both parts blink, unless part1_blinks or part2_blinks says \
+                 otherwise (left unset, part 1 blinks 25 times and part 2 75)",
+            )
+            .between(0, 40),
+        ]
+    }
+}
+
+/// How many times a part blinks: its own parameter, then `blinks`, then `default`.
+fn blinks(params: &Params, key: &str, default: usize) -> usize {
+    params.get(key, params.get("blinks", default))
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?, PART1_BLINKS).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?, PART2_BLINKS).into())
+}
+
+pub fn solve_part1(stones: &StoneLine, blinks: usize) -> usize {
+    let mut stones = stones.clone();
+    trace!("{:?}", stones.to_string());
+    for _ in 0..blinks {
+        stones.do_iteration();
+        // println!("{:?}", stones.to_string());
+    }
+    stones.stones.len()
+}
+
+fn length_is_even(n: &usize) -> bool {
+    ((*n as f64).log10() as usize) % 2 == 1
+}
+
+fn split_even_length(n: usize) -> (usize, usize) {
+    let n_digits = match n {
+        0..9 => 1,
+        _ => (n as f64).log10() as usize + 1,
+    };
+    let right_digits = n_digits / 2;
+    let divisor = 10_usize.pow(right_digits as u32);
+
+    (n / divisor, n % divisor)
+}
+
+/// A number of stones. It outgrows `usize` past ~100 blinks, so it's a `BigUint` with `bigmath`.
+#[cfg(feature = "bigmath")]
+pub type Count = num_bigint::BigUint;
+/// A number of stones; `u128` holds out to around 150 blinks.
+#[cfg(not(feature = "bigmath"))]
+pub type Count = u128;
+
+/// How many stones `n` turns into.
+#[cfg_attr(not(feature = "bigmath"), allow(clippy::clone_on_copy))]
+fn blink(n: usize, depth: usize, limit: usize, map: &mut HashMap<(usize, usize), Count>) -> Count {
+    if depth >= limit {
+        return Count::from(1u8);
+    }
+
+    if let Some(result) = map.get(&(n, depth)) {
+        count!("memo_hits");
+        return result.clone();
+    }
+    count!("memo_misses");
+
+    let result = match n {
+        0 => blink(1, depth + 1, limit, map),
+        _n if length_is_even(&_n) => {
+            let (left, right) = split_even_length(_n);
+            blink(left, depth + 1, limit, map) + blink(right, depth + 1, limit, map)
+        }
+        _ => blink(n * 2024, depth + 1, limit, map),
+    };
+
+    map.insert((n, depth), result.clone());
+
+    result
+}
+
+pub fn solve_part2(stones: &StoneLine, blinks: usize) -> Count {
+    let stones = stones.clone();
+    stones
+        .stones
+        .into_par_iter()
+        .map(|n| {
+            let mut map = HashMap::default();
+            blink(n.n, 0, blinks, &mut map)
+        })
+        .sum()
+}
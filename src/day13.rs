@@ -0,0 +1,464 @@
+use crate::answer::Answer;
+use crate::cancel;
+use crate::params::{ParamSpec, Params};
+use crate::solution::{Solution, Variant};
+use crate::trace;
+use itertools::Itertools;
+#[cfg(feature = "bigmath")]
+use num_bigint::BigInt;
+#[cfg(feature = "bigmath")]
+use num_rational::BigRational;
+use regex::Regex;
+#[cfg(feature = "bigmath")]
+use rust_decimal::{prelude::FromPrimitive, Decimal};
+#[cfg(feature = "bigmath")]
+use rust_decimal_macros::dec;
+#[cfg(feature = "bigmath")]
+use std::str::FromStr;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct Vec2d(usize, usize);
+
+#[cfg(feature = "bigmath")]
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Vecf2d(Decimal, Decimal);
+
+#[cfg(feature = "bigmath")]
+#[allow(dead_code)]
+impl Vecf2d {
+    fn from_vec2d(v: Vec2d) -> Self {
+        Vecf2d(
+            Decimal::from_usize(v.0).unwrap(),
+            Decimal::from_usize(v.1).unwrap(),
+        )
+    }
+
+    fn s_divu(&self, n: usize) -> Self {
+        let n = Decimal::from_usize(n).unwrap();
+        self.s_div(n)
+    }
+
+    fn s_div(&self, n: Decimal) -> Self {
+        Vecf2d(self.0 / n, self.1 / n)
+    }
+
+    fn s_mul(&self, n: Decimal) -> Self {
+        Vecf2d(self.0 * n, self.1 * n)
+    }
+
+    fn add(&self, other: Vecf2d) -> Self {
+        Vecf2d(self.0 + other.0, self.1 + other.1)
+    }
+
+    fn sub(&self, other: Vecf2d) -> Self {
+        Vecf2d(self.0 - other.0, self.1 - other.1)
+    }
+}
+
+impl Vec2d {
+    fn s_mul(&self, n: usize) -> Self {
+        Vec2d(n * self.0, n * self.1)
+    }
+
+    fn add(&self, other: Vec2d) -> Self {
+        Vec2d(self.0 + other.0, self.1 + other.1)
+    }
+}
+
+#[cfg(feature = "bigmath")]
+#[derive(Debug, PartialEq, Clone, Copy)]
+struct Mat2d(Vecf2d, Vecf2d);
+
+#[cfg(feature = "bigmath")]
+impl Mat2d {
+    fn transposed(&self) -> Self {
+        Mat2d(Vecf2d(self.0 .0, self.1 .0), Vecf2d(self.0 .1, self.1 .1))
+    }
+
+    fn det(&self) -> Decimal {
+        self.0 .0 * self.1 .1 - self.0 .1 * self.1 .0
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct ClawProblem {
+    a: Vec2d,
+    b: Vec2d,
+    target: Vec2d,
+}
+
+impl ClawProblem {
+    fn solve(&self, max_presses: usize) -> Option<usize> {
+        let mut sols = Vec::new();
+        for i in 0..=max_presses {
+            cancel::check();
+            for j in 0..=max_presses {
+                if self.a.s_mul(i).add(self.b.s_mul(j)) == self.target {
+                    sols.push((i, j));
+                }
+            }
+        }
+        let (best_i, best_j) = sols.into_iter().min_by_key(|(i, j)| 3 * i + j)?;
+        trace!(
+            "{:?}*{:?} + {:?}*{:?} = {:?}  ",
+            self.a,
+            best_i,
+            self.b,
+            best_j,
+            self.target
+        );
+        Some(3 * best_i + best_j)
+    }
+
+    #[cfg(feature = "bigmath")]
+    fn solve_p2_math(&self) -> Option<Decimal> {
+        let va = Vecf2d::from_vec2d(self.a);
+        let vb = Vecf2d::from_vec2d(self.b);
+        let mat = Mat2d(va, vb).transposed();
+        let target = Vecf2d::from_vec2d(self.target);
+
+        trace!("det: {:?}", mat.det());
+
+        if mat.det() != Decimal::from_u8(0).unwrap() {
+            let a = BigInt::from_str(mat.0 .0.to_string().as_str()).unwrap();
+            trace!("a: {:?}", a);
+            let b = BigInt::from_str(mat.0 .1.to_string().as_str()).unwrap();
+            trace!("b: {:?}", b);
+            let c = BigInt::from_str(mat.1 .0.to_string().as_str()).unwrap();
+            trace!("c: {:?}", c);
+            let d = BigInt::from_str(mat.1 .1.to_string().as_str()).unwrap();
+            trace!("d: {:?}", d);
+            let e = BigInt::from_str(target.0.to_string().as_str()).unwrap();
+            trace!("e: {:?}", e);
+            let f = BigInt::from_str(target.1.to_string().as_str()).unwrap();
+            trace!("f: {:?}", f);
+
+            let d_minus_bc_over_a = BigRational::from_integer(d.clone())
+                - BigRational::new(b.clone() * c.clone(), a.clone());
+
+            let f_minus_ce_over_a = BigRational::from_integer(f.clone())
+                - BigRational::new(c.clone() * e.clone(), a.clone());
+
+            let unique_solution_b = f_minus_ce_over_a / d_minus_bc_over_a;
+
+            let unique_solution_a = (BigRational::from_integer(e.clone())
+                - BigRational::from_integer(b.clone()) * unique_solution_b.clone())
+                / a.clone();
+
+            trace!(
+                "unique solution: ({:?})A + ({:?})B",
+                unique_solution_a,
+                unique_solution_b
+            );
+
+            if !(unique_solution_a.is_integer() && unique_solution_b.is_integer()) {
+                trace!("no integer solution");
+                return None;
+            }
+            let unique_solution_a = unique_solution_a.numer();
+            let unique_solution_b = unique_solution_b.numer();
+
+            // let d_minus_bc_over_a = mat.1 .1 - ((mat.0 .1 * mat.1 .0) / mat.0 .0);
+            // let f_minus_ce_over_a = target.1 - ((mat.1 .0 * target.0) / mat.0 .0);
+
+            // let unique_solution_b = f_minus_ce_over_a / d_minus_bc_over_a;
+            // let unique_solution_a = (target.0 - (mat.0 .1 * unique_solution_b)) / mat.0 .0;
+
+            // let tokens = unique_solution_a * Decimal::from_u8(3).unwrap() + unique_solution_b;
+            let tokens: BigInt = unique_solution_a * 3 + unique_solution_b;
+            trace!("=> {:?} tokens", tokens);
+            return Some(Decimal::from_str(tokens.to_string().as_str()).unwrap());
+        };
+
+        let one_vector_only_solution_using_a = Vecf2d(target.0 / va.0, target.1 / va.1).0;
+        let one_vector_only_solution_using_b = Vecf2d(target.0 / vb.0, target.1 / vb.1).0;
+
+        trace!("o_v_o_s_u_a: {:?}", one_vector_only_solution_using_a);
+        trace!("o_v_o_s_u_b: {:?}", one_vector_only_solution_using_b);
+
+        let min_tokens = if one_vector_only_solution_using_b.is_integer()
+            && one_vector_only_solution_using_a.is_integer()
+        {
+            if one_vector_only_solution_using_b < dec!(3) * one_vector_only_solution_using_a {
+                trace!(
+                    "only multiplying B by {:?}",
+                    one_vector_only_solution_using_b
+                );
+                Some(one_vector_only_solution_using_b)
+            } else {
+                trace!(
+                    "only multiplying A by {:?}",
+                    one_vector_only_solution_using_a
+                );
+                Some(one_vector_only_solution_using_a * dec!(3))
+            }
+        } else {
+            if one_vector_only_solution_using_a.is_integer() {
+                trace!(
+                    "only multiplying A by {:?}",
+                    one_vector_only_solution_using_a
+                );
+                Some(one_vector_only_solution_using_a * dec!(3))
+            } else if one_vector_only_solution_using_b.is_integer() {
+                trace!(
+                    "only multiplying B by {:?}",
+                    one_vector_only_solution_using_b
+                );
+                Some(one_vector_only_solution_using_b)
+            } else {
+                None
+            }
+        };
+
+        if let Some(tokens) = min_tokens {
+            trace!("=> {:?} tokens", tokens);
+            Some(tokens)
+        } else {
+            trace!("no solution");
+            None
+        }
+    }
+
+    /// `solve_p2_math` without `bigmath`: Cramer's rule, which fits in an `i128` for these sizes.
+    #[cfg(not(feature = "bigmath"))]
+    fn solve_p2_math(&self) -> Option<i128> {
+        let (ax, ay) = (self.a.0 as i128, self.a.1 as i128);
+        let (bx, by) = (self.b.0 as i128, self.b.1 as i128);
+        let (px, py) = (self.target.0 as i128, self.target.1 as i128);
+        let det = ax * by - bx * ay;
+
+        trace!("det: {:?}", det);
+
+        if det != 0 {
+            let a = px * by - bx * py;
+            let b = ax * py - px * ay;
+            if a % det != 0 || b % det != 0 {
+                trace!("no integer solution");
+                return None;
+            }
+            let tokens = a / det * 3 + b / det;
+            trace!("=> {:?} tokens", tokens);
+            return Some(tokens);
+        }
+
+        let using_a = (px % ax == 0).then(|| px / ax);
+        let using_b = (px % bx == 0).then(|| px / bx);
+
+        let min_tokens = match (using_a, using_b) {
+            (Some(a), Some(b)) => Some(b.min(3 * a)),
+            (Some(a), None) => Some(3 * a),
+            (None, Some(b)) => Some(b),
+            (None, None) => None,
+        };
+
+        if let Some(tokens) = min_tokens {
+            trace!("=> {:?} tokens", tokens);
+        } else {
+            trace!("no solution");
+        }
+        min_tokens
+    }
+
+    //first naive approach of just doing gaussian elim
+    #[cfg(feature = "bigmath")]
+    #[allow(dead_code)]
+    fn solve_p2(&self) -> Option<Decimal> {
+        let mut mat = Mat2d(Vecf2d::from_vec2d(self.a), Vecf2d::from_vec2d(self.b)).transposed();
+
+        let mut target = Vecf2d::from_vec2d(self.target);
+
+        trace!("{:?} | {:?}\n", mat, target);
+
+        let scalar_to_get_0_0_to_1 = mat.0 .0;
+
+        mat.0 = mat.0.s_div(scalar_to_get_0_0_to_1);
+        target.0 = target
+            .0
+            .checked_div(scalar_to_get_0_0_to_1)
+            .expect("button has x = 0");
+
+        trace!(
+            "{:?} | {:?}\n{:?} | {:?}\n",
+            mat.0,
+            target.0,
+            mat.1,
+            target.1
+        );
+
+        let coeff_to_get_1_0_to_0 = mat.1 .0;
+
+        mat.1 = mat.1.sub(mat.0.s_mul(coeff_to_get_1_0_to_0));
+        target.1 -= target.0 * coeff_to_get_1_0_to_0;
+
+        trace!(
+            "{:?} | {:?}\n{:?} | {:?}\n",
+            mat.0,
+            target.0,
+            mat.1,
+            target.1
+        );
+
+        let coeff_to_get_0_1_to_0 = mat.0 .1 / mat.1 .1;
+
+        mat.0 = mat.0.sub(mat.1.s_mul(coeff_to_get_0_1_to_0));
+        target.0 += target.1 * coeff_to_get_0_1_to_0;
+
+        trace!(
+            "{:?} | {:?}\n{:?} | {:?}\n",
+            mat.0,
+            target.0,
+            mat.1,
+            target.1
+        );
+
+        let scalar_to_get_1_1_to_1 = mat.1 .1;
+
+        mat.1 = mat.1.s_div(scalar_to_get_1_1_to_1);
+        target.1 /= scalar_to_get_1_1_to_1;
+
+        trace!(
+            "{:?} | {:?}\n{:?} | {:?}\n",
+            mat.0,
+            target.0,
+            mat.1,
+            target.1
+        );
+
+        if !(target.0.fract() < Decimal::new(1, 15) && target.1.fract() < Decimal::new(1, 15)) {
+            None
+        } else {
+            let tokens = Decimal::from_u8(3).unwrap() * target.0 + target.1;
+            trace!("{:?}", tokens);
+            Some(tokens)
+        }
+    }
+}
+
+pub fn parse(content: &str) -> Result<Vec<ClawProblem>, String> {
+    let a_re = Regex::new(r"Button A: X\+([0-9]+), Y\+([0-9]+)").unwrap();
+    let b_re = Regex::new(r"Button B: X\+([0-9]+), Y\+([0-9]+)").unwrap();
+    let prize_re = Regex::new(r"Prize: X=([0-9]+), Y=([0-9]+)").unwrap();
+
+    let problem_strings = content.trim().lines().chunks(4);
+
+    problem_strings
+        .into_iter()
+        .enumerate()
+        .map(|(i, chunk)| {
+            let chunk_string = chunk.collect::<String>();
+            let vec = |re: &Regex, what: &str| {
+                let n = re
+                    .captures(&chunk_string)
+                    .ok_or_else(|| format!("machine {} has no {}", i + 1, what))?;
+                let coord = |j: usize| {
+                    n[j].parse::<usize>()
+                        .map_err(|e| format!("machine {}: {}", i + 1, e))
+                };
+                Ok::<_, String>(Vec2d(coord(1)?, coord(2)?))
+            };
+            Ok(ClawProblem {
+                a: vec(&a_re, "button A")?,
+                b: vec(&b_re, "button B")?,
+                target: vec(&prize_re, "prize")?,
+            })
+        })
+        .collect()
+}
+
+/// The token count for part 2, exact with `bigmath` and an `i128` without.
+#[cfg(feature = "bigmath")]
+pub type Tokens = Decimal;
+/// The token count for part 2.
+#[cfg(not(feature = "bigmath"))]
+pub type Tokens = i128;
+
+/// How much further away the prizes really are in part 2.
+pub const PRIZE_OFFSET: usize = 10_000_000_000_000;
+/// The most times part 1 will press either button.
+pub const MAX_PRESSES: usize = 100;
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    const DAY: u8 = 13;
+    type Input = Vec<ClawProblem>;
+
+    fn parse(input: &str) -> Result<Vec<ClawProblem>, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<Vec<ClawProblem>>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |problems: &Vec<ClawProblem>, params: &Params| {
+                    solve_part1(problems, params.get("max_presses", MAX_PRESSES)).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |problems: &Vec<ClawProblem>, params: &Params| {
+                    solve_part2(problems, params.get("prize_offset", PRIZE_OFFSET)).into()
+                },
+            },
+        ]
+    }
+
+    fn params() -> Vec<ParamSpec> {
+        vec![
+            // Part 1 tries every pair of press counts, so this is squared for every machine.
+            ParamSpec::new(
+                "max_presses",
+                MAX_PRESSES,
+                "most presses of each button in part 1",
+            )
+            .between(0, 1000),
+            // Anything much bigger overflows working out the presses.
+            ParamSpec::new(
+                "prize_offset",
+                PRIZE_OFFSET,
+                "how much further away part 2's prizes are",
+            )
+            .between(0, 100 * PRIZE_OFFSET),
+        ]
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?, MAX_PRESSES).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?, PRIZE_OFFSET).into())
+}
+
+pub fn solve_part1(problems: &[ClawProblem], max_presses: usize) -> usize {
+    let mut result = 0;
+    for problem in problems.iter() {
+        if let Some(solution) = problem.solve(max_presses) {
+            result += solution;
+        }
+    }
+    result
+}
+
+pub fn solve_part2(problems: &[ClawProblem], prize_offset: usize) -> Tokens {
+    let mut result = Tokens::default();
+    for problem in problems
+        .iter()
+        .map(|&p| {
+            let mut p2 = p;
+            p2.target = p2.target.add(Vec2d(prize_offset, prize_offset));
+            p2
+        })
+        .collect::<Vec<ClawProblem>>()
+    {
+        trace!("{:?}", problem);
+        if let Some(solution) = problem.solve_p2_math() {
+            result += solution;
+        }
+    }
+    result
+}
@@ -0,0 +1,412 @@
+use crate::answer::Answer;
+use crate::cancel;
+use crate::collections::{HashMap, HashSet};
+use crate::grid::{CharGrid, Picture};
+use crate::parallel::prelude::*;
+use crate::params::{ParamSpec, Params};
+use crate::progress::Progress;
+use crate::scale;
+use crate::simulation::{Driver, Playback, Simulation, SNAPSHOT_EVERY};
+use crate::solution::{Solution, Variant};
+use crate::trace;
+use cgmath::Vector2;
+use itertools::Itertools;
+use regex::Regex;
+use std::cmp::Reverse;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+struct Robot {
+    pos: Vector2<isize>,
+    vel: Vector2<isize>,
+}
+
+impl Robot {
+    fn move_once(&mut self, max_x: isize, max_y: isize) {
+        self.pos.x = (((self.pos.x + self.vel.x) % max_x) + max_x) % max_x;
+        self.pos.y = (((self.pos.y + self.vel.y) % max_y) + max_y) % max_y;
+    }
+
+    fn moved(mut self, steps: isize, max_x: isize, max_y: isize) -> Robot {
+        self.pos.x = (self.pos.x + self.vel.x * steps).rem_euclid(max_x);
+        self.pos.y = (self.pos.y + self.vel.y * steps).rem_euclid(max_y);
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct RobotGrid {
+    robots: Vec<Robot>,
+    max_x: isize,
+    max_y: isize,
+}
+
+impl RobotGrid {
+    /// This grid with its size taken from `max_x`/`max_y`, if set (the examples use 11x7).
+    fn with_params(&self, params: &Params) -> RobotGrid {
+        RobotGrid {
+            robots: self.robots.clone(),
+            max_x: params.get("max_x", self.max_x),
+            max_y: params.get("max_y", self.max_y),
+        }
+    }
+
+    /// The grid `steps` iterations from now, without going through every one in between.
+    fn after(&self, steps: isize) -> RobotGrid {
+        RobotGrid {
+            robots: self
+                .robots
+                .iter()
+                .map(|r| r.moved(steps, self.max_x, self.max_y))
+                .collect(),
+            max_x: self.max_x,
+            max_y: self.max_y,
+        }
+    }
+
+    fn do_iteration(&mut self) {
+        self.robots
+            .iter_mut()
+            .for_each(|r| r.move_once(self.max_x, self.max_y));
+    }
+
+    fn n_robots_in_top_left(&self) -> usize {
+        self.robots
+            .iter()
+            .filter(|r| r.pos.x < self.max_x / 2 && r.pos.y < self.max_y / 2)
+            .count()
+    }
+
+    fn n_robots_in_top_right(&self) -> usize {
+        self.robots
+            .iter()
+            .filter(|r| r.pos.x > self.max_x / 2 && r.pos.y < self.max_y / 2)
+            .count()
+    }
+
+    fn n_robots_in_bottom_left(&self) -> usize {
+        self.robots
+            .iter()
+            .filter(|r| r.pos.x < self.max_x / 2 && r.pos.y > self.max_y / 2)
+            .count()
+    }
+
+    fn n_robots_in_bottom_right(&self) -> usize {
+        self.robots
+            .iter()
+            .filter(|r| r.pos.x > self.max_x / 2 && r.pos.y > self.max_y / 2)
+            .count()
+    }
+
+    fn get_largest_contiguous_region(&self) -> usize {
+        let by_pos = self
+            .robots
+            .iter()
+            .map(|r| ((r.pos.x, r.pos.y), *r))
+            .collect::<HashMap<_, _>>();
+        let mut seen = HashSet::<Robot>::default();
+        let mut largest = 0;
+        for r in self.robots.iter() {
+            if !seen.contains(r) {
+                let region = Self::find_region_of(*r, &by_pos);
+                if region.len() > largest {
+                    largest = region.len();
+                }
+                // println!("found region of size: {:?}", region.len());
+                seen.extend(region);
+            }
+        }
+        largest
+    }
+    fn check_point(by_pos: &HashMap<(isize, isize), Robot>, x: isize, y: isize) -> Option<Robot> {
+        by_pos.get(&(x, y)).copied()
+    }
+    fn find_region_of(robot: Robot, by_pos: &HashMap<(isize, isize), Robot>) -> HashSet<Robot> {
+        let mut set = HashSet::<Robot>::default();
+
+        // println!("finding region around {:?}", robot);
+
+        set.insert(robot);
+
+        let mut neighbors = Self::neighbors_of(robot, by_pos);
+
+        while !neighbors.is_empty() {
+            set.extend(neighbors.clone());
+            neighbors = neighbors
+                .into_iter()
+                .flat_map(|robot| Self::neighbors_of(robot, by_pos))
+                .unique()
+                .filter(|robot| !set.contains(robot))
+                .collect();
+        }
+        set
+    }
+    fn neighbors_of(robot: Robot, by_pos: &HashMap<(isize, isize), Robot>) -> Vec<Robot> {
+        let (x, y) = (robot.pos.x, robot.pos.y);
+        [(x, y + 1), (x + 1, y), (x, y - 1), (x - 1, y)]
+            .into_iter()
+            .filter_map(|(x, y)| Self::check_point(by_pos, x, y))
+            .collect_vec()
+    }
+}
+
+/// The robots wandering round the bathroom, one second per step, forever.
+pub struct Robots;
+
+impl Simulation for Robots {
+    type State = RobotGrid;
+
+    fn step(&self, grid: &mut RobotGrid) {
+        grid.do_iteration();
+    }
+
+    fn render(&self, grid: &RobotGrid) -> Option<CharGrid> {
+        Day14::grid(grid, &Params::new())
+    }
+}
+
+pub fn parse(content: &str) -> Result<RobotGrid, String> {
+    let robot_re = Regex::new(r"^p=([0-9]+),([0-9]+) v=(-?[0-9]+),(-?[0-9]+)$").unwrap();
+
+    let robots = content
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .map(|(i, line)| {
+            let captures = robot_re
+                .captures(line.trim())
+                .ok_or_else(|| format!("line {} isn't a robot", i + 1))?;
+            let n = |j: usize| {
+                captures[j]
+                    .parse::<isize>()
+                    .map_err(|e| format!("line {}: {}", i + 1, e))
+            };
+            Ok(Robot {
+                pos: Vector2::new(n(1)?, n(2)?),
+                vel: Vector2::new(n(3)?, n(4)?),
+            })
+        })
+        .collect::<Result<Vec<_>, String>>()?;
+    if robots.is_empty() {
+        return Err("there are no robots".to_string());
+    }
+
+    Ok(RobotGrid {
+        robots,
+        max_x: MAX_X,
+        max_y: MAX_Y,
+    })
+}
+
+/// How big the real bathroom is.
+pub const MAX_X: isize = 101;
+pub const MAX_Y: isize = 103;
+/// How long part 1 waits before counting robots.
+pub const PART1_STEPS: isize = 100;
+/// How many seconds part 2 looks through for the tree.
+pub const PART2_STEPS: u32 = 100000;
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    const DAY: u8 = 14;
+    type Input = RobotGrid;
+
+    fn parse(input: &str) -> Result<RobotGrid, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<RobotGrid>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |grid: &RobotGrid, params: &Params| {
+                    solve_part1(&grid.with_params(params), params.get("steps", PART1_STEPS)).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |grid: &RobotGrid, params: &Params| {
+                    part2_nopar(grid.with_params(params)).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "parallel",
+                run: |grid: &RobotGrid, params: &Params| {
+                    solve_part2(&grid.with_params(params)).into()
+                },
+            },
+        ]
+    }
+
+    fn params() -> Vec<ParamSpec> {
+        // Every cell of the bathroom is drawn, and a zero-sized one would divide by zero.
+        vec![
+            ParamSpec::new(
+                "max_x",
+                MAX_X,
+                "width of the bathroom (11 for the examples)",
+            )
+            .between(1, 1000),
+            ParamSpec::new(
+                "max_y",
+                MAX_Y,
+                "height of the bathroom (7 for the examples)",
+            )
+            .between(1, 1000),
+            ParamSpec::new(
+                "steps",
+                PART1_STEPS,
+                "seconds part 1 waits before counting robots",
+            ),
+        ]
+    }
+
+    fn grid(grid: &RobotGrid, params: &Params) -> Option<CharGrid> {
+        let grid = grid.with_params(params);
+        let counts = grid.robots.iter().counts_by(|r| (r.pos.x, r.pos.y));
+        Some(CharGrid::from_fn(
+            grid.max_x as usize,
+            grid.max_y as usize,
+            |x, y| match counts.get(&(x as isize, y as isize)) {
+                Some(&n) => char::from_digit(n.min(9) as u32, 10).unwrap(),
+                None => '.',
+            },
+        ))
+    }
+
+    fn pictures(grid: &RobotGrid, params: &Params, answers: &[(u8, Answer)]) -> Vec<Picture> {
+        let Some(Answer::Int(index)) = answers
+            .iter()
+            .find(|(part, _)| *part == 2)
+            .map(|(_, answer)| answer)
+        else {
+            return Vec::new();
+        };
+        // Part 2's answer `i` is the grid after `i + 1` iterations.
+        let frame = grid.with_params(params).after(*index as isize + 1);
+        Self::grid(&frame, &Params::new())
+            .map(|grid| Picture {
+                title: format!("The robots after {} seconds", index + 1),
+                grid,
+            })
+            .into_iter()
+            .collect()
+    }
+
+    fn scale(input: &str, factor: usize) -> Option<String> {
+        Some(scale::repeat_lines(input, factor))
+    }
+
+    fn simulation(grid: &RobotGrid, params: &Params) -> Option<Box<dyn Playback>> {
+        Some(Box::new(Driver::new(
+            Robots,
+            grid.with_params(params),
+            SNAPSHOT_EVERY,
+        )))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?, PART1_STEPS).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(part2_nopar(parse(input)?).into())
+}
+
+pub fn solve_part1(grid: &RobotGrid, steps: isize) -> usize {
+    let mut grid = grid.clone();
+    for _ in 0..steps {
+        grid.do_iteration();
+    }
+    trace!(
+        "bl: {:?}, br: {:?}, tl: {:?}, tr: {:?}",
+        grid.n_robots_in_bottom_left(),
+        grid.n_robots_in_bottom_right(),
+        grid.n_robots_in_top_left(),
+        grid.n_robots_in_top_right()
+    );
+    grid.n_robots_in_bottom_left()
+        * grid.n_robots_in_bottom_right()
+        * grid.n_robots_in_top_left()
+        * grid.n_robots_in_top_right()
+}
+
+pub fn solve_part2(grid: &RobotGrid) -> u32 {
+    let progress = Progress::new("day 14 part 2", PART2_STEPS as u64);
+    let token = cancel::current();
+    (0..PART2_STEPS)
+        .into_par_iter()
+        .map(|i| {
+            token.check();
+            progress.tick();
+            // Index i is the grid after i + 1 iterations, same as in `part2_nopar`.
+            let region_size = grid.after(i as isize + 1).get_largest_contiguous_region();
+            // Ties go to the earliest index.
+            (region_size, Reverse(i))
+        })
+        .max()
+        .map_or(0, |(_, Reverse(i))| i)
+}
+
+fn part2_nopar(mut grid: RobotGrid) -> u32 {
+    let mut largest = 0;
+    let mut largest_index = 0;
+    let progress = Progress::new("day 14 part 2", PART2_STEPS as u64);
+
+    for i in 0..PART2_STEPS {
+        // println!("still largest {:?} at {:?}", largest, largest_index);
+        progress.tick();
+        cancel::check();
+        grid.do_iteration();
+        let region_size = grid.get_largest_contiguous_region() as u32;
+        if largest < region_size {
+            trace!(
+                "\n new largest region found at index {:?}: len {:?}",
+                i,
+                region_size
+            );
+            largest = region_size;
+            largest_index = i;
+        };
+        // }
+    }
+    largest_index
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn regions_join_robots_side_by_side() {
+        // A row of three and a column of two, nowhere near each other's transposes.
+        let robots = [(3, 1), (4, 1), (5, 1), (8, 4), (8, 5)]
+            .into_iter()
+            .map(|(x, y)| Robot {
+                pos: Vector2::new(x, y),
+                vel: Vector2::new(0, 0),
+            })
+            .collect();
+        let grid = RobotGrid {
+            robots,
+            max_x: 11,
+            max_y: 7,
+        };
+        assert_eq!(grid.get_largest_contiguous_region(), 3);
+    }
+
+    #[test]
+    fn part2_finds_the_first_time_robots_line_up() {
+        // Two robots close in on a still one from either side and meet it in a row of three
+        // after 5 seconds, which is index 4. On an 11 wide grid that happens again every 11.
+        let mut grid = parse("p=9,3 v=1,0\np=10,3 v=-1,0\np=4,3 v=0,0\n").unwrap();
+        (grid.max_x, grid.max_y) = (11, 7);
+        assert_eq!(grid.after(5).get_largest_contiguous_region(), 3);
+        assert_eq!(solve_part2(&grid), 4);
+        assert_eq!(part2_nopar(grid), 4);
+    }
+}
@@ -0,0 +1,591 @@
+use crate::answer::Answer;
+use crate::collections::HashMap;
+use crate::grid::CharGrid;
+use crate::params::{ParamSpec, Params};
+use crate::simulation::{Driver, Playback, Simulation, SNAPSHOT_EVERY};
+use crate::solution::{Solution, Variant};
+use crate::tile::Tile;
+use crate::trace;
+use cgmath::Vector2;
+use itertools::Itertools;
+use std::{fmt, marker::PhantomData};
+
+#[derive(Debug, Clone)]
+pub struct Warehouse {
+    tiles: HashMap<Vector2<i32>, WarehouseTile>,
+    robot_position: Vector2<i32>,
+}
+impl Warehouse {
+    /// Reads the map, up to the blank line before the moves.
+    pub fn from_string(s: &str) -> Result<Self, String> {
+        let mut map = HashMap::<Vector2<i32>, WarehouseTile>::default();
+        let mut robot_position = None;
+        for (y, line) in s.lines().enumerate() {
+            if line.is_empty() {
+                break;
+            }
+            for (x, c) in line.chars().enumerate() {
+                let tile = WarehouseTile::from_char(c)
+                    .map_err(|e| format!("line {}, column {}: {}", y + 1, x + 1, e))?;
+                let pos = Vector2::new(x as i32, y as i32);
+                if tile == WarehouseTile::Robot {
+                    robot_position = Some(pos);
+                }
+                map.insert(pos, tile);
+            }
+        }
+        let robot_position = robot_position.ok_or("no robot found")?;
+        Ok(Self {
+            tiles: map,
+            robot_position,
+        })
+    }
+
+    fn do_move(&mut self, dir: &Direction) {
+        let new_robot_pos = self.robot_position + dir.to_vec2();
+        let new_robot_tile = self.tiles.get(&new_robot_pos);
+
+        // println!(
+        //     "movement vector: {:?}, takes our robot ({:?}) to {:?}",
+        //     dir.to_vec2(),
+        //     self.robot_position,
+        //     self.robot_position + dir.to_vec2()
+        // );
+        // println!(
+        //     "found next to robot: {:?} at {:?}",
+        //     new_robot_tile, new_robot_pos
+        // );
+
+        match new_robot_tile {
+            Some(WarehouseTile::Free) => {
+                self.tiles.insert(self.robot_position, WarehouseTile::Free);
+                self.tiles.insert(new_robot_pos, WarehouseTile::Robot);
+                self.robot_position = new_robot_pos;
+            }
+            Some(WarehouseTile::Box) => {
+                if !self.move_box(new_robot_pos, dir) {
+                    return;
+                }
+                self.tiles.insert(self.robot_position, WarehouseTile::Free);
+                self.tiles.insert(new_robot_pos, WarehouseTile::Robot);
+                self.robot_position = new_robot_pos;
+            }
+            _ => (),
+        };
+    }
+
+    fn move_box(&mut self, from: Vector2<i32>, dir: &Direction) -> bool {
+        let next_pos = from + dir.to_vec2();
+        let next_tile = self.tiles.get(&next_pos);
+
+        match next_tile {
+            Some(WarehouseTile::Free) => {
+                self.tiles.insert(next_pos, WarehouseTile::Box);
+                true
+            }
+            Some(WarehouseTile::Box) => self.move_box(next_pos, dir),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for Warehouse {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max_x = self.tiles.keys().max_by_key(|v| v.x).unwrap().x;
+        let max_y = self.tiles.keys().max_by_key(|v| v.y).unwrap().y;
+
+        for i in 0..=max_y {
+            for j in 0..=max_x {
+                write!(
+                    f,
+                    "{}",
+                    self.tiles
+                        .get(&Vector2::new(j, i))
+                        .unwrap_or_else(|| panic!("couldn't file tile at x={}, y={}", j, i)),
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Tile)]
+enum WarehouseTile {
+    #[tile('#')]
+    Wall,
+    #[tile('O')]
+    Box,
+    #[tile('@')]
+    Robot,
+    #[tile('.')]
+    Free,
+}
+
+#[derive(Debug, Clone)]
+pub struct WarehousePart2 {
+    tiles: HashMap<Vector2<i32>, WarehouseTilePart2>,
+    robot_position: Vector2<i32>,
+}
+
+impl WarehousePart2 {
+    pub fn from_warehouse(warehouse: &Warehouse) -> Self {
+        let s = warehouse.to_string();
+
+        let pt2_string = s
+            .chars()
+            .flat_map(|c| match c {
+                '#' => "##".chars(),
+                '.' => "..".chars(),
+                '@' => "@.".chars(),
+                'O' => "[]".chars(),
+                '\n' => "\n".chars(),
+                _ => "".chars(),
+            })
+            .collect::<String>();
+
+        WarehousePart2::from_string(pt2_string)
+            .expect("widening a warehouse only writes its own tiles")
+    }
+    fn from_string(s: String) -> Result<Self, String> {
+        let mut map = HashMap::<Vector2<i32>, WarehouseTilePart2>::default();
+        let mut robot_position = None;
+        for (y, line) in s.lines().enumerate() {
+            for (x, c) in line.chars().enumerate() {
+                let tile = WarehouseTilePart2::from_char(c)
+                    .map_err(|e| format!("line {}, column {}: {}", y + 1, x + 1, e))?;
+                let pos = Vector2::new(x as i32, y as i32);
+                if tile == WarehouseTilePart2::Robot {
+                    robot_position = Some(pos);
+                }
+                map.insert(pos, tile);
+            }
+        }
+        let robot_position = robot_position.ok_or("no robot found")?;
+        Ok(Self {
+            tiles: map,
+            robot_position,
+        })
+    }
+    fn do_move(&mut self, dir: &Direction) {
+        let new_robot_pos = self.robot_position + dir.to_vec2();
+        let new_robot_tile = self.tiles.get(&new_robot_pos);
+
+        match new_robot_tile {
+            Some(WarehouseTilePart2::Free) => {
+                self.tiles
+                    .insert(self.robot_position, WarehouseTilePart2::Free);
+                self.tiles.insert(new_robot_pos, WarehouseTilePart2::Robot);
+                self.robot_position = new_robot_pos;
+            }
+            Some(WarehouseTilePart2::LeftBox) if dir == &Direction::Right => {
+                if !self.can_move(new_robot_pos, dir) {
+                    return;
+                }
+                self.move_box(new_robot_pos, dir);
+                self.tiles
+                    .insert(self.robot_position, WarehouseTilePart2::Free);
+                self.tiles.insert(new_robot_pos, WarehouseTilePart2::Robot);
+                self.robot_position = new_robot_pos;
+            }
+            Some(WarehouseTilePart2::RightBox) if dir == &Direction::Left => {
+                if !self.move_box(new_robot_pos, dir) {
+                    return;
+                }
+                self.tiles
+                    .insert(self.robot_position, WarehouseTilePart2::Free);
+                self.tiles.insert(new_robot_pos, WarehouseTilePart2::Robot);
+                self.robot_position = new_robot_pos;
+            }
+            Some(WarehouseTilePart2::LeftBox) | Some(WarehouseTilePart2::RightBox) => {
+                let other_side_offset = if new_robot_tile == Some(&WarehouseTilePart2::LeftBox) {
+                    Direction::Right.to_vec2()
+                } else {
+                    Direction::Left.to_vec2()
+                };
+                let other_side_of_box_pos = new_robot_pos + other_side_offset;
+                if self.can_move(new_robot_pos, dir) && self.can_move(other_side_of_box_pos, dir) {
+                    self.naive_move(new_robot_pos, dir);
+                    self.naive_move(other_side_of_box_pos, dir);
+                    self.tiles
+                        .insert(self.robot_position, WarehouseTilePart2::Free);
+                    self.tiles.insert(new_robot_pos, WarehouseTilePart2::Robot);
+
+                    self.robot_position = new_robot_pos;
+                };
+            }
+            _ => (),
+        };
+    }
+
+    fn move_box(&mut self, from: Vector2<i32>, dir: &Direction) -> bool {
+        let next_pos = from + dir.to_vec2();
+        let next_tile = self.tiles.get(&next_pos);
+        let this_tile = *self.tiles.get(&from).unwrap();
+
+        let this_box_other_side_offset = if this_tile == WarehouseTilePart2::LeftBox {
+            Direction::Right.to_vec2()
+        } else {
+            Direction::Left.to_vec2()
+        };
+        let this_box_other_tile = if this_tile == WarehouseTilePart2::LeftBox {
+            WarehouseTilePart2::RightBox
+        } else {
+            WarehouseTilePart2::LeftBox
+        };
+
+        let is_horizontal_move = dir == &Direction::Right || dir == &Direction::Left;
+
+        let other_box_other_side_offset = if next_tile == Some(&WarehouseTilePart2::LeftBox) {
+            Direction::Right.to_vec2()
+        } else {
+            Direction::Left.to_vec2()
+        };
+
+        let other_side_of_other_box_pos = next_pos + other_box_other_side_offset;
+
+        let other_box_tile = if next_tile == Some(&WarehouseTilePart2::LeftBox) {
+            WarehouseTilePart2::RightBox
+        } else {
+            WarehouseTilePart2::LeftBox
+        };
+
+        let beside_from = from + this_box_other_side_offset;
+
+        assert!(
+            this_tile == WarehouseTilePart2::LeftBox || this_tile == WarehouseTilePart2::RightBox
+        );
+
+        match next_tile {
+            Some(WarehouseTilePart2::Free) => {
+                self.tiles.insert(from, WarehouseTilePart2::Free);
+                self.tiles.insert(beside_from, WarehouseTilePart2::Free);
+                self.tiles.insert(next_pos, this_tile);
+                self.tiles
+                    .insert(next_pos + this_box_other_side_offset, this_box_other_tile);
+                true
+            }
+            Some(WarehouseTilePart2::LeftBox) | Some(WarehouseTilePart2::RightBox)
+                if self.can_move(next_pos, dir)
+                    && (!is_horizontal_move || self.can_move(other_side_of_other_box_pos, dir)) =>
+            {
+                self.move_box(next_pos, dir);
+
+                if !is_horizontal_move && other_box_other_side_offset != this_box_other_side_offset
+                {
+                    self.move_box(next_pos + other_box_other_side_offset, dir);
+                }
+                self.tiles.insert(from, WarehouseTilePart2::Free);
+                self.tiles.insert(next_pos, this_tile);
+                if !is_horizontal_move {
+                    self.tiles.insert(beside_from, WarehouseTilePart2::Free);
+                    self.tiles
+                        .insert(next_pos + this_box_other_side_offset, other_box_tile);
+                };
+                true
+            }
+
+            _ => false,
+        }
+    }
+
+    fn naive_move(&mut self, from: Vector2<i32>, dir: &Direction) -> bool {
+        let next_pos = from + dir.to_vec2();
+        let next_tile = self.tiles.get(&next_pos);
+        let this_tile = *self.tiles.get(&from).unwrap();
+
+        match next_tile {
+            Some(WarehouseTilePart2::Free) => {
+                self.tiles.insert(from, WarehouseTilePart2::Free);
+                self.tiles.insert(next_pos, this_tile);
+                true
+            }
+            Some(WarehouseTilePart2::Wall) => false,
+            _ => {
+                let other_box_other_side_offset = if next_tile == Some(&WarehouseTilePart2::LeftBox)
+                {
+                    Direction::Right.to_vec2()
+                } else {
+                    Direction::Left.to_vec2()
+                };
+
+                let other_side_of_other_box_pos = next_pos + other_box_other_side_offset;
+                if self.can_move(next_pos, dir)
+                    && self.can_move(other_side_of_other_box_pos, dir)
+                    && self.naive_move(next_pos, dir)
+                    && self.naive_move(other_side_of_other_box_pos, dir)
+                {
+                    self.tiles.insert(from, WarehouseTilePart2::Free);
+                    self.tiles.insert(next_pos, this_tile);
+                    true
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    fn can_move(&self, from: Vector2<i32>, dir: &Direction) -> bool {
+        let next_pos = from + dir.to_vec2();
+        let next_tile = self.tiles.get(&next_pos);
+        let this_tile = *self.tiles.get(&from).unwrap();
+
+        assert!(
+            this_tile == WarehouseTilePart2::LeftBox || this_tile == WarehouseTilePart2::RightBox
+        );
+
+        match next_tile {
+            Some(WarehouseTilePart2::Free) => true,
+            Some(WarehouseTilePart2::LeftBox) | Some(WarehouseTilePart2::RightBox)
+                if dir == &Direction::Up || dir == &Direction::Down =>
+            {
+                let other_side_offset = if next_tile == Some(&WarehouseTilePart2::LeftBox) {
+                    Direction::Right.to_vec2()
+                } else {
+                    Direction::Left.to_vec2()
+                };
+                let other_side_of_box_pos = next_pos + other_side_offset;
+                self.can_move(next_pos, dir) && self.can_move(other_side_of_box_pos, dir)
+            }
+            Some(WarehouseTilePart2::LeftBox) | Some(WarehouseTilePart2::RightBox) => {
+                self.can_move(next_pos, dir)
+            }
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for WarehousePart2 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let max_x = self.tiles.keys().max_by_key(|v| v.x).unwrap().x;
+        let max_y = self.tiles.keys().max_by_key(|v| v.y).unwrap().y;
+
+        for i in 0..=max_y {
+            for j in 0..=max_x {
+                write!(
+                    f,
+                    "{}",
+                    self.tiles
+                        .get(&Vector2::new(j, i))
+                        .unwrap_or_else(|| panic!("couldn't file tile at x={}, y={}", j, i)),
+                )?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Tile)]
+enum WarehouseTilePart2 {
+    #[tile('#')]
+    Wall,
+    #[tile('[')]
+    LeftBox,
+    #[tile(']')]
+    RightBox,
+    #[tile('@')]
+    Robot,
+    #[tile('.')]
+    Free,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Tile)]
+pub enum Direction {
+    #[tile('^')]
+    Up,
+    #[tile('>')]
+    Right,
+    #[tile('v')]
+    Down,
+    #[tile('<')]
+    Left,
+}
+
+impl Direction {
+    fn to_vec2(self) -> Vector2<i32> {
+        match self {
+            Self::Up => Vector2::new(0, -1),
+            Self::Right => Vector2::new(1, 0),
+            Self::Down => Vector2::new(0, 1),
+            Self::Left => Vector2::new(-1, 0),
+        }
+    }
+}
+
+/// Either warehouse, as far as the robot's concerned.
+pub trait Floor: Clone + fmt::Display {
+    fn do_move(&mut self, dir: &Direction);
+}
+
+impl Floor for Warehouse {
+    fn do_move(&mut self, dir: &Direction) {
+        Warehouse::do_move(self, dir)
+    }
+}
+
+impl Floor for WarehousePart2 {
+    fn do_move(&mut self, dir: &Direction) {
+        WarehousePart2::do_move(self, dir)
+    }
+}
+
+/// The robot working through its list of moves, one per step, in either warehouse.
+pub struct Robot<W> {
+    moves: Vec<Direction>,
+    warehouse: PhantomData<W>,
+}
+
+/// The warehouse, and which move the robot makes next.
+#[derive(Debug, Clone)]
+pub struct Shift<W> {
+    warehouse: W,
+    next: usize,
+}
+
+impl<W: Floor> Robot<W> {
+    fn new(warehouse: W, moves: &[Direction]) -> (Robot<W>, Shift<W>) {
+        let robot = Robot {
+            moves: moves.to_vec(),
+            warehouse: PhantomData,
+        };
+        (robot, Shift { warehouse, next: 0 })
+    }
+}
+
+impl<W: Floor> Simulation for Robot<W> {
+    type State = Shift<W>;
+
+    fn step(&self, shift: &mut Shift<W>) {
+        shift.warehouse.do_move(&self.moves[shift.next]);
+        shift.next += 1;
+    }
+
+    fn is_done(&self, shift: &Shift<W>) -> bool {
+        shift.next == self.moves.len()
+    }
+
+    fn render(&self, shift: &Shift<W>) -> Option<CharGrid> {
+        Some(CharGrid::from(shift.warehouse.to_string().as_str()))
+    }
+}
+
+pub fn parse(content: &str) -> Result<(Warehouse, Vec<Direction>), String> {
+    let warehouse = Warehouse::from_string(content)?;
+
+    let moves = content
+        .chars()
+        .filter_map(|c| Direction::from_char(c).ok())
+        .collect_vec();
+
+    Ok((warehouse, moves))
+}
+
+/// Whether part 2 runs in the twice-as-wide warehouse.
+pub const WIDEN: bool = true;
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    const DAY: u8 = 15;
+    type Input = (Warehouse, Vec<Direction>);
+
+    fn parse(input: &str) -> Result<(Warehouse, Vec<Direction>), String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<(Warehouse, Vec<Direction>)>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |(warehouse, moves): &(Warehouse, Vec<Direction>), _: &Params| {
+                    solve_part1(warehouse, moves).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |(warehouse, moves): &(Warehouse, Vec<Direction>), params: &Params| {
+                    solve_part2(warehouse, moves, params.get("widen", WIDEN)).into()
+                },
+            },
+        ]
+    }
+
+    fn params() -> Vec<ParamSpec> {
+        vec![ParamSpec::new(
+            "widen",
+            WIDEN,
+            "whether part 2 doubles the warehouse's width (without it, part 2 is part 1)",
+        )]
+    }
+
+    fn grid((warehouse, _): &(Warehouse, Vec<Direction>), _: &Params) -> Option<CharGrid> {
+        Some(CharGrid::from(warehouse.to_string().as_str()))
+    }
+
+    fn simulation(
+        (warehouse, moves): &(Warehouse, Vec<Direction>),
+        params: &Params,
+    ) -> Option<Box<dyn Playback>> {
+        Some(if params.get("widen", WIDEN) {
+            let (robot, shift) = Robot::new(WarehousePart2::from_warehouse(warehouse), moves);
+            Box::new(Driver::new(robot, shift, SNAPSHOT_EVERY))
+        } else {
+            let (robot, shift) = Robot::new(warehouse.clone(), moves);
+            Box::new(Driver::new(robot, shift, SNAPSHOT_EVERY))
+        })
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let (warehouse, moves) = parse(input)?;
+    Ok(solve_part1(&warehouse, &moves).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let (warehouse, moves) = parse(input)?;
+    Ok(solve_part2(&warehouse, &moves, WIDEN).into())
+}
+
+pub fn solve_part1(warehouse: &Warehouse, moves: &[Direction]) -> usize {
+    let (robot, mut shift) = Robot::new(warehouse.clone(), moves);
+    robot.run_to_end(&mut shift);
+    shift
+        .warehouse
+        .tiles
+        .iter()
+        .filter(|(_, tile)| **tile == WarehouseTile::Box)
+        .map(|(&pos, _)| get_gps_coord(pos))
+        .sum()
+}
+
+fn get_gps_coord(v: Vector2<i32>) -> usize {
+    (v.y as usize * 100) + v.x as usize
+}
+
+pub fn solve_part2(warehouse: &Warehouse, moves: &[Direction], widen: bool) -> usize {
+    if !widen {
+        return solve_part1(warehouse, moves);
+    }
+    let (robot, mut shift) = Robot::new(WarehousePart2::from_warehouse(warehouse), moves);
+    trace!("{}", shift.warehouse);
+    robot.run_to_end(&mut shift);
+    let warehouse = shift.warehouse;
+    trace!("{}", warehouse);
+
+    let max_x = warehouse.tiles.keys().max_by_key(|v| v.x).unwrap().x;
+    let max_y = warehouse.tiles.keys().max_by_key(|v| v.y).unwrap().y;
+    trace!("max x: {:?}, max y: {:?}", max_x, max_y);
+    warehouse
+        .tiles
+        .iter()
+        .filter(|(_, tile)| **tile == WarehouseTilePart2::LeftBox)
+        .map(|(&pos, _)| {
+            let n = get_gps_coord(pos);
+            trace!("{:?}: {}", pos, n);
+            n
+        })
+        .sum()
+}
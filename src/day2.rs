@@ -0,0 +1,174 @@
+use crate::answer::Answer;
+use crate::cancel;
+use crate::params::{ParamSpec, Params};
+use crate::scale;
+use crate::solution::{Solution, Variant};
+use itertools::Itertools;
+
+pub fn parse(content: &str) -> Result<Vec<Vec<usize>>, String> {
+    let mut parsed_content = Vec::<Vec<usize>>::new();
+    for (i, line) in content.trim().lines().enumerate() {
+        let parts = line.split_whitespace();
+        let parsed_parts = parts
+            .map(|x| x.parse::<usize>())
+            .collect::<Result<_, _>>()
+            .map_err(|e| format!("line {}: {}", i + 1, e))?;
+        parsed_content.push(parsed_parts);
+    }
+    // println!("{:?}",parsed_content);
+    Ok(parsed_content)
+}
+
+/// The biggest step between two levels that's still safe.
+pub const MAX_STEP: usize = 3;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    type Input = Vec<Vec<usize>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<usize>>, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<Vec<Vec<usize>>>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |reports: &Vec<Vec<usize>>, params: &Params| {
+                    solve_part1(reports, params.get("max_step", MAX_STEP)).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |reports: &Vec<Vec<usize>>, params: &Params| {
+                    solve_part2(reports, params.get("max_step", MAX_STEP)).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "recursive",
+                run: |reports: &Vec<Vec<usize>>, params: &Params| {
+                    part2_2(reports.clone(), params.get("max_step", MAX_STEP)).into()
+                },
+            },
+        ]
+    }
+
+    fn params() -> Vec<ParamSpec> {
+        vec![ParamSpec::new(
+            "max_step",
+            MAX_STEP,
+            "biggest difference between neighbouring levels in a safe report",
+        )]
+    }
+
+    fn scale(input: &str, factor: usize) -> Option<String> {
+        Some(scale::repeat_lines(input, factor))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?, MAX_STEP).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?, MAX_STEP).into())
+}
+
+pub fn solve_part1(reports: &[Vec<usize>], max_step: usize) -> usize {
+    let args = reports.to_vec();
+    let mut result = 0;
+    for v in args {
+        let mut it = v.into_iter().tuple_windows::<(usize, usize)>();
+        let safe = it
+            .clone()
+            .all(|(x1, x2)| x1 > x2 && x1.abs_diff(x2) <= max_step)
+            || it.all(|(x1, x2)| x1 < x2 && x1.abs_diff(x2) <= max_step);
+        if safe {
+            result += 1;
+        };
+    }
+    result
+}
+
+//not a good approach!!!
+fn part2_2(args: Vec<Vec<usize>>, max_step: usize) -> usize {
+    let mut result = 0;
+    for v in args {
+        let orig_size = v.len();
+        if is_safe(v.clone(), usize::gt, orig_size, max_step)
+            || is_safe(v.clone(), usize::lt, orig_size, max_step)
+        {
+            result += 1;
+        }
+    }
+    result
+}
+
+fn is_safe(
+    v: Vec<usize>,
+    operator: fn(&usize, &usize) -> bool,
+    orig_size: usize,
+    max_step: usize,
+) -> bool {
+    cancel::check();
+    let mut pkbl = v.iter().peekable();
+    let mut i = 0;
+    while let Some(x1) = pkbl.next() {
+        if let Some(x2) = pkbl.peek() {
+            if !(operator(x1, x2) && x1.abs_diff(**x2) <= max_step) {
+                if v.len() != orig_size {
+                    return false;
+                };
+                if is_safe(
+                    v.iter()
+                        .enumerate()
+                        .filter(|(j, _)| *j != i)
+                        .map(|(_, e)| *e)
+                        .collect(),
+                    operator,
+                    orig_size,
+                    max_step,
+                ) {
+                    return true;
+                } else {
+                    continue;
+                }
+            }
+        } else if v.len() == orig_size {
+            return is_safe(v[..v.len() - 1].to_vec(), operator, orig_size, max_step);
+        }
+        i += 1;
+    }
+    true
+}
+
+pub fn solve_part2(reports: &[Vec<usize>], max_step: usize) -> usize {
+    let args = reports.to_vec();
+    let mut result = 0;
+
+    for v in args.clone() {
+        for (i, _) in v.iter().enumerate() {
+            let v_without_element: Vec<usize> = v
+                .iter()
+                .enumerate()
+                .filter(|(j, _)| *j != i)
+                .map(|(_, e)| *e)
+                .collect();
+            let mut it = v_without_element.iter().tuple_windows::<(&usize, &usize)>();
+            let safe = it
+                .clone()
+                .all(|(x1, x2)| x1 > x2 && x1.abs_diff(*x2) <= max_step)
+                || it.all(|(x1, x2)| x1 < x2 && x1.abs_diff(*x2) <= max_step);
+            if safe {
+                result += 1;
+                break;
+            };
+        }
+    }
+    result
+}
@@ -0,0 +1,84 @@
+use crate::answer::Answer;
+use crate::params::Params;
+use crate::solution::{Solution, Variant};
+use crate::trace;
+use regex::Regex;
+
+/// Any text is corrupted memory, so only an empty input is turned away.
+pub fn parse(content: &str) -> Result<String, String> {
+    if content.trim().is_empty() {
+        return Err("the memory is empty".to_string());
+    }
+    Ok(content.to_string())
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    type Input = String;
+
+    fn parse(input: &str) -> Result<String, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<String>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |memory: &String, _: &Params| solve_part1(memory).into(),
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |memory: &String, _: &Params| solve_part2(memory).into(),
+            },
+        ]
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?).into())
+}
+
+pub fn solve_part1(s: &str) -> usize {
+    let mut result = 0;
+
+    let re = Regex::new(r"(mul\(([0-9]{1,3}),([0-9]{1,3})\))").unwrap();
+    for mul_str in re.captures_iter(s) {
+        let a = mul_str.get(2).unwrap().as_str().parse::<usize>().unwrap();
+        let b = mul_str.get(3).unwrap().as_str().parse::<usize>().unwrap();
+        result += a * b;
+    }
+    result
+}
+
+pub fn solve_part2(s: &str) -> usize {
+    let mut result = 0;
+    let mut ignore_mul = false;
+
+    let re = Regex::new(r"(mul\(([0-9]{1,3}),([0-9]{1,3})\))|((don't)|(do))").unwrap();
+    for mul_str in re.captures_iter(s) {
+        trace!("{:?}", mul_str);
+        let maybe_ignore_command = mul_str.get(4);
+        if let Some(x) = maybe_ignore_command {
+            if x.as_str() == "do" {
+                ignore_mul = false;
+            } else if x.as_str() == "don't" {
+                ignore_mul = true;
+            }
+            continue;
+        }
+        if !ignore_mul {
+            let a = mul_str.get(2).unwrap().as_str().parse::<usize>().unwrap();
+            let b = mul_str.get(3).unwrap().as_str().parse::<usize>().unwrap();
+            result += a * b;
+        }
+    }
+    result
+}
@@ -0,0 +1,155 @@
+use crate::answer::Answer;
+use crate::grid::CharGrid;
+use crate::params::Params;
+use crate::solution::{Solution, Variant};
+
+pub fn parse(content: &str) -> Result<Vec<Vec<char>>, String> {
+    content
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| match c {
+                    // Puzzles can pad the letters that matter with dots, as the second example does.
+                    'X' | 'M' | 'A' | 'S' | '.' => Ok(c),
+                    _ => Err(format!(
+                        "line {}, column {}: {:?} isn't X, M, A, S or '.'",
+                        y + 1,
+                        x + 1,
+                        c
+                    )),
+                })
+                .collect()
+        })
+        .collect()
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    type Input = Vec<Vec<char>>;
+
+    fn parse(input: &str) -> Result<Vec<Vec<char>>, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<Vec<Vec<char>>>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |letters: &Vec<Vec<char>>, _: &Params| solve_part1(letters).into(),
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |letters: &Vec<Vec<char>>, _: &Params| solve_part2(letters).into(),
+            },
+        ]
+    }
+
+    fn grid(letters: &Vec<Vec<char>>, _: &Params) -> Option<CharGrid> {
+        Some(CharGrid::new(letters.clone()))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?).into())
+}
+
+pub fn solve_part1(v: &[Vec<char>]) -> usize {
+    let mut result = 0;
+    for (i, c_vec) in v.iter().enumerate() {
+        for (j, c) in c_vec.iter().enumerate() {
+            if *c == 'X' {
+                for op in [usize::wrapping_add, usize::wrapping_sub] {
+                    if check_horizontal(c_vec, j, op) {
+                        result += 1;
+                    }
+                    if check_vertical(v, i, j, op) {
+                        result += 1;
+                    }
+                    if check_diagonal(v, i, j, op) {
+                        result += 1;
+                    }
+                    if check_other_diagonal(v, i, j, op) {
+                        result += 1;
+                    }
+                }
+            }
+        }
+    }
+    result
+}
+
+fn check_horizontal(v: &[char], j: usize, op: fn(usize, usize) -> usize) -> bool {
+    v.get(op(j, 1)) == Some(&'M') && v.get(op(j, 2)) == Some(&'A') && v.get(op(j, 3)) == Some(&'S')
+}
+
+fn check_vertical(v: &[Vec<char>], i: usize, j: usize, op: fn(usize, usize) -> usize) -> bool {
+    v.get(op(i, 1)).and_then(|row| row.get(j)) == Some(&'M')
+        && v.get(op(i, 2)).and_then(|row| row.get(j)) == Some(&'A')
+        && v.get(op(i, 3)).and_then(|row| row.get(j)) == Some(&'S')
+}
+
+fn check_diagonal(v: &[Vec<char>], i: usize, j: usize, op: fn(usize, usize) -> usize) -> bool {
+    v.get(op(i, 1)).and_then(|row| row.get(op(j, 1))) == Some(&'M')
+        && v.get(op(i, 2)).and_then(|row| row.get(op(j, 2))) == Some(&'A')
+        && v.get(op(i, 3)).and_then(|row| row.get(op(j, 3))) == Some(&'S')
+}
+
+fn check_other_diagonal(
+    v: &[Vec<char>],
+    i: usize,
+    j: usize,
+    op: fn(usize, usize) -> usize,
+) -> bool {
+    let other_op = opposite(op);
+
+    v.get(op(i, 1)).and_then(|row| row.get(other_op(j, 1))) == Some(&'M')
+        && v.get(op(i, 2)).and_then(|row| row.get(other_op(j, 2))) == Some(&'A')
+        && v.get(op(i, 3)).and_then(|row| row.get(other_op(j, 3))) == Some(&'S')
+}
+
+fn opposite(op: fn(usize, usize) -> usize) -> fn(usize, usize) -> usize {
+    if op(0, 1) == 1 {
+        usize::wrapping_sub
+    } else {
+        usize::wrapping_add
+    }
+}
+
+pub fn solve_part2(v: &[Vec<char>]) -> usize {
+    let mut result = 0;
+    for (i, c_vec) in v.iter().enumerate() {
+        for (j, c) in c_vec.iter().enumerate() {
+            if *c == 'A' && check_diagonal_sam(v, i, j, usize::wrapping_add) {
+                result += 1;
+            }
+        }
+    }
+    result
+}
+
+fn check_diagonal_sam(v: &[Vec<char>], i: usize, j: usize, op: fn(usize, usize) -> usize) -> bool {
+    let other_op = opposite(op);
+
+    ((v.get(op(i, 1)).and_then(|row| row.get(op(j, 1))) == Some(&'M')
+        && v.get(other_op(i, 1))
+            .and_then(|row| row.get(other_op(j, 1)))
+            == Some(&'S'))
+        || (v.get(op(i, 1)).and_then(|row| row.get(op(j, 1))) == Some(&'S')
+            && v.get(other_op(i, 1))
+                .and_then(|row| row.get(other_op(j, 1)))
+                == Some(&'M')))
+        && ((v.get(other_op(i, 1)).and_then(|row| row.get(op(j, 1))) == Some(&'M')
+            && v.get(op(i, 1)).and_then(|row| row.get(other_op(j, 1))) == Some(&'S'))
+            || (v.get(other_op(i, 1)).and_then(|row| row.get(op(j, 1))) == Some(&'S')
+                && v.get(op(i, 1)).and_then(|row| row.get(other_op(j, 1))) == Some(&'M')))
+}
@@ -0,0 +1,207 @@
+use crate::answer::Answer;
+use crate::count;
+use crate::params::Params;
+use crate::solution::{Solution, Variant};
+use crate::trace;
+
+#[derive(Copy, Clone, Debug)]
+pub struct Rules(usize, usize);
+
+pub struct Day5Args(Vec<Rules>, Vec<Vec<usize>>);
+
+pub fn parse(content: &str) -> Result<Day5Args, String> {
+    let mut rules = Vec::new();
+    let mut updates = Vec::new();
+    for (i, line) in content.lines().enumerate() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let page = |n: &str| {
+            n.parse::<usize>()
+                .map_err(|e| format!("line {}: {:?}: {}", i + 1, n, e))
+        };
+        match line.split_once('|') {
+            Some((l, r)) => rules.push(Rules(page(l)?, page(r)?)),
+            None => updates.push(line.split(',').map(page).collect::<Result<Vec<_>, _>>()?),
+        }
+    }
+    if updates.is_empty() {
+        return Err("there are no updates".to_string());
+    }
+    Ok(Day5Args(rules, updates))
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    type Input = Day5Args;
+
+    fn parse(input: &str) -> Result<Day5Args, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<Day5Args>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |Day5Args(rules, updates): &Day5Args, _: &Params| {
+                    solve_part1(rules, updates).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |Day5Args(rules, updates): &Day5Args, _: &Params| {
+                    solve_part2(rules, updates).into()
+                },
+            },
+        ]
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    let Day5Args(rules, updates) = parse(input)?;
+    Ok(solve_part1(&rules, &updates).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    let Day5Args(rules, updates) = parse(input)?;
+    Ok(solve_part2(&rules, &updates).into())
+}
+
+pub fn solve_part1(rules: &[Rules], updates: &[Vec<usize>]) -> usize {
+    let mut result = 0;
+    trace!("Rules: {:?}", rules);
+    trace!("Updates: {:?}", updates);
+
+    for update in updates {
+        let relevant_rules = rules
+            .iter()
+            .filter(|Rules(l, r)| update.contains(l) && update.contains(r))
+            .collect::<Vec<&Rules>>();
+        for (i, n) in update.iter().enumerate() {
+            let mut found_before = Vec::new();
+            let mut found_after = Vec::new();
+            let before = get_before(&relevant_rules, *n, &mut found_before);
+            let _after = get_after(&relevant_rules, *n, &mut found_after);
+
+            // println!("Before {:?}: {:?}", n, before);
+            // println!("After {:?}: {:?}", n, after);
+            let _b = String::new();
+            // std::io::stdin().read_line(&mut b).expect("msg");
+            if i != before.len() {
+                break;
+            }
+            if i == update.len() - 1 {
+                let middle = update.get(update.len() / 2).unwrap();
+                trace!("{:?} is middle of {:?}", middle, update);
+                result += middle;
+            }
+
+            // if before.len() == after.len() {
+            //     println!("{:?} in the middle of {:?}", n, update);
+            //     result += n;
+            //     break;
+            // }
+        }
+    }
+
+    result
+}
+
+pub fn solve_part2(rules: &[Rules], updates: &[Vec<usize>]) -> usize {
+    let mut result = 0;
+
+    for update in updates {
+        let relevant_rules = rules
+            .iter()
+            .filter(|Rules(l, r)| update.contains(l) && update.contains(r))
+            .collect::<Vec<&Rules>>();
+
+        let mut middle = Option::None;
+        let mut not_correct = false;
+
+        for (i, n) in update.iter().enumerate() {
+            let mut found_before = Vec::new();
+            let mut found_after = Vec::new();
+
+            let before = get_before(&relevant_rules, *n, &mut found_before);
+            let after = get_after(&relevant_rules, *n, &mut found_after);
+
+            // println!("Before {:?}: {:?}", n, before);
+            // println!("After {:?}: {:?}", n, after);
+
+            if before.len() == after.len() {
+                trace!("{:?} in the middle of {:?}", n, update);
+                middle = Some(n);
+            }
+            if i != before.len() {
+                not_correct = true;
+            }
+            if let Some(x) = middle {
+                if not_correct {
+                    result += x;
+                    break;
+                }
+            }
+        }
+    }
+
+    result
+}
+fn get_before<'a>(
+    rules: &'a Vec<&'a Rules>,
+    n: usize,
+    found_before: &mut Vec<usize>,
+) -> Vec<&'a usize> {
+    count!("get_before_calls");
+    count!("rules_scanned", rules.len());
+    let mut before = rules
+        .iter()
+        .filter(|Rules(_, r)| *r == n && !found_before.contains(r))
+        .map(|Rules(l, _)| l)
+        .collect::<Vec<_>>();
+
+    found_before.extend(before.clone());
+
+    if before.is_empty() {
+        return Vec::new();
+    }
+
+    before.extend(
+        before
+            .iter()
+            .flat_map(|n| get_before(rules, **n, found_before))
+            .collect::<Vec<_>>(),
+    );
+    before
+}
+
+fn get_after<'a>(
+    rules: &'a Vec<&'a Rules>,
+    n: usize,
+    found_after: &mut Vec<usize>,
+) -> Vec<&'a usize> {
+    count!("get_after_calls");
+    count!("rules_scanned", rules.len());
+    let mut after = rules
+        .iter()
+        .filter(|Rules(l, _)| *l == n && !found_after.contains(l))
+        .map(|Rules(_, r)| r)
+        .collect::<Vec<_>>();
+
+    found_after.extend(after.clone());
+
+    if after.is_empty() {
+        return Vec::new();
+    }
+    after.extend(
+        after
+            .iter()
+            .flat_map(|n| get_after(rules, **n, found_after))
+            .collect::<Vec<_>>(),
+    );
+    after
+}
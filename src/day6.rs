@@ -0,0 +1,262 @@
+use crate::answer::Answer;
+use crate::cancel;
+use crate::collections::{HashMap, HashSet};
+use crate::count;
+use crate::grid::{CharGrid, Picture};
+use crate::parallel::prelude::*;
+use crate::params::Params;
+use crate::progress::Progress;
+use crate::simulation::{Driver, Playback, Simulation, SNAPSHOT_EVERY};
+use crate::solution::{Solution, Variant};
+use crate::tile::{self, Tile};
+use crate::trace;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct Point(usize, usize);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Tile)]
+enum TileType {
+    #[tile('#')]
+    Obst,
+    #[tile('^')]
+    Player,
+    #[tile('.')]
+    Free,
+}
+
+/// Which way the guard faces, drawn as the arrow that points that way.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Tile)]
+enum Direction {
+    #[tile('^')]
+    Up,
+    #[tile('>')]
+    Right,
+    #[tile('v')]
+    Down,
+    #[tile('<')]
+    Left,
+}
+
+impl Direction {
+    fn rotate_right_90(self) -> Direction {
+        match self {
+            Direction::Up => Direction::Right,
+            Direction::Right => Direction::Down,
+            Direction::Down => Direction::Left,
+            Direction::Left => Direction::Up,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Grid {
+    tiles: HashMap<Point, TileType>,
+}
+
+impl Grid {
+    fn from_rows(rows: Vec<Vec<TileType>>) -> Grid {
+        let mut tiles = HashMap::default();
+        for (i, line) in rows.into_iter().enumerate() {
+            for (j, tile) in line.into_iter().enumerate() {
+                tiles.insert(Point(j, i), tile);
+            }
+        }
+        Grid { tiles }
+    }
+    fn with_obst_at(&self, pt: Point) -> Grid {
+        count!("grid_clones");
+        let mut new = self.clone();
+        new.tiles.insert(pt, TileType::Obst);
+        new
+    }
+
+    fn find_player(&self) -> Point {
+        *self
+            .tiles
+            .iter()
+            .find(|(_, tile)| *tile == &TileType::Player)
+            .expect("No player found")
+            .0
+    }
+
+    /// Stepping off the top or left edge wraps round to a point that isn't on the map either.
+    fn move_from(p: &Point, d: Direction) -> Point {
+        match d {
+            Direction::Up => Point(p.0, p.1.wrapping_sub(1)),
+            Direction::Right => Point(p.0 + 1, p.1),
+            Direction::Down => Point(p.0, p.1 + 1),
+            Direction::Left => Point(p.0.wrapping_sub(1), p.1),
+        }
+    }
+}
+
+pub fn parse(content: &str) -> Result<Grid, String> {
+    let rows = tile::parse_grid(content)?;
+    if !rows.iter().flatten().any(|tile| *tile == TileType::Player) {
+        return Err("there's no guard on the map".to_string());
+    }
+    Ok(Grid::from_rows(rows))
+}
+
+/// The guard walking their route until they leave the map.
+pub struct Patrol {
+    grid: Grid,
+}
+
+/// Where the guard is, which way they're facing, and whether they've walked off the map.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Guard {
+    pos: Point,
+    dir: Direction,
+    gone: bool,
+}
+
+impl Patrol {
+    fn new(grid: Grid) -> (Patrol, Guard) {
+        let guard = Guard {
+            pos: grid.find_player(),
+            dir: Direction::Up,
+            gone: false,
+        };
+        (Patrol { grid }, guard)
+    }
+}
+
+impl Simulation for Patrol {
+    type State = Guard;
+
+    fn step(&self, guard: &mut Guard) {
+        let ahead = Grid::move_from(&guard.pos, guard.dir);
+        match self.grid.tiles.get(&ahead) {
+            None => guard.gone = true,
+            Some(TileType::Obst) => {
+                count!("turns");
+                guard.dir = guard.dir.rotate_right_90();
+            }
+            Some(_) => {
+                count!("steps");
+                guard.pos = ahead;
+            }
+        }
+    }
+
+    fn is_done(&self, guard: &Guard) -> bool {
+        guard.gone
+    }
+
+    fn render(&self, guard: &Guard) -> Option<CharGrid> {
+        let map = Day6::grid(&self.grid, &Params::default())?;
+        Some(CharGrid::from_fn(
+            map.width(),
+            map.height(),
+            |x, y| match map.get(x, y) {
+                _ if !guard.gone && guard.pos == Point(x, y) => guard.dir.to_char(),
+                Some('^') => '.',
+                Some(c) => c,
+                None => ' ',
+            },
+        ))
+    }
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    type Input = Grid;
+
+    fn parse(input: &str) -> Result<Grid, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<Grid>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |grid: &Grid, _: &Params| solve_part1(grid).into(),
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |grid: &Grid, _: &Params| solve_part2(grid).into(),
+            },
+        ]
+    }
+
+    fn pictures(grid: &Grid, params: &Params, _: &[(u8, Answer)]) -> Vec<Picture> {
+        let Some(map) = Self::grid(grid, params) else {
+            return Vec::new();
+        };
+        let path = guard_path(grid);
+        vec![Picture {
+            title: format!("The guard's path ({} positions)", path.len()),
+            grid: CharGrid::from_fn(map.width(), map.height(), |x, y| match map.get(x, y) {
+                Some('.') if path.contains(&Point(x, y)) => 'X',
+                Some(c) => c,
+                None => ' ',
+            }),
+        }]
+    }
+
+    fn simulation(grid: &Grid, _: &Params) -> Option<Box<dyn Playback>> {
+        let (patrol, guard) = Patrol::new(grid.clone());
+        Some(Box::new(Driver::new(patrol, guard, SNAPSHOT_EVERY)))
+    }
+
+    fn grid(grid: &Grid, _: &Params) -> Option<CharGrid> {
+        let width = grid.tiles.keys().map(|p| p.0 + 1).max().unwrap_or(0);
+        let height = grid.tiles.keys().map(|p| p.1 + 1).max().unwrap_or(0);
+        Some(CharGrid::from_tiles(width, height, |x, y| {
+            grid.tiles.get(&Point(x, y)).copied()
+        }))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?).into())
+}
+
+pub fn solve_part1(grid: &Grid) -> usize {
+    guard_path(grid).len()
+}
+
+/// Everywhere the guard goes before walking off the map, including where they start.
+fn guard_path(grid: &Grid) -> HashSet<Point> {
+    let (patrol, mut guard) = Patrol::new(grid.clone());
+    let mut path = HashSet::from_iter([guard.pos]);
+    while !patrol.is_done(&guard) {
+        patrol.step(&mut guard);
+        trace!("{:?}", guard);
+        path.insert(guard.pos);
+    }
+    path
+}
+
+pub fn solve_part2(grid: &Grid) -> usize {
+    let progress = Progress::new("day 6 part 2", grid.tiles.len() as u64);
+    let token = cancel::current();
+    grid.tiles
+        .par_iter()
+        .filter(|(pt, tile_type)| {
+            progress.tick();
+            token.check();
+            if **tile_type != TileType::Free {
+                return false;
+            }
+            count!("obstacles_tried");
+
+            // Only the start is kept: nothing seeks back through these.
+            let (patrol, guard) = Patrol::new(grid.with_obst_at(**pt));
+            let mut driver = Driver::new(patrol, guard, 0);
+            // Being somewhere, facing the same way, a second time means they're going round in
+            // circles.
+            let mut seen = HashSet::<Guard>::default();
+            driver.run_until(|guard| !seen.insert(*guard)).is_some()
+        })
+        .count()
+}
@@ -0,0 +1,152 @@
+use crate::answer::Answer;
+use crate::cancel;
+use crate::parallel::prelude::*;
+use crate::params::Params;
+use crate::scale;
+use crate::solution::{Solution, Variant};
+use crate::trace;
+use itertools::Itertools;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PotentialOp {
+    Plus,
+    Mul,
+    Comb,
+}
+
+impl PotentialOp {
+    fn f(&self, x: &usize, y: &usize) -> usize {
+        match &self {
+            PotentialOp::Plus => x + y,
+            PotentialOp::Mul => x * y,
+            PotentialOp::Comb => (x.to_string() + y.to_string().as_str())
+                .parse::<usize>()
+                .unwrap_or_else(|_| panic!("failed Comb operator on {x}, {y}")),
+        }
+    }
+    fn variants() -> Vec<PotentialOp> {
+        vec![PotentialOp::Plus, PotentialOp::Mul]
+    }
+    fn variants_pt2() -> Vec<PotentialOp> {
+        vec![PotentialOp::Plus, PotentialOp::Mul, PotentialOp::Comb]
+    }
+    /// Every way of filling `n` gaps with `+` or `*`, one at a time, as there can be a lot.
+    fn cproduct(n: usize) -> impl Iterator<Item = Vec<PotentialOp>> {
+        vec![PotentialOp::variants(); n]
+            .into_iter()
+            .multi_cartesian_product()
+    }
+    fn cproduct_pt2(n: usize) -> impl Iterator<Item = Vec<PotentialOp>> {
+        vec![PotentialOp::variants_pt2(); n]
+            .into_iter()
+            .multi_cartesian_product()
+    }
+}
+
+pub fn parse(content: &str) -> Result<Vec<(usize, Vec<usize>)>, String> {
+    content
+        .trim()
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (l, r) = line
+                .split(":")
+                .collect_tuple()
+                .ok_or_else(|| format!("line {} isn't a test value, ':' and numbers", i + 1))?;
+            let l = l
+                .parse::<usize>()
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+            let r = r
+                .split_whitespace()
+                .map(|n| n.parse::<usize>())
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|e| format!("line {}: {}", i + 1, e))?;
+            if r.is_empty() {
+                return Err(format!("line {} has no numbers after the ':'", i + 1));
+            }
+            Ok((l, r))
+        })
+        .collect()
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    const DAY: u8 = 7;
+    type Input = Vec<(usize, Vec<usize>)>;
+
+    fn parse(input: &str) -> Result<Vec<(usize, Vec<usize>)>, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<Vec<(usize, Vec<usize>)>>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |equations: &Vec<(usize, Vec<usize>)>, _: &Params| {
+                    solve_part1(equations).into()
+                },
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |equations: &Vec<(usize, Vec<usize>)>, _: &Params| {
+                    solve_part2(equations).into()
+                },
+            },
+        ]
+    }
+
+    fn scale(input: &str, factor: usize) -> Option<String> {
+        Some(scale::repeat_lines(input, factor))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?).into())
+}
+
+pub fn solve_part1(eqs: &[(usize, Vec<usize>)]) -> usize {
+    let token = cancel::current();
+    let result = eqs
+        .par_iter()
+        .filter(|(l, r)| {
+            PotentialOp::cproduct(r.len() - 1).any(|ops_perm| {
+                token.check();
+                apply_ops(r, ops_perm) == *l
+            })
+        })
+        .map(|(l, _)| l)
+        .sum();
+    trace!(
+        "whole thing: {:?}",
+        eqs.iter().map(|(x, _)| x).sum::<usize>()
+    );
+    result
+}
+
+pub fn solve_part2(eqs: &[(usize, Vec<usize>)]) -> usize {
+    let token = cancel::current();
+    eqs.par_iter()
+        .filter(|(l, r)| {
+            PotentialOp::cproduct_pt2(r.len() - 1).any(|ops_perm| {
+                token.check();
+                apply_ops(r, ops_perm) == *l
+            })
+        })
+        .map(|(l, _)| l)
+        .sum()
+}
+fn apply_ops(v: &[usize], ops_list: Vec<PotentialOp>) -> usize {
+    assert_eq!(ops_list.len(), v.len() - 1, "incorrect number of ops for v");
+    let mut result = v[0];
+    for (n, op) in v[1..].iter().zip(ops_list) {
+        result = op.f(&result, n);
+    }
+    result
+}
@@ -0,0 +1,250 @@
+use crate::answer::Answer;
+use crate::collections::{HashMap, HashSet};
+use crate::grid::CharGrid;
+use crate::params::Params;
+use crate::solution::{Solution, Variant};
+use crate::trace;
+use itertools::Itertools;
+use std::collections::BTreeMap;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct Point(usize, usize);
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+struct Diff(isize, isize);
+
+impl Point {
+    fn diff(&self, other: &Point) -> Diff {
+        Diff(
+            other.0 as isize - self.0 as isize,
+            other.1 as isize - self.1 as isize,
+        )
+    }
+    fn add_diff(&self, diff: Diff, upper_limit: &Point, rep: isize) -> Option<Self> {
+        let i = self.0 as isize + ((2 + rep) * diff.0);
+        let j = self.1 as isize + ((2 + rep) * diff.1);
+
+        trace!("(i: {:?}, j: {:?}): ", i, j);
+
+        let added = Point::new(i, j)?;
+
+        if added.fully_lt(upper_limit) {
+            trace!("valid.");
+            Some(added)
+        } else {
+            trace!("outside");
+            None
+        }
+    }
+    fn new(
+        i: impl std::convert::TryInto<usize>,
+        j: impl std::convert::TryInto<usize>,
+    ) -> Option<Self> {
+        if let (Ok(i), Ok(j)) = (i.try_into(), j.try_into()) {
+            Some(Self(i, j))
+        } else {
+            None
+        }
+    }
+    fn fully_lt(&self, other: &Point) -> bool {
+        self.0 < other.0 && self.1 < other.1
+    }
+    fn get_anodes_for(
+        &self,
+        p2: &Point,
+        upper_limit: &Point,
+    ) -> impl Iterator<Item = Option<Point>> {
+        let diff = self.diff(p2);
+        let rev_diff = p2.diff(self);
+        trace!("self: {:?}, p2: {:?}", self, p2);
+        [
+            self.add_diff(diff, upper_limit, 0),
+            p2.add_diff(rev_diff, upper_limit, 0),
+        ]
+        .into_iter()
+    }
+    fn get_repeating_anodes_for(&self, p2: &Point, upper_limit: &Point) -> Vec<Point> {
+        let diff = self.diff(p2);
+        let rev_diff = p2.diff(self);
+        trace!("self: {:?}, p2: {:?}", self, p2);
+
+        let mut anodes = Vec::<Point>::new();
+
+        anodes.extend_from_slice(&[*self, *p2]);
+
+        let mut rep = 0;
+
+        while let Some(p) = self.add_diff(diff, upper_limit, rep) {
+            anodes.push(p);
+            rep += 1;
+        }
+        rep = 0;
+        while let Some(p) = p2.add_diff(rev_diff, upper_limit, rep) {
+            anodes.push(p);
+            rep += 1;
+        }
+        anodes
+    }
+}
+
+#[derive(Copy, PartialEq, Eq, PartialOrd, Ord, Debug, Clone, Hash)]
+enum Alphanum {
+    Char(char),
+}
+
+impl Alphanum {
+    fn new(c: char) -> Option<Alphanum> {
+        if c.is_ascii_alphanumeric() {
+            Some(Alphanum::Char(c))
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(PartialEq, Eq, PartialOrd, Ord, Debug, Copy, Clone, Hash)]
+enum TileType {
+    Freq(Alphanum),
+    Free,
+}
+
+impl TileType {
+    fn from_char(c: char) -> TileType {
+        if c == '.' {
+            TileType::Free
+        } else {
+            TileType::Freq(Alphanum::new(c).expect("Found invalid character"))
+        }
+    }
+}
+
+pub struct AntennaGrid {
+    /// Each frequency's antennas, in frequency order so they're always paired up in the same order.
+    tiles: BTreeMap<TileType, Vec<Point>>,
+    upper_limit: Point,
+}
+
+impl AntennaGrid {
+    fn get_unique_anodes(&self) -> HashSet<Point> {
+        let mut anodes = HashSet::<Point>::default();
+        for tiles_vec in self.tiles.values() {
+            for comb in tiles_vec.iter().combinations(2) {
+                trace!("comb: {:?}", comb);
+                if let [&p1, &p2] = comb.as_slice() {
+                    for pt in p1.get_anodes_for(&p2, &self.upper_limit).flatten() {
+                        anodes.insert(pt);
+                    }
+                }
+            }
+        }
+        anodes
+    }
+    fn get_unique_anodes_pt2(&self) -> HashSet<Point> {
+        let mut anodes = HashSet::<Point>::default();
+        for tiles_vec in self.tiles.values() {
+            for comb in tiles_vec.iter().combinations(2) {
+                trace!("comb: {:?}", comb);
+                if let [&p1, &p2] = comb.as_slice() {
+                    for anode in p1
+                        .get_repeating_anodes_for(&p2, &self.upper_limit)
+                        .into_iter()
+                    {
+                        anodes.insert(anode);
+                    }
+                }
+            }
+        }
+        anodes
+    }
+}
+
+pub fn parse(content: &str) -> Result<AntennaGrid, String> {
+    let content = content.trim();
+    trace!("{:?}", content.lines().collect_vec());
+    let content_vec = content.lines().collect_vec();
+    let upper_limit = Point(
+        content_vec.len(),
+        content_vec.first().ok_or("the map is empty")?.len(),
+    );
+
+    let mut tiles = BTreeMap::<TileType, Vec<Point>>::new();
+    for (i, line) in content.lines().enumerate() {
+        for (j, c) in line.chars().enumerate() {
+            let tt = TileType::from_char(c);
+            if tt == TileType::Free {
+                continue;
+            }
+            let current_pts = tiles.get_mut(&tt);
+            if let Some(tt_vec) = current_pts {
+                tt_vec.push(Point(i, j));
+            } else {
+                tiles.insert(tt, vec![Point(i, j)]);
+            }
+        }
+    }
+    trace!("upper lim: {:?}", upper_limit);
+    trace!("tiles: {:?}", tiles);
+    Ok(AntennaGrid { tiles, upper_limit })
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    const DAY: u8 = 8;
+    type Input = AntennaGrid;
+
+    fn parse(input: &str) -> Result<AntennaGrid, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<AntennaGrid>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |grid: &AntennaGrid, _: &Params| solve_part1(grid).into(),
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |grid: &AntennaGrid, _: &Params| solve_part2(grid).into(),
+            },
+        ]
+    }
+
+    fn grid(grid: &AntennaGrid, _: &Params) -> Option<CharGrid> {
+        let antennas = grid
+            .tiles
+            .iter()
+            .flat_map(|(tile, points)| points.iter().map(move |p| (*p, *tile)))
+            .collect::<HashMap<Point, TileType>>();
+        Some(CharGrid::from_fn(
+            grid.upper_limit.1,
+            grid.upper_limit.0,
+            |x, y| match antennas.get(&Point(y, x)) {
+                Some(TileType::Freq(Alphanum::Char(c))) => *c,
+                _ => '.',
+            },
+        ))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?).into())
+}
+
+pub fn solve_part1(grid: &AntennaGrid) -> usize {
+    let anodes = grid.get_unique_anodes();
+    trace!("{:?}", anodes);
+    anodes.len()
+}
+
+pub fn solve_part2(grid: &AntennaGrid) -> usize {
+    let anodes = grid.get_unique_anodes_pt2();
+    trace!("pt2 anodes: {:?}", anodes);
+    anodes.len()
+}
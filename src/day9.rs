@@ -0,0 +1,344 @@
+use crate::answer::Answer;
+use crate::count;
+use crate::params::Params;
+use crate::solution::{Solution, Variant};
+use crate::tile::Tile;
+use itertools::Itertools;
+use std::fmt;
+
+pub struct DiskMap {
+    map: Vec<usize>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ExpandedDiskMap {
+    map: Vec<FileBlock>,
+}
+
+/// A block of the disk, drawn like the puzzle's examples: a file's as the last digit of its ID.
+#[derive(Debug, Clone, Copy, Tile)]
+enum FileBlock {
+    #[tile('.')]
+    Free,
+    #[tile(digit)]
+    Taken(usize),
+}
+
+impl DiskMap {
+    pub fn from_string(s: String) -> Result<Self, String> {
+        Ok(Self {
+            map: s
+                .trim()
+                .chars()
+                .enumerate()
+                .map(|(i, c)| {
+                    c.to_digit(10)
+                        .map(|n| n as usize)
+                        .ok_or_else(|| format!("column {}: {:?} isn't a digit", i + 1, c))
+                })
+                .collect::<Result<Vec<usize>, _>>()?,
+        })
+    }
+}
+
+impl ExpandedDiskMap {
+    pub fn from_diskmap(m: &DiskMap) -> Self {
+        let mut v = Vec::<FileBlock>::new();
+        for (i, n) in m.map.iter().enumerate() {
+            if i % 2 == 1 {
+                v.extend(vec![FileBlock::Free; *n]);
+            } else {
+                v.extend(vec![FileBlock::Taken(i / 2); *n]);
+            }
+        }
+        Self { map: v }
+    }
+    fn find_first_free_space(&self) -> Option<usize> {
+        for (i, block) in self.map.iter().enumerate() {
+            count!("blocks_scanned");
+            if let FileBlock::Free = block {
+                return Some(i);
+            };
+        }
+        None
+    }
+    fn find_last_filled_space(&self) -> Option<usize> {
+        for (i, block) in self.map.iter().rev().enumerate() {
+            count!("blocks_scanned");
+            if let FileBlock::Taken(_) = block {
+                return Some(self.map.len() - 1 - i);
+            };
+        }
+        None
+    }
+    fn checksum(&self) -> usize {
+        self.map
+            .iter()
+            .enumerate()
+            .map(|(i, b)| match b {
+                FileBlock::Taken(n) => n * i,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+impl fmt::Display for ExpandedDiskMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map
+            .iter()
+            .try_for_each(|file_block| write!(f, "{}", file_block))
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CompactDiskMap {
+    map: Vec<WholeFile>,
+}
+
+impl CompactDiskMap {
+    pub fn from_diskmap(m: &DiskMap) -> Self {
+        let mut v = Vec::<WholeFile>::new();
+        for (i, n) in m.map.iter().enumerate() {
+            if i % 2 == 1 {
+                v.push(WholeFile::Free(*n));
+            } else {
+                v.push(WholeFile::Taken(i / 2, *n));
+            }
+        }
+        Self { map: v }
+    }
+    fn find_first_free_space_big_enough(&self, file: &WholeFile) -> Option<usize> {
+        let n = match file {
+            &WholeFile::Taken(_, n) => Some(n),
+            _ => None,
+        }?;
+
+        count!("free_space_searches");
+        for (i, block) in self.map.iter().enumerate() {
+            count!("blocks_scanned");
+            match block {
+                &WholeFile::Free(size) if size >= n => return Some(i),
+                _ => (),
+            };
+        }
+        None
+    }
+    fn to_expanded_disk_map(&self) -> ExpandedDiskMap {
+        ExpandedDiskMap {
+            map: self
+                .map
+                .iter()
+                .flat_map(|f| f.to_file_blocks())
+                .collect::<Vec<_>>(),
+        }
+    }
+    fn checksum(&self) -> usize {
+        self.to_expanded_disk_map().checksum()
+    }
+    //sillyness...
+    fn flatten_frees_in_place(&mut self) {
+        let mut i = 0;
+
+        while i < self.map.len() {
+            if let WholeFile::Free(mut size) = self.map[i] {
+                if size == 0 {
+                    self.map.remove(i);
+                    continue;
+                }
+                let j = i + 1;
+                while j < self.map.len() {
+                    if let WholeFile::Free(next_size) = self.map[j] {
+                        size += next_size;
+                        self.map.remove(j);
+                    } else {
+                        break;
+                    }
+                }
+                self.map[i] = WholeFile::Free(size);
+            }
+            i += 1;
+        }
+    }
+    fn find_index_of_file_id(&self, n: usize) -> Option<(usize, &WholeFile)> {
+        self.map
+            .iter()
+            .find_position(|f| f.get_file_id() == Some(n))
+    }
+}
+
+impl fmt::Display for CompactDiskMap {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.map.iter().try_for_each(|file| write!(f, "{}", file))
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+enum WholeFile {
+    Taken(usize, usize),
+    Free(usize),
+}
+
+impl WholeFile {
+    fn get_file_id(&self) -> Option<usize> {
+        match self {
+            &WholeFile::Taken(n, _) => Some(n),
+            _ => None,
+        }
+    }
+    fn to_file_blocks(self) -> Vec<FileBlock> {
+        match self {
+            WholeFile::Free(n) => vec![FileBlock::Free; n],
+            WholeFile::Taken(i, n) => vec![FileBlock::Taken(i); n],
+        }
+    }
+}
+
+impl fmt::Display for WholeFile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.to_file_blocks()
+            .into_iter()
+            .try_for_each(|block| write!(f, "{}", block))
+    }
+}
+
+pub fn parse(content: &str) -> Result<DiskMap, String> {
+    let map = DiskMap::from_string(content.to_string())?;
+    debug_assert_eq!(
+        ExpandedDiskMap::from_diskmap(&map).to_string(),
+        CompactDiskMap::from_diskmap(&map).to_string()
+    );
+    Ok(map)
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    const DAY: u8 = 9;
+    type Input = DiskMap;
+
+    fn parse(input: &str) -> Result<DiskMap, String> {
+        parse(input)
+    }
+
+    fn variants() -> Vec<Variant<DiskMap>> {
+        vec![
+            Variant {
+                part: 1,
+                name: "default",
+                run: |map: &DiskMap, _: &Params| solve_part1(map).into(),
+            },
+            Variant {
+                part: 2,
+                name: "default",
+                run: |map: &DiskMap, _: &Params| solve_part2(map).into(),
+            },
+        ]
+    }
+
+    /// The disk map over and over, with no free space between the copies. The files in later
+    /// copies get new IDs, as IDs come from position.
+    fn scale(input: &str, factor: usize) -> Option<String> {
+        let map = input.trim();
+        // A map of odd length ends on a file, so the next copy's first file needs an empty free
+        // space before it; one of even length ends on a free space already.
+        let gap = if map.len() % 2 == 1 { "0" } else { "" };
+        Some(vec![map; factor].join(gap))
+    }
+}
+
+pub fn part1(input: &str) -> Result<Answer, String> {
+    Ok(solve_part1(&parse(input)?).into())
+}
+
+pub fn part2(input: &str) -> Result<Answer, String> {
+    Ok(solve_part2(&parse(input)?).into())
+}
+
+pub fn solve_part1(map: &DiskMap) -> usize {
+    let mut map = ExpandedDiskMap::from_diskmap(map);
+    let mut first_free_index = map
+        .find_first_free_space()
+        .expect("found no free space in map");
+    let mut last_filled_index = map
+        .find_last_filled_space()
+        .expect("found no filled space in map");
+
+    while first_free_index < last_filled_index {
+        count!("swaps");
+        map.map.swap(first_free_index, last_filled_index);
+        first_free_index = map.find_first_free_space().unwrap();
+        last_filled_index = map.find_last_filled_space().unwrap();
+    }
+    map.checksum()
+}
+
+pub fn solve_part2(map: &DiskMap) -> usize {
+    let mut map = CompactDiskMap::from_diskmap(map);
+    for (id, file) in map
+        .clone()
+        .map
+        .iter()
+        .filter_map(|f| f.get_file_id().map(|id| (id, f)))
+        .sorted_by_key(|&(id, _)| id)
+        .skip(1)
+        .rev()
+    {
+        if let Some(free_space_index) = map.find_first_free_space_big_enough(file) {
+            if free_space_index
+                >= map
+                    .find_index_of_file_id(id)
+                    .expect("didn't find file to get index of")
+                    .0
+            {
+                continue;
+            }
+            let file_size = match file {
+                WholeFile::Taken(_, n) => n,
+                _ => panic!("wtf"),
+            };
+            let free_space = map.map.get(free_space_index).unwrap();
+            let free_space_size = match free_space {
+                WholeFile::Free(n) => n,
+                _ => panic!("wtf"),
+            };
+
+            map.map.splice(
+                free_space_index..free_space_index + 1,
+                [
+                    WholeFile::Free(*file_size),
+                    WholeFile::Free(*free_space_size - file_size),
+                ],
+            );
+            let (new_block_index, _) = map
+                .find_index_of_file_id(id)
+                .expect("didn't find file to get index of");
+            map.map.swap(new_block_index, free_space_index);
+            count!("files_moved");
+            map.flatten_frees_in_place();
+        }
+    }
+    map.checksum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn scaled_maps_keep_files_and_free_space_alternating() {
+        // Ends on a file.
+        assert_eq!(Day9::scale("12345", 3).unwrap(), "12345012345012345");
+        // Ends on a free space.
+        assert_eq!(Day9::scale("1234\n", 3).unwrap(), "123412341234");
+        let expanded = |map: &str| ExpandedDiskMap::from_diskmap(&parse(map).unwrap()).to_string();
+        assert_eq!(
+            expanded(&Day9::scale("12345", 2).unwrap()),
+            "0..111....222223..444....55555"
+        );
+        assert_eq!(
+            expanded(&Day9::scale("1234", 2).unwrap()),
+            "0..111....2..333...."
+        );
+    }
+}
@@ -0,0 +1,285 @@
+//! The C ABI that solvers are passed across: plugins loaded at runtime (see `plugin`) export a
+//! [`PluginVtable`], usually through [`export_plugin!`](crate::export_plugin), and the `capi`
+//! library exports every day's solver the other way, as `aoc2024_solve`.
+//!
+//! Every solve function has the same contract. The input is `input_len` bytes of UTF-8 at
+//! `input`. On entry `*out_len` is the size of the buffer at `out`; on return it's the length of
+//! the UTF-8 text written there, which is the answer for [`OK`] and a message for anything else.
+//! If the buffer's too small nothing is written, [`BUFFER_TOO_SMALL`] comes back and `*out_len`
+//! says how big it needs to be. The text is kept, so a plugin's [`CopyFn`] can hand it over in a
+//! bigger buffer without solving again. A panic never unwinds across the boundary: it's caught
+//! and reported as [`PANICKED`].
+
+use crate::answer::Answer;
+use crate::params::Params;
+use crate::runner;
+use crate::solution;
+use std::{cell::RefCell, ffi::c_char, ptr, slice, str};
+
+/// Bumped whenever [`PluginVtable`] changes, so old plugins are turned away rather than misread.
+pub const ABI_VERSION: u32 = 2;
+
+/// The symbol a plugin exports: `extern "C" fn() -> *const PluginVtable`.
+pub const PLUGIN_ENTRY_POINT: &str = "aoc2024_plugin";
+
+/// The answer was written to the output buffer.
+pub const OK: i32 = 0;
+/// The solver failed; the message was written to the output buffer.
+pub const ERROR: i32 = 1;
+/// The output buffer is too small; `*out_len` has the size needed.
+pub const BUFFER_TOO_SMALL: i32 = 2;
+/// The input isn't valid UTF-8.
+pub const INVALID_INPUT: i32 = 3;
+/// The solver panicked; what it said, and where, was written to the output buffer.
+pub const PANICKED: i32 = 4;
+/// There's no solver for that day or part.
+pub const UNKNOWN_PART: i32 = 5;
+/// `out_len` was null, or `input` or `out` was null with a non-zero length.
+pub const NULL_POINTER: i32 = 6;
+
+/// What a status code means, for when there's no message to go with it.
+pub fn describe(status: i32) -> &'static str {
+    match status {
+        OK => "ok",
+        ERROR => "the solver failed",
+        BUFFER_TOO_SMALL => "the output buffer is too small",
+        INVALID_INPUT => "the input isn't UTF-8",
+        PANICKED => "the solver panicked",
+        UNKNOWN_PART => "there's no solver for that day or part",
+        NULL_POINTER => "a pointer that's needed was null",
+        _ => "unknown status",
+    }
+}
+
+/// Solves one part of the plugin's day. See the module docs for the contract.
+pub type SolveFn = unsafe extern "C" fn(
+    part: u8,
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+) -> i32;
+
+/// Copies what the last solve on this thread couldn't fit into the buffer at `out`, which is
+/// `*out_len` bytes. Returns the status the solve would have returned with a big enough buffer,
+/// [`BUFFER_TOO_SMALL`] again if this one isn't either, or [`ERROR`] if there's nothing waiting.
+pub type CopyFn = unsafe extern "C" fn(out: *mut u8, out_len: *mut usize) -> i32;
+
+/// What a plugin library hands over: which day it solves, what to call it, and how to run it.
+#[repr(C)]
+#[derive(Debug)]
+pub struct PluginVtable {
+    /// Always [`ABI_VERSION`] as the plugin was built against.
+    pub abi_version: u32,
+    pub day: u8,
+    /// Which parts it solves: bit 0 for part 1, bit 1 for part 2.
+    pub parts: u8,
+    /// Its name as an `--impl`, NUL-terminated UTF-8.
+    pub name: *const c_char,
+    pub solve: SolveFn,
+    pub copy: CopyFn,
+}
+
+// The name points at a static string, so the vtable can be shared like any other static.
+unsafe impl Sync for PluginVtable {}
+
+thread_local! {
+    /// The status and text of the last solve on this thread that didn't fit, until it's copied
+    /// or the next solve.
+    static PENDING: RefCell<Option<(i32, String)>> = const { RefCell::new(None) };
+}
+
+/// Runs `solve` on the input and writes what it came up with to the output buffer, turning
+/// everything that can go wrong (bad UTF-8, an error, a panic, too small a buffer) into a status
+/// code. `solve` returns `None` if it has no such part.
+///
+/// # Safety
+///
+/// The pointers must be valid as the module docs describe.
+pub unsafe fn solve_into(
+    input: *const u8,
+    input_len: usize,
+    out: *mut u8,
+    out_len: *mut usize,
+    solve: impl FnOnce(&str) -> Option<Result<Answer, String>>,
+) -> i32 {
+    PENDING.set(None);
+    if out_len.is_null() || (input.is_null() && input_len > 0) {
+        return NULL_POINTER;
+    }
+    let bytes = if input_len == 0 {
+        &[]
+    } else {
+        slice::from_raw_parts(input, input_len)
+    };
+    let (status, text) = match str::from_utf8(bytes) {
+        Err(e) => (INVALID_INPUT, format!("the input isn't UTF-8: {}", e)),
+        Ok(input) => match runner::isolate(|| solve(input)) {
+            Ok(Some(Ok(answer))) => (OK, answer.to_string()),
+            Ok(Some(Err(e))) => (ERROR, e),
+            Ok(None) => (UNKNOWN_PART, "there's no solver for that part".to_string()),
+            Err(failure) => (PANICKED, failure.to_string()),
+        },
+    };
+    match write_out(&text, out, out_len) {
+        Ok(()) => status,
+        Err(BUFFER_TOO_SMALL) => {
+            PENDING.set(Some((status, text)));
+            BUFFER_TOO_SMALL
+        }
+        Err(status) => status,
+    }
+}
+
+/// Copies what the last [`solve_into`] on this thread couldn't fit, as a [`CopyFn`] does.
+///
+/// # Safety
+///
+/// `out` must point at `*out_len` writable bytes, and `out_len` at a `size_t`.
+pub unsafe fn copy_pending(out: *mut u8, out_len: *mut usize) -> i32 {
+    if out_len.is_null() {
+        return NULL_POINTER;
+    }
+    let Some((status, text)) = PENDING.take() else {
+        let text = "there's no answer waiting to be copied";
+        return match write_out(text, out, out_len) {
+            Ok(()) => ERROR,
+            Err(status) => status,
+        };
+    };
+    match write_out(&text, out, out_len) {
+        Ok(()) => status,
+        Err(BUFFER_TOO_SMALL) => {
+            PENDING.set(Some((status, text)));
+            BUFFER_TOO_SMALL
+        }
+        Err(status) => status,
+    }
+}
+
+/// Copies `text` into the caller's buffer. If it can't, returns the status saying why (and
+/// `*out_len` says how big the buffer needs to be).
+unsafe fn write_out(text: &str, out: *mut u8, out_len: *mut usize) -> Result<(), i32> {
+    let capacity = *out_len;
+    *out_len = text.len();
+    if text.len() > capacity {
+        return Err(BUFFER_TOO_SMALL);
+    }
+    if !text.is_empty() {
+        if out.is_null() {
+            return Err(NULL_POINTER);
+        }
+        ptr::copy_nonoverlapping(text.as_ptr(), out, text.len());
+    }
+    Ok(())
+}
+
+/// Solves `part` of `day` on `input` with its default implementation and parameters, the way
+/// `aoc2024_solve` does. `None` if that part of that day isn't solved here.
+pub fn solve(day: u8, part: u8, input: &str) -> Option<Result<Answer, String>> {
+    let solution = solution::get(day)?;
+    if !solution.variants().iter().any(|&(p, _)| p == part) {
+        return None;
+    }
+    match solution.parse(input) {
+        Ok(parsed) => parsed.run(part, None, &Params::new()),
+        Err(e) => Some(Err(e)),
+    }
+}
+
+/// Exports a plugin from a `cdylib`: the day it solves, its name as an `--impl`, and a
+/// `fn(&str) -> Result<Answer, String>` for each part it solves.
+///
+/// ```ignore
+/// aoc2024::export_plugin! {
+///     day: 1,
+///     name: "counting",
+///     parts: { 1 => part1, 2 => part2 },
+/// }
+/// ```
+#[macro_export]
+macro_rules! export_plugin {
+    (day: $day:expr, name: $name:literal, parts: { $($part:literal => $solve:expr),+ $(,)? } $(,)?) => {
+        #[no_mangle]
+        pub extern "C" fn aoc2024_plugin() -> *const $crate::ffi::PluginVtable {
+            unsafe extern "C" fn solve(
+                part: u8,
+                input: *const u8,
+                input_len: usize,
+                out: *mut u8,
+                out_len: *mut usize,
+            ) -> i32 {
+                unsafe {
+                    $crate::ffi::solve_into(input, input_len, out, out_len, |input| match part {
+                        $($part => Some($solve(input)),)+
+                        _ => None,
+                    })
+                }
+            }
+            unsafe extern "C" fn copy(out: *mut u8, out_len: *mut usize) -> i32 {
+                unsafe { $crate::ffi::copy_pending(out, out_len) }
+            }
+            static VTABLE: $crate::ffi::PluginVtable = $crate::ffi::PluginVtable {
+                abi_version: $crate::ffi::ABI_VERSION,
+                day: $day,
+                parts: 0 $(| 1 << ($part - 1))+,
+                name: concat!($name, "\0").as_ptr() as *const ::std::ffi::c_char,
+                solve,
+                copy,
+            };
+            &VTABLE
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::Cell;
+
+    #[test]
+    fn copies_what_didnt_fit_without_solving_again() {
+        let solves = Cell::new(0);
+        let answer = "a long answer".repeat(100);
+        let mut out = vec![0; 8];
+        let mut len = out.len();
+        let status = unsafe {
+            solve_into([].as_ptr(), 0, out.as_mut_ptr(), &mut len, |_| {
+                solves.set(solves.get() + 1);
+                Some(Ok(Answer::from(answer.as_str())))
+            })
+        };
+        assert_eq!((status, len), (BUFFER_TOO_SMALL, answer.len()));
+
+        // Still too small, and it's kept for another go.
+        let mut len = out.len();
+        assert_eq!(
+            unsafe { copy_pending(out.as_mut_ptr(), &mut len) },
+            BUFFER_TOO_SMALL
+        );
+        out.resize(len, 0);
+        assert_eq!(unsafe { copy_pending(out.as_mut_ptr(), &mut len) }, OK);
+        assert_eq!(String::from_utf8(out).unwrap(), answer);
+        assert_eq!(solves.get(), 1);
+
+        // It's handed over once.
+        let mut out = vec![0; 256];
+        let mut len = out.len();
+        assert_eq!(unsafe { copy_pending(out.as_mut_ptr(), &mut len) }, ERROR);
+    }
+
+    #[test]
+    fn keeps_the_status_of_what_didnt_fit() {
+        let mut out = vec![0; 4];
+        let mut len = out.len();
+        let status = unsafe {
+            solve_into([].as_ptr(), 0, out.as_mut_ptr(), &mut len, |_| {
+                Some(Err("the input is all wrong".to_string()))
+            })
+        };
+        assert_eq!(status, BUFFER_TOO_SMALL);
+        out.resize(len, 0);
+        assert_eq!(unsafe { copy_pending(out.as_mut_ptr(), &mut len) }, ERROR);
+        assert_eq!(String::from_utf8(out).unwrap(), "the input is all wrong");
+    }
+}
@@ -0,0 +1,77 @@
+use std::fmt;
+
+/// A rectangular grid of characters, used to show a day's parsed input.
+/// `x` is the column and `y` is the row, both starting at 0 in the top left.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CharGrid {
+    rows: Vec<Vec<char>>,
+}
+
+impl CharGrid {
+    pub fn new(rows: Vec<Vec<char>>) -> Self {
+        Self { rows }
+    }
+
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> char) -> Self {
+        Self {
+            rows: (0..height)
+                .map(|y| (0..width).map(|x| f(x, y)).collect())
+                .collect(),
+        }
+    }
+
+    pub fn width(&self) -> usize {
+        self.rows.iter().map(|row| row.len()).max().unwrap_or(0)
+    }
+
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        self.rows.get(y)?.get(x).copied()
+    }
+
+    /// The part of the grid between the two corners, inclusive, clipped to the grid's edges.
+    pub fn window(&self, x0: usize, y0: usize, x1: usize, y1: usize) -> CharGrid {
+        CharGrid {
+            rows: self
+                .rows
+                .iter()
+                .skip(y0)
+                .take((y1 + 1).saturating_sub(y0))
+                .map(|row| {
+                    row.iter()
+                        .skip(x0)
+                        .take((x1 + 1).saturating_sub(x0))
+                        .copied()
+                        .collect()
+                })
+                .collect(),
+        }
+    }
+}
+
+/// A grid worth showing off, like day 14's tree, with a caption.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Picture {
+    pub title: String,
+    pub grid: CharGrid,
+}
+
+impl From<&str> for CharGrid {
+    fn from(s: &str) -> Self {
+        Self {
+            rows: s.lines().map(|line| line.chars().collect()).collect(),
+        }
+    }
+}
+
+impl fmt::Display for CharGrid {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for row in self.rows.iter() {
+            writeln!(f, "{}", row.iter().collect::<String>())?;
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+use clap::Args;
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+/// Where the `dayN` directories of inputs live, unless `--input-dir`/`AOC_INPUT_DIR` says otherwise.
+pub const DEFAULT_DIR: &str = "input";
+
+/// Which input a command runs on: `--input` if given, otherwise the day's real input or one of its
+/// examples from the input directory.
+#[derive(Args, Debug, Clone)]
+pub struct InputArgs {
+    /// The input file (defaults to dayN/input.txt in the input directory)
+    #[arg(short, long, conflicts_with = "example")]
+    pub input: Option<PathBuf>,
+
+    /// Run on example N instead, i.e. dayN/testN.txt (example 1 is test.txt)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..))]
+    pub example: Option<u8>,
+}
+
+impl InputArgs {
+    pub fn resolve(&self, dir: &Path, day: u8) -> PathBuf {
+        match (&self.input, self.example) {
+            (Some(input), _) => input.clone(),
+            (None, Some(example)) => example_path(dir, day, example),
+            (None, None) => real_path(dir, day),
+        }
+    }
+}
+
+/// The day's own puzzle input, as downloaded by `aoc fetch`.
+pub fn real_path(dir: &Path, day: u8) -> PathBuf {
+    dir.join(format!("day{}", day)).join("input.txt")
+}
+
+/// The day's `n`th example: `test.txt`, then `test2.txt`, `test3.txt`...
+pub fn example_path(dir: &Path, day: u8, n: u8) -> PathBuf {
+    let name = match n {
+        1 => "test.txt".to_string(),
+        n => format!("test{}.txt", n),
+    };
+    dir.join(format!("day{}", day)).join(name)
+}
+
+/// Reads an input file, suggesting `aoc fetch` if it's a day's real input that isn't there yet.
+pub fn read(path: &Path, day: u8) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| {
+        if e.kind() == io::ErrorKind::NotFound && path.ends_with("input.txt") {
+            format!(
+                "couldn't read {}: {} (download it with `aoc fetch {}`)",
+                path.display(),
+                e,
+                day
+            )
+        } else {
+            format!("couldn't read {}: {}", path.display(), e)
+        }
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolves_the_input_to_run_on() {
+        let dir = Path::new("inputs");
+        let args = |input: Option<&str>, example| InputArgs {
+            input: input.map(PathBuf::from),
+            example,
+        };
+        assert_eq!(
+            args(None, None).resolve(dir, 9),
+            Path::new("inputs/day9/input.txt")
+        );
+        assert_eq!(
+            args(None, Some(1)).resolve(dir, 9),
+            Path::new("inputs/day9/test.txt")
+        );
+        assert_eq!(
+            args(None, Some(3)).resolve(dir, 12),
+            Path::new("inputs/day12/test3.txt")
+        );
+        assert_eq!(
+            args(Some("elsewhere.txt"), None).resolve(dir, 9),
+            Path::new("elsewhere.txt")
+        );
+    }
+
+    #[test]
+    fn suggests_fetching_a_missing_real_input() {
+        let dir = Path::new("no/such/dir");
+        let err = read(&real_path(dir, 4), 4).unwrap_err();
+        assert!(err.ends_with("(download it with `aoc fetch 4`)"), "{}", err);
+        let err = read(&example_path(dir, 4, 2), 4).unwrap_err();
+        assert!(
+            err.starts_with("couldn't read no/such/dir/day4/test2.txt"),
+            "{}",
+            err
+        );
+        assert!(!err.contains("aoc fetch"), "{}", err);
+    }
+}
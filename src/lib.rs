@@ -0,0 +1,61 @@
+//! Advent of Code 2024 solutions.
+//!
+//! Every `dayN` module has the same public face: `parse(&str)` turns the puzzle input into that
+//! day's input type, `part1(&str)` / `part2(&str)` solve straight from the input text to an
+//! [`answer::Answer`], and `solve_part1` / `solve_part2` solve from an already parsed input (plus
+//! any constants the puzzle fixes, like day 11's blink counts). `solution` ties the days together
+//! for the runner.
+
+// Lets `#[derive(Tile)]` name this crate as `::aoc2024` from inside it too.
+extern crate self as aoc2024;
+
+pub mod answer;
+pub mod batch;
+pub mod cache;
+pub mod cancel;
+pub mod collections;
+pub mod config;
+pub mod ffi;
+pub mod grid;
+pub mod input;
+pub mod manifest;
+pub mod memory;
+#[cfg(feature = "net")]
+pub mod net;
+pub mod parallel;
+pub mod params;
+#[cfg(feature = "plugins")]
+pub mod plugin;
+pub mod progress;
+pub mod repl;
+pub mod report;
+pub mod runner;
+pub mod scale;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod simulation;
+pub mod snapshot;
+pub mod solution;
+pub mod stats;
+pub mod tile;
+pub mod trace;
+
+pub mod day1;
+pub mod day10;
+pub mod day11;
+pub mod day12;
+pub mod day13;
+pub mod day14;
+pub mod day15;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+pub mod day7;
+pub mod day8;
+pub mod day9;
+
+#[cfg(test)]
+#[path = "../tests/common/mod.rs"]
+mod test_common;
@@ -0,0 +1,39 @@
+use crate::answer::Answer;
+use std::{collections::BTreeMap, fs, io, path::Path};
+
+/// What a manifest is called when it sits next to the inputs it describes.
+pub const FILE_NAME: &str = "answers.json";
+
+/// The known answers for a directory of inputs, keyed by file name and then part:
+///
+/// ```json
+/// {
+///     "test.txt": { "1": 11, "2": 31 },
+///     "alice.txt": { "1": "218433348" }
+/// }
+/// ```
+///
+/// Answers can be numbers or strings (big ones have to be strings), and parts can be left out.
+#[derive(Debug, Clone, Default)]
+pub struct Manifest {
+    files: BTreeMap<String, BTreeMap<u8, Answer>>,
+}
+
+impl Manifest {
+    /// Reads the manifest at `path`. A missing file is an empty manifest.
+    pub fn load(path: &Path) -> Result<Self, String> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(e) => return Err(format!("couldn't read {}: {}", path.display(), e)),
+        };
+        let files = serde_json::from_str(&content)
+            .map_err(|e| format!("couldn't read {}: {}", path.display(), e))?;
+        Ok(Self { files })
+    }
+
+    /// The answer `part` should give for the input called `file`, if it's known.
+    pub fn expected(&self, file: &str, part: u8) -> Option<&Answer> {
+        self.files.get(file)?.get(&part)
+    }
+}
@@ -0,0 +1,295 @@
+//! Counts allocations, when built with the `memstats` feature, so parsing and each part can
+//! report how much memory they went through. Without the feature nothing is counted and
+//! [`Measure::finish`] gives `None`.
+
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt,
+    sync::{
+        atomic::{AtomicU64, AtomicU8, AtomicUsize, Ordering},
+        Arc,
+    },
+};
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+/// How many threads that were given up on are still running.
+static STRAYS: AtomicUsize = AtomicUsize::new(0);
+/// How many threads have ever been given up on, to tell if one was while something was measured.
+static ABANDONED_EVER: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting as it goes.
+pub struct Counting;
+
+#[cfg(feature = "memstats")]
+#[global_allocator]
+static GLOBAL: Counting = Counting;
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            allocated(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new = System.realloc(ptr, layout, new_size);
+        if !new.is_null() {
+            LIVE.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            allocated(new_size);
+        }
+        new
+    }
+}
+
+fn allocated(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+/// Whether allocations are being counted at all.
+pub fn enabled() -> bool {
+    cfg!(feature = "memstats")
+}
+
+/// What a stretch of code allocated.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Usage {
+    pub allocations: u64,
+    /// Every byte asked for, even if it was freed again straight away.
+    pub bytes: u64,
+    /// The most that was allocated at once, over what was already allocated at the start.
+    pub peak: u64,
+}
+
+/// Measures the allocations between [`Measure::start`] and [`Measure::finish`]. The counts are
+/// for the whole process, so they include other threads (like rayon's) and only mean something if
+/// nothing else is running at the same time. In particular, while a part that timed out is still
+/// running in the background (see [`Stray`]), there are no counts at all.
+///
+/// Measurements can be nested: the outer one's peak takes in the inner one's.
+pub struct Measure {
+    allocations: u64,
+    bytes: u64,
+    live: u64,
+    /// The peak as it was before this started, given back when it's done.
+    outer_peak: u64,
+    /// Whether a stray thread was running when this started.
+    strays: bool,
+    abandoned_ever: usize,
+}
+
+impl Measure {
+    pub fn start() -> Self {
+        let live = LIVE.load(Ordering::Relaxed);
+        Self {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            live,
+            outer_peak: PEAK.swap(live, Ordering::Relaxed),
+            strays: STRAYS.load(Ordering::SeqCst) > 0,
+            abandoned_ever: ABANDONED_EVER.load(Ordering::SeqCst),
+        }
+    }
+
+    /// What was allocated since the start, or `None` if allocations aren't being counted or
+    /// a stray thread's could have been counted too.
+    pub fn finish(self) -> Option<Usage> {
+        (enabled() && self.reliable()).then(|| self.usage())
+    }
+
+    fn reliable(&self) -> bool {
+        !self.strays
+            && STRAYS.load(Ordering::SeqCst) == 0
+            && ABANDONED_EVER.load(Ordering::SeqCst) == self.abandoned_ever
+    }
+
+    fn usage(&self) -> Usage {
+        Usage {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - self.bytes,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(self.live),
+        }
+    }
+}
+
+impl Drop for Measure {
+    fn drop(&mut self) {
+        PEAK.fetch_max(self.outer_peak, Ordering::Relaxed);
+    }
+}
+
+const RUNNING: u8 = 0;
+const FINISHED: u8 = 1;
+const ABANDONED: u8 = 2;
+
+/// A worker thread that might be given up on (a part that timed out and never checked its
+/// cancellation token) and left running. Until it finishes it's still allocating, so nothing
+/// measured in the meantime has counts.
+#[derive(Debug, Clone, Default)]
+pub struct Stray(Arc<AtomicU8>);
+
+impl Stray {
+    /// Called by the worker when it's done, whether or not it was given up on.
+    pub fn finished(&self) {
+        let was = self.0.swap(FINISHED, Ordering::SeqCst);
+        if was == ABANDONED {
+            STRAYS.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+
+    /// Called by whoever gave up waiting for it. Does nothing if it's finished already.
+    pub fn abandon(&self) {
+        STRAYS.fetch_add(1, Ordering::SeqCst);
+        let left_running = self
+            .0
+            .compare_exchange(RUNNING, ABANDONED, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok();
+        if left_running {
+            ABANDONED_EVER.fetch_add(1, Ordering::SeqCst);
+        } else {
+            STRAYS.fetch_sub(1, Ordering::SeqCst);
+        }
+    }
+}
+
+impl fmt::Display for Usage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{} allocations, {} allocated, {} peak",
+            self.allocations,
+            human_bytes(self.bytes),
+            human_bytes(self.peak)
+        )
+    }
+}
+
+impl Serialize for Usage {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut usage = serializer.serialize_struct("Usage", 3)?;
+        usage.serialize_field("allocations", &self.allocations)?;
+        usage.serialize_field("bytes", &self.bytes)?;
+        usage.serialize_field("peak_bytes", &self.peak)?;
+        usage.end()
+    }
+}
+
+fn human_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{} B", bytes);
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while size >= 1024.0 && unit < UNITS.len() - 1 {
+        size /= 1024.0;
+        unit += 1;
+    }
+    format!("{:.1} {}", size, UNITS[unit])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+
+    /// The counters are shared, so these tests take turns.
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    /// Big enough that whatever the test harness allocates with `memstats` on doesn't show.
+    const GIB: u64 = 1 << 30;
+
+    fn alloc(gib: u64) {
+        allocated((gib * GIB) as usize);
+    }
+
+    fn free(gib: u64) {
+        LIVE.fetch_sub(gib * GIB, Ordering::Relaxed);
+    }
+
+    fn peak(measure: &Measure) -> u64 {
+        measure.usage().peak / GIB
+    }
+
+    #[test]
+    fn measures_one_after_another() {
+        let _lock = LOCK.lock().unwrap();
+        let first = Measure::start();
+        alloc(3);
+        free(3);
+        let usage = first.usage();
+        assert_eq!((usage.bytes / GIB, usage.peak / GIB), (3, 3));
+        assert!(usage.allocations >= 1);
+        drop(first);
+
+        // The first one's peak is behind it.
+        let second = Measure::start();
+        alloc(1);
+        assert_eq!(peak(&second), 1);
+        free(1);
+        assert_eq!(second.usage().bytes / GIB, 1);
+    }
+
+    #[test]
+    fn nested_measurements_keep_the_outer_peak() {
+        let _lock = LOCK.lock().unwrap();
+        let outer = Measure::start();
+        alloc(4);
+        free(4);
+        let inner = Measure::start();
+        alloc(1);
+        free(1);
+        assert_eq!(peak(&inner), 1);
+        drop(inner);
+        assert_eq!(peak(&outer), 4);
+
+        // And an inner peak that's higher than the outer one so far counts for both.
+        let inner = Measure::start();
+        alloc(6);
+        free(6);
+        drop(inner);
+        assert_eq!(peak(&outer), 6);
+        assert_eq!(outer.usage().bytes / GIB, 11);
+    }
+
+    #[test]
+    fn no_counts_while_a_stray_is_running() {
+        let _lock = LOCK.lock().unwrap();
+        let stray = Stray::default();
+        let before = Measure::start();
+        stray.abandon();
+        assert!(!before.reliable());
+        assert!(!Measure::start().reliable());
+        stray.finished();
+        assert!(Measure::start().reliable());
+        // One that finished before it was given up on never counted as a stray.
+        let finished = Stray::default();
+        finished.finished();
+        finished.abandon();
+        assert!(Measure::start().reliable());
+        // It was running for part of this one, even if it's stopped since.
+        assert!(!before.reliable());
+        assert_eq!(before.finish(), None);
+    }
+}
@@ -0,0 +1,470 @@
+use crate::answer::Answer;
+use regex::Regex;
+use std::{
+    env, fmt, fs, io,
+    path::{Path, PathBuf},
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+pub const YEAR: u32 = 2024;
+
+const USER_AGENT: &str = "github.com/jathoms/aoc2024 by jathoms";
+const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(5);
+
+const LAST_REQUEST_FILE: &str = "last_request";
+const SUBMIT_AFTER_FILE: &str = "submit_after";
+const SUBMISSIONS_FILE: &str = "submissions";
+
+#[derive(Debug)]
+pub enum NetError {
+    NoSession,
+    NotFound(String),
+    Http(u16, String),
+    Transport(String),
+    Io(io::Error),
+}
+
+impl fmt::Display for NetError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            NetError::NoSession => write!(
+                f,
+                "no session token found, set AOC_SESSION or put it in ~/.config/aoc/session"
+            ),
+            NetError::NotFound(url) => write!(f, "{} not found (is the puzzle unlocked yet?)", url),
+            NetError::Http(code, url) => write!(f, "{} returned HTTP {}", url, code),
+            NetError::Transport(e) => write!(f, "request failed: {}", e),
+            NetError::Io(e) => write!(f, "{}", e),
+        }
+    }
+}
+
+impl From<io::Error> for NetError {
+    fn from(e: io::Error) -> Self {
+        NetError::Io(e)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SubmitOutcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    Incorrect,
+    Wait(Duration),
+    AlreadySolved,
+    Unknown(String),
+}
+
+impl SubmitOutcome {
+    /// Works out what happened to a submission from the text of the page AoC sends back.
+    pub fn from_response(page: &str) -> Self {
+        let text = article_text(page);
+
+        if text.contains("That's the right answer") {
+            SubmitOutcome::Correct
+        } else if text.contains("That's not the right answer") {
+            if text.contains("too high") {
+                SubmitOutcome::TooHigh
+            } else if text.contains("too low") {
+                SubmitOutcome::TooLow
+            } else {
+                SubmitOutcome::Incorrect
+            }
+        } else if text.contains("You gave an answer too recently") {
+            SubmitOutcome::Wait(parse_wait(&text).unwrap_or(Duration::from_secs(60)))
+        } else if text.contains("You don't seem to be solving the right level") {
+            SubmitOutcome::AlreadySolved
+        } else {
+            SubmitOutcome::Unknown(text)
+        }
+    }
+
+    /// Wrong answers come with a lockout ("please wait one minute before trying again").
+    fn lockout(page: &str) -> Option<Duration> {
+        let re = Regex::new(r"(?i)please wait (\w+) minutes?").unwrap();
+        let n = re.captures(page)?.get(1)?.as_str().to_lowercase();
+        let minutes = match n.as_str() {
+            "one" => 1,
+            "two" => 2,
+            "three" => 3,
+            "four" => 4,
+            "five" => 5,
+            "ten" => 10,
+            _ => n.parse::<u64>().ok()?,
+        };
+        Some(Duration::from_secs(minutes * 60))
+    }
+
+    fn key(&self) -> Option<&'static str> {
+        match self {
+            SubmitOutcome::Correct => Some("correct"),
+            SubmitOutcome::TooHigh => Some("too-high"),
+            SubmitOutcome::TooLow => Some("too-low"),
+            SubmitOutcome::Incorrect => Some("incorrect"),
+            _ => None,
+        }
+    }
+
+    fn from_key(key: &str) -> Option<Self> {
+        match key {
+            "correct" => Some(SubmitOutcome::Correct),
+            "too-high" => Some(SubmitOutcome::TooHigh),
+            "too-low" => Some(SubmitOutcome::TooLow),
+            "incorrect" => Some(SubmitOutcome::Incorrect),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SubmitOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SubmitOutcome::Correct => write!(f, "correct"),
+            SubmitOutcome::TooHigh => write!(f, "wrong, too high"),
+            SubmitOutcome::TooLow => write!(f, "wrong, too low"),
+            SubmitOutcome::Incorrect => write!(f, "wrong"),
+            SubmitOutcome::Wait(d) => write!(f, "rate limited, wait {}s", d.as_secs()),
+            SubmitOutcome::AlreadySolved => write!(f, "already solved (or part not unlocked)"),
+            SubmitOutcome::Unknown(text) => write!(f, "unrecognised response: {}", text),
+        }
+    }
+}
+
+fn article_text(page: &str) -> String {
+    let article = Regex::new(r"(?s)<article[^>]*>(.*?)</article>").unwrap();
+    let tags = Regex::new(r"<[^>]*>").unwrap();
+    let body = article
+        .captures(page)
+        .and_then(|c| c.get(1))
+        .map_or(page, |m| m.as_str());
+    tags.replace_all(body, "")
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn parse_wait(text: &str) -> Option<Duration> {
+    let re = Regex::new(r"(?:(\d+)m )?(\d+)s left to wait").unwrap();
+    let captures = re.captures(text)?;
+    let minutes = captures
+        .get(1)
+        .map_or(0, |m| m.as_str().parse::<u64>().unwrap());
+    let seconds = captures.get(2)?.as_str().parse::<u64>().ok()?;
+    Some(Duration::from_secs(minutes * 60 + seconds))
+}
+
+/// Reads the session cookie from `AOC_SESSION`, falling back to `~/.config/aoc/session`.
+pub fn find_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session.trim().to_string());
+        }
+    }
+    let home = env::var_os("HOME")?;
+    let session = fs::read_to_string(Path::new(&home).join(".config/aoc/session")).ok()?;
+    Some(session.trim().to_string()).filter(|s| !s.is_empty())
+}
+
+pub struct Client {
+    base_url: String,
+    session: String,
+    state_dir: PathBuf,
+    min_interval: Duration,
+    agent: ureq::Agent,
+}
+
+impl Client {
+    /// `state_dir` holds the rate limit timestamps and the log of previous submissions.
+    pub fn new(base_url: &str, session: &str, state_dir: impl Into<PathBuf>) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            state_dir: state_dir.into(),
+            min_interval: MIN_REQUEST_INTERVAL,
+            agent: ureq::AgentBuilder::new()
+                .user_agent(USER_AGENT)
+                .timeout(Duration::from_secs(30))
+                .build(),
+        }
+    }
+
+    pub fn with_min_interval(mut self, min_interval: Duration) -> Self {
+        self.min_interval = min_interval;
+        self
+    }
+
+    /// Returns the input for `day`, only downloading it if `path` doesn't exist yet.
+    /// The bool is true when the input came from disk.
+    pub fn fetch_input_to(&self, day: u8, path: &Path) -> Result<(String, bool), NetError> {
+        if let Ok(content) = fs::read_to_string(path) {
+            if !content.is_empty() {
+                return Ok((content, true));
+            }
+        }
+        let content = self.fetch_input(day)?;
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, &content)?;
+        Ok((content, false))
+    }
+
+    pub fn fetch_input(&self, day: u8) -> Result<String, NetError> {
+        let url = format!("{}/{}/day/{}/input", self.base_url, YEAR, day);
+        self.wait_for_slot()?;
+        let response = self
+            .agent
+            .get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .call();
+        read_body(response, &url)
+    }
+
+    /// Submits `answer`, unless we can already tell what AoC would say from earlier
+    /// submissions or we're still inside a lockout.
+    pub fn submit(&self, day: u8, part: u8, answer: &Answer) -> Result<SubmitOutcome, NetError> {
+        if let Some(outcome) = self.known_outcome(day, part, answer) {
+            return Ok(outcome);
+        }
+        if let Some(after) = self.read_timestamp(SUBMIT_AFTER_FILE) {
+            if let Ok(remaining) = after.duration_since(SystemTime::now()) {
+                return Ok(SubmitOutcome::Wait(remaining));
+            }
+        }
+
+        let url = format!("{}/{}/day/{}/answer", self.base_url, YEAR, day);
+        self.wait_for_slot()?;
+        let response = self
+            .agent
+            .post(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .send_form(&[
+                ("level", &part.to_string()),
+                ("answer", &answer.to_string()),
+            ]);
+        let page = read_body(response, &url)?;
+
+        let outcome = SubmitOutcome::from_response(&page);
+        let lockout = match &outcome {
+            SubmitOutcome::Wait(d) => Some(*d),
+            _ => SubmitOutcome::lockout(&page),
+        };
+        if let Some(lockout) = lockout {
+            self.write_timestamp(SUBMIT_AFTER_FILE, SystemTime::now() + lockout)?;
+        }
+        if let Some(key) = outcome.key() {
+            self.log_submission(day, part, answer, key)?;
+        }
+        Ok(outcome)
+    }
+
+    fn known_outcome(&self, day: u8, part: u8, answer: &Answer) -> Option<SubmitOutcome> {
+        let log = fs::read_to_string(self.state_dir.join(SUBMISSIONS_FILE)).ok()?;
+
+        for line in log.lines() {
+            let fields = line.split('\t').collect::<Vec<_>>();
+            let [logged_day, logged_part, logged_answer, key] = fields[..] else {
+                continue;
+            };
+            if logged_day != day.to_string() || logged_part != part.to_string() {
+                continue;
+            }
+            let Some(outcome) = SubmitOutcome::from_key(key) else {
+                continue;
+            };
+            let logged_answer = Answer::from(logged_answer);
+            if logged_answer == *answer {
+                return Some(outcome);
+            }
+            // an answer past one we already know is too high/low is also too high/low
+            match (answer, logged_answer, &outcome) {
+                (Answer::Int(n), Answer::Int(bound), SubmitOutcome::TooHigh) if *n > bound => {
+                    return Some(SubmitOutcome::TooHigh)
+                }
+                (Answer::Int(n), Answer::Int(bound), SubmitOutcome::TooLow) if *n < bound => {
+                    return Some(SubmitOutcome::TooLow)
+                }
+                _ => (),
+            }
+        }
+        None
+    }
+
+    fn log_submission(&self, day: u8, part: u8, answer: &Answer, key: &str) -> io::Result<()> {
+        use io::Write;
+        fs::create_dir_all(&self.state_dir)?;
+        let mut log = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.state_dir.join(SUBMISSIONS_FILE))?;
+        writeln!(log, "{}\t{}\t{}\t{}", day, part, answer, key)
+    }
+
+    /// Sleeps until at least `min_interval` has passed since the last request,
+    /// including requests made by earlier runs.
+    fn wait_for_slot(&self) -> io::Result<()> {
+        if let Some(last) = self.read_timestamp(LAST_REQUEST_FILE) {
+            if let Ok(since) = SystemTime::now().duration_since(last) {
+                if since < self.min_interval {
+                    thread::sleep(self.min_interval - since);
+                }
+            }
+        }
+        self.write_timestamp(LAST_REQUEST_FILE, SystemTime::now())
+    }
+
+    fn read_timestamp(&self, name: &str) -> Option<SystemTime> {
+        let millis = fs::read_to_string(self.state_dir.join(name))
+            .ok()?
+            .trim()
+            .parse::<u64>()
+            .ok()?;
+        Some(UNIX_EPOCH + Duration::from_millis(millis))
+    }
+
+    fn write_timestamp(&self, name: &str, time: SystemTime) -> io::Result<()> {
+        let millis = time
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis();
+        fs::create_dir_all(&self.state_dir)?;
+        fs::write(self.state_dir.join(name), millis.to_string())
+    }
+}
+
+fn read_body(response: Result<ureq::Response, ureq::Error>, url: &str) -> Result<String, NetError> {
+    match response {
+        Ok(response) => response
+            .into_string()
+            .map_err(|e| NetError::Transport(e.to_string())),
+        Err(ureq::Error::Status(404, _)) => Err(NetError::NotFound(url.to_string())),
+        Err(ureq::Error::Status(code, _)) => Err(NetError::Http(code, url.to_string())),
+        Err(e) => Err(NetError::Transport(e.to_string())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_common::temp_dir;
+    use std::time;
+
+    fn page(text: &str) -> String {
+        format!(
+            "<html><main><article><p>{}</p></article></main></html>",
+            text
+        )
+    }
+
+    #[test]
+    fn reads_submission_outcomes() {
+        let cases = [
+            (
+                "That's the right answer! You are <em>one gold star</em> closer.",
+                SubmitOutcome::Correct,
+            ),
+            (
+                "That's not the right answer; your answer is too high.",
+                SubmitOutcome::TooHigh,
+            ),
+            (
+                "That's not the right answer; your answer is too low.",
+                SubmitOutcome::TooLow,
+            ),
+            ("That's not the right answer.", SubmitOutcome::Incorrect),
+            (
+                "You gave an answer too recently. You have 1m 30s left to wait.",
+                SubmitOutcome::Wait(Duration::from_secs(90)),
+            ),
+            (
+                "You gave an answer too recently.",
+                SubmitOutcome::Wait(Duration::from_secs(60)),
+            ),
+            (
+                "You don't seem to be solving the right level. Did you already complete it?",
+                SubmitOutcome::AlreadySolved,
+            ),
+            (
+                "Something   <b>else</b>",
+                SubmitOutcome::Unknown("Something else".to_string()),
+            ),
+        ];
+        for (text, outcome) in cases {
+            assert_eq!(
+                SubmitOutcome::from_response(&page(text)),
+                outcome,
+                "{}",
+                text
+            );
+        }
+    }
+
+    #[test]
+    fn parses_the_time_left_to_wait() {
+        assert_eq!(
+            parse_wait("You have 45s left to wait."),
+            Some(Duration::from_secs(45))
+        );
+        assert_eq!(
+            parse_wait("You have 2m 5s left to wait."),
+            Some(Duration::from_secs(125))
+        );
+        assert_eq!(parse_wait("You have a while left to wait."), None);
+        assert_eq!(
+            SubmitOutcome::lockout("Please wait one minute before trying again."),
+            Some(Duration::from_secs(60))
+        );
+        assert_eq!(
+            SubmitOutcome::lockout("please wait 10 minutes before trying again."),
+            Some(Duration::from_secs(600))
+        );
+        assert_eq!(SubmitOutcome::lockout("That's the right answer!"), None);
+    }
+
+    #[test]
+    fn knows_outcomes_from_earlier_submissions() {
+        let client = Client::new(DEFAULT_BASE_URL, "session", temp_dir("net-known"));
+        assert_eq!(client.known_outcome(1, 1, &Answer::Int(5)), None);
+        client
+            .log_submission(1, 1, &Answer::Int(100), "too-high")
+            .unwrap();
+        client
+            .log_submission(1, 1, &Answer::Int(10), "too-low")
+            .unwrap();
+        client
+            .log_submission(1, 2, &Answer::Int(42), "correct")
+            .unwrap();
+
+        let known = |day, part, n| client.known_outcome(day, part, &Answer::Int(n));
+        assert_eq!(known(1, 1, 100), Some(SubmitOutcome::TooHigh));
+        assert_eq!(known(1, 1, 150), Some(SubmitOutcome::TooHigh));
+        assert_eq!(known(1, 1, 5), Some(SubmitOutcome::TooLow));
+        assert_eq!(known(1, 1, 50), None);
+        assert_eq!(known(1, 2, 42), Some(SubmitOutcome::Correct));
+        assert_eq!(known(1, 2, 43), None);
+        assert_eq!(known(2, 1, 100), None);
+        fs::remove_dir_all(&client.state_dir).unwrap();
+    }
+
+    #[test]
+    fn spaces_requests_out() {
+        let dir = temp_dir("net-slot");
+        let first = Client::new(DEFAULT_BASE_URL, "session", &dir)
+            .with_min_interval(Duration::from_secs(60));
+        let start = time::Instant::now();
+        // Nothing's been sent before, so there's nothing to wait for.
+        first.wait_for_slot().unwrap();
+        assert!(start.elapsed() < Duration::from_secs(30));
+
+        // A later run waits out the interval since the timestamp the first left behind (which
+        // is only kept to the millisecond).
+        let interval = Duration::from_millis(200);
+        let second = Client::new(DEFAULT_BASE_URL, "session", &dir).with_min_interval(interval);
+        second.wait_for_slot().unwrap();
+        assert!(start.elapsed() >= interval - Duration::from_millis(1));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
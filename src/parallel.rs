@@ -0,0 +1,63 @@
+/// Sizes rayon's global pool, which every day's parallel loops run on. `1` runs everything on a
+/// single worker, e.g. for deterministic profiling. Has to happen before anything uses the pool.
+#[cfg(feature = "parallel")]
+pub fn set_threads(threads: usize) -> Result<(), String> {
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(threads)
+        .build_global()
+        .map_err(|e| format!("couldn't set up {} threads: {}", threads, e))
+}
+
+/// Without the `parallel` feature everything already runs on the calling thread.
+#[cfg(not(feature = "parallel"))]
+pub fn set_threads(_threads: usize) -> Result<(), String> {
+    Ok(())
+}
+
+/// How many threads the global pool has: `--threads`, or one per core.
+#[cfg(feature = "parallel")]
+pub fn threads() -> usize {
+    rayon::current_num_threads()
+}
+
+/// Without the `parallel` feature, just the calling thread.
+#[cfg(not(feature = "parallel"))]
+pub fn threads() -> usize {
+    1
+}
+
+/// What the days import for their parallel loops: rayon's prelude, or with the `parallel` feature
+/// off, `par_iter`/`into_par_iter` that hand back plain serial iterators.
+pub mod prelude {
+    #[cfg(feature = "parallel")]
+    pub use rayon::prelude::*;
+
+    #[cfg(not(feature = "parallel"))]
+    pub trait IntoParallelIterator: IntoIterator + Sized {
+        fn into_par_iter(self) -> Self::IntoIter {
+            self.into_iter()
+        }
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    impl<T: IntoIterator> IntoParallelIterator for T {}
+
+    #[cfg(not(feature = "parallel"))]
+    pub trait IntoParallelRefIterator<'a> {
+        type Iter: Iterator;
+
+        fn par_iter(&'a self) -> Self::Iter;
+    }
+
+    #[cfg(not(feature = "parallel"))]
+    impl<'a, T: 'a + ?Sized> IntoParallelRefIterator<'a> for T
+    where
+        &'a T: IntoIterator,
+    {
+        type Iter = <&'a T as IntoIterator>::IntoIter;
+
+        fn par_iter(&'a self) -> Self::Iter {
+            self.into_iter()
+        }
+    }
+}
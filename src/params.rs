@@ -0,0 +1,171 @@
+use serde::{ser::SerializeStruct, Serialize, Serializer};
+use std::{any, collections::BTreeMap, fmt, str::FromStr};
+
+/// A constant a solution lets you override, with its type, default and what it's for.
+#[derive(Debug, Clone)]
+pub struct ParamSpec {
+    pub name: &'static str,
+    /// The type its value has to parse as, e.g. `usize`.
+    pub kind: &'static str,
+    pub default: String,
+    pub doc: &'static str,
+    /// The smallest and biggest values allowed, if it's limited.
+    pub range: Option<(String, String)>,
+    parses: fn(&str) -> bool,
+    /// Whether the value is within the range, compared as its type.
+    within: fn(&str, &str, &str) -> bool,
+}
+
+impl ParamSpec {
+    pub fn new<T: FromStr + PartialOrd + fmt::Display>(
+        name: &'static str,
+        default: T,
+        doc: &'static str,
+    ) -> Self {
+        Self {
+            name,
+            kind: any::type_name::<T>(),
+            default: default.to_string(),
+            doc,
+            range: None,
+            parses: |value| value.parse::<T>().is_ok(),
+            within: |value, min, max| match (value.parse::<T>(), min.parse::<T>(), max.parse::<T>())
+            {
+                (Ok(value), Ok(min), Ok(max)) => min <= value && value <= max,
+                _ => false,
+            },
+        }
+    }
+
+    /// Only allows values from `min` to `max`, for parameters that would take the process down
+    /// (overflowing the stack, running out of memory) if they were too big.
+    pub fn between<T: fmt::Display>(mut self, min: T, max: T) -> Self {
+        self.range = Some((min.to_string(), max.to_string()));
+        self
+    }
+
+    /// Why `value` isn't allowed for this parameter, if it isn't.
+    pub fn validate(&self, value: &str) -> Result<(), String> {
+        if !(self.parses)(value) {
+            return Err(format!(
+                "invalid value for parameter {}: {:?} isn't a valid {}",
+                self.name, value, self.kind
+            ));
+        }
+        match &self.range {
+            Some((min, max)) if !(self.within)(value, min, max) => Err(format!(
+                "invalid value for parameter {}: {} isn't between {} and {}",
+                self.name, value, min, max
+            )),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether `value` would be accepted for this parameter.
+    pub fn accepts(&self, value: &str) -> bool {
+        self.validate(value).is_ok()
+    }
+}
+
+/// What `aoc params --format json` and the server's `GET /days` say about a parameter.
+impl Serialize for ParamSpec {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut spec = serializer.serialize_struct("ParamSpec", 6)?;
+        spec.serialize_field("name", self.name)?;
+        spec.serialize_field("type", self.kind)?;
+        spec.serialize_field("default", &self.default)?;
+        spec.serialize_field("min", &self.range.as_ref().map(|(min, _)| min))?;
+        spec.serialize_field("max", &self.range.as_ref().map(|(_, max)| max))?;
+        spec.serialize_field("doc", self.doc)?;
+        spec.end()
+    }
+}
+
+/// Reads a `key=value` pair, as given to `--param`.
+pub fn parse_assignment(s: &str) -> Result<(String, String), String> {
+    match s.split_once('=') {
+        Some((key, value)) if !key.trim().is_empty() => {
+            Ok((key.trim().to_string(), value.trim().to_string()))
+        }
+        _ => Err(format!("expected key=value, got {:?}", s)),
+    }
+}
+
+/// Overrides for the constants a solution would otherwise hardcode (blink counts, grid sizes...).
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct Params {
+    values: BTreeMap<String, String>,
+}
+
+impl Params {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn set(&mut self, key: &str, value: &str) {
+        self.values.insert(key.to_string(), value.to_string());
+    }
+
+    pub fn unset(&mut self, key: &str) -> bool {
+        self.values.remove(key).is_some()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Makes sure every overridden parameter is one of `specs`, with a value of the right type
+    /// that's within its range.
+    pub fn check(&self, specs: &[ParamSpec]) -> Result<(), String> {
+        for (key, value) in self.iter() {
+            let Some(spec) = specs.iter().find(|spec| spec.name == key) else {
+                let known = specs.iter().map(|spec| spec.name).collect::<Vec<_>>();
+                return Err(if known.is_empty() {
+                    format!("unknown parameter {} (this day has none)", key)
+                } else {
+                    format!(
+                        "unknown parameter {} (expected one of {})",
+                        key,
+                        known.join(", ")
+                    )
+                });
+            };
+            spec.validate(value)?;
+        }
+        Ok(())
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.values.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// The overridden value of `key`, or `default` if it hasn't been set.
+    pub fn get<T: FromStr>(&self, key: &str, default: T) -> T {
+        match self.values.get(key) {
+            Some(value) => value
+                .parse::<T>()
+                .unwrap_or_else(|_| panic!("invalid value for parameter {}: {:?}", key, value)),
+            None => default,
+        }
+    }
+}
+
+impl FromIterator<(String, String)> for Params {
+    fn from_iter<I: IntoIterator<Item = (String, String)>>(iter: I) -> Self {
+        Self {
+            values: iter.into_iter().collect(),
+        }
+    }
+}
+
+impl fmt::Display for Params {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (key, value)) in self.iter().enumerate() {
+            if i > 0 {
+                write!(f, " ")?;
+            }
+            write!(f, "{}={}", key, value)?;
+        }
+        Ok(())
+    }
+}
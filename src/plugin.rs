@@ -0,0 +1,306 @@
+use crate::answer::Answer;
+use crate::ffi::{self, PluginVtable};
+use crate::grid::{CharGrid, Picture};
+use crate::params::{ParamSpec, Params};
+use crate::simulation::Playback;
+use crate::solution::{self, DynSolution, Parsed};
+use libloading::Library;
+use std::{
+    collections::BTreeMap,
+    env,
+    ffi::CStr,
+    fmt, fs,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+/// Where plugins are loaded from unless `plugin_dir` says otherwise.
+pub const DEFAULT_DIR: &str = "plugins";
+
+/// How much room answers get at first. Anything longer is copied again into a bigger buffer.
+const ANSWER_BUFFER: usize = 256;
+
+/// An alternative solver for one day, loaded from a dynamic library and run as one more `--impl`
+/// of that day.
+pub struct Plugin {
+    pub day: u8,
+    pub name: &'static str,
+    pub path: PathBuf,
+    parts: u8,
+    /// Points into `library`, which is never unloaded while the plugin's around.
+    vtable: &'static PluginVtable,
+    _library: Library,
+}
+
+impl fmt::Debug for Plugin {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Plugin")
+            .field("day", &self.day)
+            .field("name", &self.name)
+            .field("path", &self.path)
+            .field("parts", &self.parts())
+            .finish()
+    }
+}
+
+impl Plugin {
+    /// Loads the plugin in the library at `path`, checking it was built for this ABI.
+    ///
+    /// # Safety
+    ///
+    /// Loading a library runs its initialisers, and its vtable is trusted to point at functions
+    /// that keep to the ABI. Only load plugins you trust.
+    pub unsafe fn load(path: &Path) -> Result<Plugin, String> {
+        let library = Library::new(path).map_err(|e| e.to_string())?;
+        let entry = library
+            .get::<unsafe extern "C" fn() -> *const PluginVtable>(
+                ffi::PLUGIN_ENTRY_POINT.as_bytes(),
+            )
+            .map_err(|e| format!("it doesn't export {}: {}", ffi::PLUGIN_ENTRY_POINT, e))?;
+        let Some(vtable) = entry().as_ref() else {
+            return Err(format!("{} returned nothing", ffi::PLUGIN_ENTRY_POINT));
+        };
+        if vtable.abi_version != ffi::ABI_VERSION {
+            return Err(format!(
+                "it was built for version {} of the plugin ABI, not {}",
+                vtable.abi_version,
+                ffi::ABI_VERSION
+            ));
+        }
+        if vtable.name.is_null() {
+            return Err("it has no name".to_string());
+        }
+        let name = CStr::from_ptr(vtable.name)
+            .to_str()
+            .map_err(|_| "its name isn't UTF-8".to_string())?;
+        if name.is_empty() || vtable.parts & 0b11 == 0 {
+            return Err("it has no name or doesn't solve either part".to_string());
+        }
+        Ok(Plugin {
+            day: vtable.day,
+            name: Box::leak(name.to_string().into_boxed_str()),
+            path: path.to_path_buf(),
+            parts: vtable.parts,
+            // The library is kept alongside it, so it outlives every use.
+            vtable: &*(vtable as *const PluginVtable),
+            _library: library,
+        })
+    }
+
+    /// Which parts it solves.
+    pub fn parts(&self) -> Vec<u8> {
+        [1, 2]
+            .into_iter()
+            .filter(|part| self.parts & (1 << (part - 1)) != 0)
+            .collect()
+    }
+
+    /// Solves `part` of `input`. Everything the plugin reports as going wrong, panics included,
+    /// comes back as an error.
+    pub fn solve(&self, part: u8, input: &str) -> Result<Answer, String> {
+        let mut buffer = vec![0; ANSWER_BUFFER];
+        let mut len = buffer.len();
+        // SAFETY: the buffer and input are valid for the lengths given, as the ABI asks.
+        let mut status = unsafe {
+            (self.vtable.solve)(
+                part,
+                input.as_ptr(),
+                input.len(),
+                buffer.as_mut_ptr(),
+                &mut len,
+            )
+        };
+        // The plugin keeps what didn't fit, so only the copy is tried again.
+        while status == ffi::BUFFER_TOO_SMALL && len > buffer.len() {
+            buffer.resize(len, 0);
+            // SAFETY: as above, on the thread that solved.
+            status = unsafe { (self.vtable.copy)(buffer.as_mut_ptr(), &mut len) };
+        }
+        let text = String::from_utf8_lossy(&buffer[..len.min(buffer.len())]);
+        match status {
+            ffi::OK => Ok(Answer::from(&*text)),
+            _ if !text.is_empty() => Err(text.into_owned()),
+            status => Err(ffi::describe(status).to_string()),
+        }
+    }
+}
+
+/// Every plugin in `dir`, which needn't exist. Libraries that don't load are skipped with a
+/// warning, so one broken plugin doesn't stop the rest.
+///
+/// # Safety
+///
+/// As for [`Plugin::load`], for every library in `dir`.
+pub unsafe fn load_dir(dir: &Path) -> Result<Vec<Plugin>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("couldn't read {}: {}", dir.display(), e)),
+    };
+    let mut paths = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            path.extension()
+                .is_some_and(|ext| ext == env::consts::DLL_EXTENSION)
+        })
+        .collect::<Vec<_>>();
+    paths.sort();
+    let mut plugins = Vec::new();
+    for path in paths {
+        match Plugin::load(&path) {
+            Ok(plugin) => plugins.push(plugin),
+            Err(e) => eprintln!("warning: skipping plugin {}: {}", path.display(), e),
+        }
+    }
+    Ok(plugins)
+}
+
+static INSTALLED: OnceLock<Installed> = OnceLock::new();
+
+struct Installed {
+    plugins: Vec<Arc<Plugin>>,
+    solutions: BTreeMap<u8, &'static dyn DynSolution>,
+}
+
+/// Adds `plugins` to their days, so `solution::get` hands out each day with its plugins as extra
+/// variants from then on. A plugin for a day that isn't solved here, or named the same as one of
+/// its day's implementations, is skipped with a warning. Only the first call does anything.
+pub fn install(plugins: Vec<Plugin>) {
+    let mut by_day = BTreeMap::<u8, Vec<Arc<Plugin>>>::new();
+    let mut installed = Vec::new();
+    for plugin in plugins {
+        let Some(builtin) = solution::builtin(plugin.day) else {
+            eprintln!(
+                "warning: skipping plugin {}: day {} isn't solved here",
+                plugin.path.display(),
+                plugin.day
+            );
+            continue;
+        };
+        let others = by_day.entry(plugin.day).or_default();
+        let taken = builtin
+            .variants()
+            .iter()
+            .any(|&(_, name)| name == plugin.name)
+            || others.iter().any(|other| other.name == plugin.name);
+        if taken {
+            eprintln!(
+                "warning: skipping plugin {}: day {} already has an implementation called {:?}",
+                plugin.path.display(),
+                plugin.day,
+                plugin.name
+            );
+            continue;
+        }
+        let plugin = Arc::new(plugin);
+        others.push(Arc::clone(&plugin));
+        installed.push(plugin);
+    }
+    let solutions = by_day
+        .into_iter()
+        .filter(|(_, plugins)| !plugins.is_empty())
+        .map(|(day, plugins)| {
+            let builtin = solution::builtin(day).expect("checked above");
+            let solution: &'static dyn DynSolution =
+                Box::leak(Box::new(WithPlugins { builtin, plugins }));
+            (day, solution)
+        })
+        .collect();
+    let _ = INSTALLED.set(Installed {
+        plugins: installed,
+        solutions,
+    });
+}
+
+/// `day` with its plugins, if it has any installed.
+pub fn solution(day: u8) -> Option<&'static dyn DynSolution> {
+    INSTALLED.get()?.solutions.get(&day).copied()
+}
+
+/// Every installed plugin, in the order they were loaded.
+pub fn installed() -> &'static [Arc<Plugin>] {
+    INSTALLED.get().map_or(&[], |installed| &installed.plugins)
+}
+
+/// A day's built-in solution with its plugins added as extra variants.
+struct WithPlugins {
+    builtin: &'static dyn DynSolution,
+    plugins: Vec<Arc<Plugin>>,
+}
+
+/// The built-in parse of an input, plus the text itself for the plugins, which parse their own.
+struct PluginInput {
+    builtin: Box<dyn Parsed>,
+    input: String,
+    plugins: Vec<Arc<Plugin>>,
+}
+
+impl DynSolution for WithPlugins {
+    fn day(&self) -> u8 {
+        self.builtin.day()
+    }
+
+    fn variants(&self) -> Vec<(u8, &'static str)> {
+        let mut variants = self.builtin.variants();
+        for plugin in &self.plugins {
+            variants.extend(plugin.parts().into_iter().map(|part| (part, plugin.name)));
+        }
+        variants
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        self.builtin.params()
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, String> {
+        Ok(Box::new(PluginInput {
+            builtin: self.builtin.parse(input)?,
+            input: input.to_string(),
+            plugins: self.plugins.clone(),
+        }))
+    }
+
+    fn scale(&self, input: &str, factor: usize) -> Option<String> {
+        self.builtin.scale(input, factor)
+    }
+
+    /// A plugin can be rebuilt without this binary changing, so its answers can't be cached.
+    fn cacheable(&self, variant: &str) -> bool {
+        self.plugins.iter().all(|plugin| plugin.name != variant)
+    }
+}
+
+impl Parsed for PluginInput {
+    fn run(
+        &self,
+        part: u8,
+        variant: Option<&str>,
+        params: &Params,
+    ) -> Option<Result<Answer, String>> {
+        let plugin = self
+            .plugins
+            .iter()
+            .find(|plugin| variant == Some(plugin.name) && plugin.parts().contains(&part));
+        match plugin {
+            // The ABI has no way to hand parameters over, so they'd be ignored without a word.
+            Some(plugin) if !params.is_empty() => Some(Err(format!(
+                "plugin {} doesn't take parameters (given {})",
+                plugin.name, params
+            ))),
+            Some(plugin) => Some(plugin.solve(part, &self.input)),
+            None => self.builtin.run(part, variant, params),
+        }
+    }
+
+    fn grid(&self, params: &Params) -> Option<CharGrid> {
+        self.builtin.grid(params)
+    }
+
+    fn pictures(&self, params: &Params, answers: &[(u8, Answer)]) -> Vec<Picture> {
+        self.builtin.pictures(params, answers)
+    }
+
+    fn simulation(&self, params: &Params) -> Option<Box<dyn Playback>> {
+        self.builtin.simulation(params)
+    }
+}
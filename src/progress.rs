@@ -0,0 +1,233 @@
+use std::{
+    io::{self, IsTerminal, Write},
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Parts that finish quicker than this never show any progress.
+const QUIET_PERIOD: Duration = Duration::from_millis(200);
+/// How often the bar is redrawn on a terminal.
+const DRAW_INTERVAL: Duration = Duration::from_millis(100);
+/// How often a line is logged when stderr isn't a terminal.
+const LOG_INTERVAL: Duration = Duration::from_secs(5);
+const BAR_WIDTH: usize = 30;
+
+static ENABLED: AtomicBool = AtomicBool::new(true);
+
+/// Turns progress reporting off (or back on) everywhere, e.g. for `-q` or `--format json`.
+pub fn set_enabled(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Progress through a long loop, shown on stderr: a bar on a terminal, otherwise a line every few
+/// seconds. Can be ticked from several threads at once.
+pub struct Progress {
+    label: String,
+    total: u64,
+    done: AtomicU64,
+    start: Instant,
+    terminal: bool,
+    /// When it was last shown (and so whether a bar is on screen that needs clearing), and where
+    /// it's shown.
+    shown: Mutex<(Option<Instant>, Box<dyn Write + Send>)>,
+}
+
+impl Progress {
+    pub fn new(label: &str, total: u64) -> Self {
+        Self::to(label, total, io::stderr().is_terminal(), io::stderr())
+    }
+
+    /// Shows progress on `out`, as a bar if it's a `terminal`.
+    fn to(label: &str, total: u64, terminal: bool, out: impl Write + Send + 'static) -> Self {
+        Self {
+            label: label.to_string(),
+            total,
+            done: AtomicU64::new(0),
+            start: Instant::now(),
+            terminal,
+            shown: Mutex::new((None, Box::new(out))),
+        }
+    }
+
+    pub fn tick(&self) {
+        self.inc(1);
+    }
+
+    pub fn inc(&self, n: u64) {
+        let done = self.done.fetch_add(n, Ordering::Relaxed) + n;
+        if !ENABLED.load(Ordering::Relaxed) {
+            return;
+        }
+        let elapsed = self.start.elapsed();
+        if elapsed < QUIET_PERIOD {
+            return;
+        }
+        // Whoever's holding the lock is already drawing, so don't wait for it.
+        let Ok(mut shown) = self.shown.try_lock() else {
+            return;
+        };
+        let (last, out) = &mut *shown;
+        let interval = if self.terminal {
+            DRAW_INTERVAL
+        } else {
+            LOG_INTERVAL
+        };
+        let due = match last {
+            Some(last) => last.elapsed() >= interval,
+            None => self.terminal || elapsed >= LOG_INTERVAL,
+        };
+        if due {
+            self.show(out, done, elapsed);
+            *last = Some(Instant::now());
+        }
+    }
+
+    fn show(&self, out: &mut dyn Write, done: u64, elapsed: Duration) {
+        let rate = done as f64 / elapsed.as_secs_f64();
+        let eta = if rate > 0.0 && done <= self.total {
+            format!(
+                "{:.0?}",
+                Duration::from_secs_f64((self.total - done) as f64 / rate)
+            )
+        } else {
+            "?".to_string()
+        };
+        let fraction = (done as f64 / self.total.max(1) as f64).min(1.0);
+        if self.terminal {
+            let filled = (fraction * BAR_WIDTH as f64) as usize;
+            let _ = write!(
+                out,
+                "\r\x1b[2K{} [{}{}] {}/{} {:.0}/s ETA {}",
+                self.label,
+                "#".repeat(filled),
+                ".".repeat(BAR_WIDTH - filled),
+                done,
+                self.total,
+                rate,
+                eta
+            );
+            let _ = out.flush();
+        } else {
+            let _ = writeln!(
+                out,
+                "{}: {}/{} ({:.0}%), {:.0}/s, ETA {}",
+                self.label,
+                done,
+                self.total,
+                fraction * 100.0,
+                rate,
+                eta
+            );
+        }
+    }
+}
+
+impl Drop for Progress {
+    /// Clears the bar away so it doesn't get mixed up with the answers.
+    fn drop(&mut self) {
+        let Ok((last, out)) = self.shown.get_mut() else {
+            return;
+        };
+        if self.terminal && last.is_some() {
+            let _ = write!(out, "\r\x1b[2K");
+            let _ = out.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Arc;
+
+    /// Whether progress is shown is a global switch, so these tests take turns.
+    static LOCK: Mutex<()> = Mutex::new(());
+
+    /// Somewhere to show progress that the test can read back.
+    #[derive(Clone, Default)]
+    struct Screen(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Screen {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl Screen {
+        fn text(&self) -> String {
+            String::from_utf8(self.0.lock().unwrap().clone()).unwrap()
+        }
+    }
+
+    /// A progress of 100 on `screen` that started `ago`.
+    fn started(terminal: bool, screen: &Screen, ago: Duration) -> Progress {
+        let mut progress = Progress::to("day 6 part 2", 100, terminal, screen.clone());
+        progress.start = Instant::now() - ago;
+        progress
+    }
+
+    #[test]
+    fn draws_a_bar_on_a_terminal_and_clears_it_after() {
+        let _lock = LOCK.lock().unwrap();
+        set_enabled(true);
+        let screen = Screen::default();
+        let progress = started(true, &screen, Duration::from_secs(1));
+        progress.inc(50);
+        let bar = screen.text();
+        assert!(
+            bar.starts_with("\r\x1b[2Kday 6 part 2 [###############...............] 50/100"),
+            "{:?}",
+            bar
+        );
+        // Not drawn again until it's due.
+        progress.tick();
+        assert_eq!(screen.text(), bar);
+        drop(progress);
+        assert_eq!(screen.text(), format!("{}\r\x1b[2K", bar));
+    }
+
+    #[test]
+    fn logs_lines_when_it_isnt_a_terminal() {
+        let _lock = LOCK.lock().unwrap();
+        set_enabled(true);
+        let screen = Screen::default();
+        // No bar, and nothing at all for the first few seconds.
+        let progress = started(false, &screen, Duration::from_secs(1));
+        progress.inc(50);
+        drop(progress);
+        assert_eq!(screen.text(), "");
+
+        let progress = started(false, &screen, LOG_INTERVAL);
+        progress.inc(25);
+        drop(progress);
+        let text = screen.text();
+        assert!(
+            text.starts_with("day 6 part 2: 25/100 (25%), "),
+            "{:?}",
+            text
+        );
+        assert!(text.ends_with('\n') && !text.contains('\r'), "{:?}", text);
+    }
+
+    #[test]
+    fn shows_nothing_when_turned_off_or_quick() {
+        let _lock = LOCK.lock().unwrap();
+        let screen = Screen::default();
+        set_enabled(false);
+        let progress = started(true, &screen, LOG_INTERVAL);
+        progress.inc(50);
+        drop(progress);
+        set_enabled(true);
+        let progress = started(true, &screen, Duration::ZERO);
+        progress.inc(50);
+        drop(progress);
+        assert_eq!(screen.text(), "");
+    }
+}
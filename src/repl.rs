@@ -0,0 +1,458 @@
+use crate::grid::CharGrid;
+use crate::params::Params;
+use crate::simulation::Playback;
+use crate::solution::{DynSolution, Parsed};
+use crate::trace;
+use std::{
+    io::{self, BufRead, Write},
+    panic::{self, AssertUnwindSafe},
+    time,
+};
+
+/// How far one command (`finish`, `step n`, `seek t`) takes a simulation at most, as some never
+/// end.
+const STEP_LIMIT: usize = 1_000_000;
+
+const HELP: &str = "\
+commands:
+  run [part] [variant]      run both parts, or one part (default variant unless named)
+  1, 2                      shorthand for `run 1` / `run 2`
+  rerun                     repeat the last run
+  variants                  list the implementations of each part
+  grid [x0 y0 x1 y1]        print the parsed input, or the part between two corners
+  cell x y                  print one cell of the grid
+  set key value             override a parameter, e.g. `set part1_blinks 6`, `set max_x 11`
+  unset key                 go back to the default for a parameter
+  params                    list the day's parameters and any overrides
+  sim                       start (or restart) the day's simulation, with the current parameters
+  step [n]                  move the simulation on one step, or n steps (at most a million)
+  seek t                    go to step t of the simulation, backwards or forwards (at most a
+                            million steps on)
+  rewind                    go back to the start of the simulation
+  finish                    step until the simulation is done (at most a million steps)
+  show                      print where the simulation's got to
+  trace on|off              show or hide the solutions' debug output
+  help                      show this message
+  quit                      leave the repl";
+
+/// An interactive session over one day's input, which is parsed once up front.
+pub struct Repl {
+    solution: &'static dyn DynSolution,
+    parsed: Box<dyn Parsed>,
+    params: Params,
+    last_run: Option<(Option<u8>, Option<String>)>,
+    simulation: Option<Box<dyn Playback>>,
+}
+
+impl Repl {
+    pub fn new(solution: &'static dyn DynSolution, input: &str) -> Result<Self, String> {
+        Ok(Self {
+            solution,
+            parsed: solution.parse(input)?,
+            params: Params::new(),
+            last_run: None,
+            simulation: None,
+        })
+    }
+
+    /// Starts the session with `params` already set.
+    pub fn with_params(mut self, params: Params) -> Result<Self, String> {
+        params.check(&self.solution.params())?;
+        self.params = params;
+        Ok(self)
+    }
+
+    /// Reads commands from `reader` until it runs dry or `quit` is entered.
+    pub fn run(&mut self, mut reader: impl BufRead, mut out: impl Write) -> io::Result<()> {
+        let mut line = String::new();
+        loop {
+            write!(out, "day{}> ", self.solution.day())?;
+            out.flush()?;
+
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                writeln!(out)?;
+                return Ok(());
+            }
+            let words = line.split_whitespace().collect::<Vec<_>>();
+            match words.as_slice() {
+                [] => {}
+                ["quit" | "exit" | "q"] => return Ok(()),
+                _ => {
+                    if let Err(e) = self.command(&words, &mut out)? {
+                        writeln!(out, "error: {}", e)?;
+                    }
+                }
+            }
+        }
+    }
+
+    /// Carries out one command. The outer error is from writing, the inner one is the user's.
+    fn command(&mut self, words: &[&str], out: &mut impl Write) -> io::Result<Result<(), String>> {
+        match words {
+            ["help" | "?"] => writeln!(out, "{}", HELP)?,
+            ["run"] => self.run_parts(None, None, out)?,
+            ["run" | "part", part] | [part @ ("1" | "2")] => match parse_part(part) {
+                Ok(part) => self.run_parts(Some(part), None, out)?,
+                Err(e) => return Ok(Err(e)),
+            },
+            ["run" | "part", part, variant] => match parse_part(part) {
+                Ok(part) => self.run_parts(Some(part), Some(variant.to_string()), out)?,
+                Err(e) => return Ok(Err(e)),
+            },
+            ["rerun" | "r"] => match self.last_run.clone() {
+                Some((part, variant)) => self.run_parts(part, variant, out)?,
+                None => return Ok(Err("nothing has been run yet".to_string())),
+            },
+            ["variants"] => {
+                for (part, name) in self.solution.variants() {
+                    writeln!(out, "part {}: {}", part, name)?;
+                }
+            }
+            ["grid"] => match self.grid() {
+                Ok(grid) => write!(out, "{}", grid)?,
+                Err(e) => return Ok(Err(e)),
+            },
+            ["grid", x0, y0, x1, y1] => {
+                let corners = parse_coords(&[x0, y0, x1, y1]);
+                match corners.and_then(|corners| Ok((corners, self.grid()?))) {
+                    Ok((c, grid)) => write!(out, "{}", grid.window(c[0], c[1], c[2], c[3]))?,
+                    Err(e) => return Ok(Err(e)),
+                }
+            }
+            ["cell", x, y] => {
+                let coords = parse_coords(&[x, y]);
+                let (c, grid) = match coords.and_then(|coords| Ok((coords, self.grid()?))) {
+                    Ok(found) => found,
+                    Err(e) => return Ok(Err(e)),
+                };
+                match grid.get(c[0], c[1]) {
+                    Some(cell) => writeln!(out, "({}, {}): {:?}", c[0], c[1], cell)?,
+                    None => {
+                        return Ok(Err(format!(
+                            "({}, {}) is outside the {}x{} grid",
+                            c[0],
+                            c[1],
+                            grid.width(),
+                            grid.height()
+                        )))
+                    }
+                }
+            }
+            ["set", key, value] => {
+                let mut params = self.params.clone();
+                params.set(key, value);
+                if let Err(e) = params.check(&self.solution.params()) {
+                    return Ok(Err(e));
+                }
+                self.params = params;
+            }
+            ["unset", key] => {
+                if !self.params.unset(key) {
+                    return Ok(Err(format!("{} wasn't set", key)));
+                }
+            }
+            ["params"] => {
+                let specs = self.solution.params();
+                if specs.is_empty() {
+                    writeln!(out, "day {} has no parameters", self.solution.day())?;
+                }
+                for spec in specs {
+                    match self.params.iter().find(|(key, _)| *key == spec.name) {
+                        Some((_, value)) => {
+                            writeln!(out, "{} = {} (default {})", spec.name, value, spec.default)?
+                        }
+                        None => writeln!(out, "{} = {}", spec.name, spec.default)?,
+                    }
+                }
+            }
+            ["sim"] => {
+                let started =
+                    panic::catch_unwind(AssertUnwindSafe(|| self.parsed.simulation(&self.params)));
+                match started {
+                    Ok(Some(simulation)) => self.simulation = Some(simulation),
+                    Ok(None) => {
+                        return Ok(Err(format!(
+                            "day {} has no simulation",
+                            self.solution.day()
+                        )))
+                    }
+                    Err(_) => return Ok(Err("starting the simulation panicked".to_string())),
+                }
+                return self.show_simulation(out);
+            }
+            ["step"] => {
+                return self.simulate(out, |sim| {
+                    sim.step();
+                })
+            }
+            ["step", n] => match n.parse::<usize>() {
+                Ok(n) if n > STEP_LIMIT => {
+                    return Ok(Err(format!("at most {} steps at a time", STEP_LIMIT)))
+                }
+                Ok(n) => {
+                    return self.simulate(out, |sim| {
+                        sim.seek(sim.time() + n);
+                    })
+                }
+                Err(_) => return Ok(Err(format!("expected a number of steps, got {}", n))),
+            },
+            ["seek", t] => match t.parse::<usize>() {
+                Ok(t) if t > self.simulation.as_ref().map_or(0, |sim| sim.time()) + STEP_LIMIT => {
+                    return Ok(Err(format!("at most {} steps on at a time", STEP_LIMIT)))
+                }
+                Ok(t) => {
+                    return self.simulate(out, |sim| {
+                        sim.seek(t);
+                    })
+                }
+                Err(_) => return Ok(Err(format!("expected a step, got {}", t))),
+            },
+            ["rewind"] => return self.simulate(out, |sim| sim.rewind()),
+            ["finish"] => {
+                return self.simulate(out, |sim| {
+                    let limit = sim.time() + STEP_LIMIT;
+                    while sim.time() < limit && sim.step() {}
+                })
+            }
+            ["show"] => return self.show_simulation(out),
+            ["trace", "on"] => trace::set_enabled(true),
+            ["trace", "off"] => trace::set_enabled(false),
+            _ => {
+                return Ok(Err(format!(
+                    "unknown command: {} (try `help`)",
+                    words.join(" ")
+                )))
+            }
+        }
+        Ok(Ok(()))
+    }
+
+    fn run_parts(
+        &mut self,
+        part: Option<u8>,
+        variant: Option<String>,
+        out: &mut impl Write,
+    ) -> io::Result<()> {
+        self.last_run = Some((part, variant.clone()));
+        let parts = match part {
+            Some(part) => vec![part],
+            None => vec![1, 2],
+        };
+        for part in parts {
+            let start = time::Instant::now();
+            // A bad parameter or a bug in a variant shouldn't end the session.
+            let result = panic::catch_unwind(AssertUnwindSafe(|| {
+                self.parsed.run(part, variant.as_deref(), &self.params)
+            }));
+            let elapsed = start.elapsed();
+            match result {
+                Ok(Some(Ok(answer))) => writeln!(out, "Part {}: {} ({:?})", part, answer, elapsed)?,
+                Ok(Some(Err(e))) => writeln!(out, "error: part {} failed: {}", part, e)?,
+                Ok(None) => writeln!(
+                    out,
+                    "error: part {} has no variant {:?} (see `variants`)",
+                    part,
+                    variant.as_deref().unwrap_or("default")
+                )?,
+                Err(_) => writeln!(out, "error: part {} panicked", part)?,
+            }
+        }
+        Ok(())
+    }
+
+    /// Does `f` to the running simulation, then shows where it got to.
+    fn simulate(
+        &mut self,
+        out: &mut impl Write,
+        f: impl FnOnce(&mut dyn Playback),
+    ) -> io::Result<Result<(), String>> {
+        let Some(simulation) = self.simulation.as_mut() else {
+            return Ok(Err(
+                "no simulation running (start one with `sim`)".to_string()
+            ));
+        };
+        if panic::catch_unwind(AssertUnwindSafe(|| f(simulation.as_mut()))).is_err() {
+            self.simulation = None;
+            return Ok(Err(
+                "the simulation panicked, so it's been stopped".to_string()
+            ));
+        }
+        self.show_simulation(out)
+    }
+
+    fn show_simulation(&self, out: &mut impl Write) -> io::Result<Result<(), String>> {
+        let Some(simulation) = self.simulation.as_ref() else {
+            return Ok(Err(
+                "no simulation running (start one with `sim`)".to_string()
+            ));
+        };
+        if let Some(grid) = simulation.render() {
+            write!(out, "{}", grid)?;
+        }
+        writeln!(
+            out,
+            "step {}{}",
+            simulation.time(),
+            if simulation.is_done() { " (done)" } else { "" }
+        )?;
+        Ok(Ok(()))
+    }
+
+    /// The input drawn with the current parameters (the size of day 14's room can change).
+    fn grid(&self) -> Result<CharGrid, String> {
+        match panic::catch_unwind(AssertUnwindSafe(|| self.parsed.grid(&self.params))) {
+            Ok(Some(grid)) => Ok(grid),
+            Ok(None) => Err(format!("day {} has no grid view", self.solution.day())),
+            Err(_) => Err("drawing the grid panicked".to_string()),
+        }
+    }
+}
+
+fn parse_part(part: &str) -> Result<u8, String> {
+    match part {
+        "1" => Ok(1),
+        "2" => Ok(2),
+        _ => Err(format!("part must be 1 or 2, not {}", part)),
+    }
+}
+
+fn parse_coords(words: &[&&str]) -> Result<Vec<usize>, String> {
+    words
+        .iter()
+        .map(|word| {
+            word.parse::<usize>()
+                .map_err(|_| format!("expected a coordinate, got {}", word))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::params::ParamSpec;
+    use crate::solution;
+    use std::{
+        io::Cursor,
+        sync::atomic::{AtomicUsize, Ordering},
+    };
+
+    /// Day 14, counting how many times its input is parsed.
+    struct Counting {
+        day: &'static dyn DynSolution,
+        parses: AtomicUsize,
+    }
+
+    impl DynSolution for Counting {
+        fn day(&self) -> u8 {
+            self.day.day()
+        }
+
+        fn variants(&self) -> Vec<(u8, &'static str)> {
+            self.day.variants()
+        }
+
+        fn params(&self) -> Vec<ParamSpec> {
+            self.day.params()
+        }
+
+        fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, String> {
+            self.parses.fetch_add(1, Ordering::SeqCst);
+            self.day.parse(input)
+        }
+
+        fn scale(&self, input: &str, factor: usize) -> Option<String> {
+            self.day.scale(input, factor)
+        }
+    }
+
+    /// Runs `commands` in a session over day 14's example, returning what it printed and how
+    /// many times the input was parsed.
+    fn session(commands: &str) -> (String, usize) {
+        let counting: &'static Counting = Box::leak(Box::new(Counting {
+            day: solution::builtin(14).unwrap(),
+            parses: AtomicUsize::new(0),
+        }));
+        let mut repl = Repl::new(counting, include_str!("../input/day14/test.txt")).unwrap();
+        let mut out = Vec::new();
+        repl.run(Cursor::new(commands), &mut out).unwrap();
+        (
+            String::from_utf8(out).unwrap(),
+            counting.parses.load(Ordering::SeqCst),
+        )
+    }
+
+    #[test]
+    fn parses_once_for_the_whole_session() {
+        let (out, parses) = session(
+            "set max_x 11\nset max_y 7\nrun 1\nrerun\nrun 1 default\ngrid\nsim\nstep\nquit\nrun 1\n",
+        );
+        assert_eq!(parses, 1);
+        // Nothing after `quit` is run.
+        assert_eq!(out.matches("Part 1: 12 (").count(), 3, "{}", out);
+        assert!(!out.contains("error"), "{}", out);
+    }
+
+    #[test]
+    fn sets_and_unsets_parameters() {
+        let (out, _) = session(
+            "set max_x 11\nparams\nunset max_x\nparams\nunset max_x\nset max_x 0\nset bogus 1\n",
+        );
+        assert!(out.contains("max_x = 11 (default 101)"), "{}", out);
+        assert!(out.contains("max_x = 101\n"), "{}", out);
+        assert!(out.contains("error: max_x wasn't set"), "{}", out);
+        assert!(
+            out.contains("error: invalid value for parameter max_x: 0 isn't between 1 and 1000"),
+            "{}",
+            out
+        );
+        assert!(out.contains("error: unknown parameter bogus"), "{}", out);
+    }
+
+    #[test]
+    fn shows_the_grid_and_its_cells() {
+        let (out, _) =
+            session("set max_x 11\nset max_y 7\ngrid 0 0 3 1\ncell 0 0\ncell 3 0\ncell 11 0\n");
+        assert!(out.contains("1.12\n....\n"), "{}", out);
+        assert!(out.contains("(0, 0): '1'"), "{}", out);
+        assert!(out.contains("(3, 0): '2'"), "{}", out);
+        assert!(
+            out.contains("error: (11, 0) is outside the 11x7 grid"),
+            "{}",
+            out
+        );
+    }
+
+    #[test]
+    fn steps_and_seeks_through_the_simulation() {
+        let (out, _) =
+            session("step\nset max_x 11\nset max_y 7\nsim\nstep\nstep 4\nseek 2\nrewind\nstep x\n");
+        assert!(out.contains("error: no simulation running"), "{}", out);
+        for step in ["step 0\n", "step 1\n", "step 5\n", "step 2\n"] {
+            assert!(out.contains(step), "{}: {}", step, out);
+        }
+        assert_eq!(out.matches("step 0\n").count(), 2, "{}", out);
+        assert!(
+            out.contains("error: expected a number of steps, got x"),
+            "{}",
+            out
+        );
+    }
+
+    #[test]
+    fn wont_step_an_endless_simulation_forever() {
+        let (out, _) = session("sim\nstep 2000000\nseek 5\nseek 1000006\n");
+        assert!(
+            out.contains("error: at most 1000000 steps at a time"),
+            "{}",
+            out
+        );
+        // How far it can seek is counted from where it's got to.
+        assert!(out.contains("step 5\n"), "{}", out);
+        assert!(
+            out.contains("error: at most 1000000 steps on at a time"),
+            "{}",
+            out
+        );
+    }
+}
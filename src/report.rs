@@ -0,0 +1,443 @@
+use crate::answer::Answer;
+use crate::batch::Checked;
+use crate::grid::{CharGrid, Picture};
+use crate::input;
+use crate::manifest::{self, Manifest};
+use crate::memory::Usage;
+use crate::runner::{isolate, Runner};
+use crate::solution::DynSolution;
+use serde_json::{json, Value};
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+/// How wide a picture is drawn, at most, in CSS pixels.
+const PICTURE_WIDTH: usize = 640;
+
+/// Everything `aoc report` found out about one day.
+#[derive(Debug, Clone)]
+pub struct DayReport {
+    pub day: u8,
+    pub input: PathBuf,
+    /// The results, or why the day couldn't be run (no input, didn't parse...).
+    pub outcome: Result<DayRun, String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct DayRun {
+    pub parse: Option<Duration>,
+    pub parse_memory: Option<Usage>,
+    /// Every implementation of every part, each part's default first.
+    pub parts: Vec<Checked>,
+    pub pictures: Vec<Picture>,
+}
+
+impl DayRun {
+    /// The answers the parts' default implementations gave.
+    pub fn answers(&self) -> Vec<(u8, Answer)> {
+        self.defaults()
+            .filter_map(|part| Some((part.result.part, part.result.answer.clone().ok()?)))
+            .collect()
+    }
+
+    fn defaults(&self) -> impl Iterator<Item = &Checked> {
+        self.parts
+            .iter()
+            .enumerate()
+            .filter(|&(i, part)| is_default(&self.parts[..i], part))
+            .map(|(_, part)| part)
+    }
+}
+
+/// Whether `part` is the first implementation of its part, given the ones before it.
+fn is_default(before: &[Checked], part: &Checked) -> bool {
+    before
+        .iter()
+        .all(|other| other.result.part != part.result.part)
+}
+
+/// Runs every implementation of both parts on the input at `path`, one after the other so the
+/// timings are comparable, checks the answers against the manifest next to it and draws whatever
+/// pictures the day has.
+pub fn run_day(runner: &Runner, solution: &'static dyn DynSolution, path: &Path) -> DayReport {
+    let day = solution.day();
+    let outcome =
+        input::read(path, day).and_then(|content| run_input(runner, solution, path, &content));
+    DayReport {
+        day,
+        input: path.to_path_buf(),
+        outcome,
+    }
+}
+
+fn run_input(
+    runner: &Runner,
+    solution: &'static dyn DynSolution,
+    path: &Path,
+    content: &str,
+) -> Result<DayRun, String> {
+    let dir = path.parent().unwrap_or(Path::new(""));
+    let manifest = Manifest::load(&dir.join(manifest::FILE_NAME))?;
+    let name = path
+        .file_name()
+        .and_then(|name| name.to_str())
+        .unwrap_or("");
+
+    // Parsed once for every implementation; only an input that doesn't parse fails the day, a
+    // failing implementation is reported with the rest.
+    let runs = solution
+        .variants()
+        .into_iter()
+        .map(|(part, variant)| (part, Some(variant)))
+        .collect::<Vec<_>>();
+    let report = runner.run_each(solution, content, &runs)?;
+    let mut run = DayRun {
+        parse: report.parse,
+        parse_memory: report.parse_memory,
+        parts: report
+            .parts
+            .into_iter()
+            .map(|result| Checked {
+                expected: manifest.expected(name, result.part).cloned(),
+                result,
+            })
+            .collect(),
+        pictures: Vec::new(),
+    };
+    if let Some(input) = report.input {
+        let answers = run.answers();
+        match isolate(|| input.pictures(runner.params(), &answers)) {
+            Ok(pictures) => run.pictures = pictures,
+            Err(failure) => eprintln!(
+                "warning: drawing day {}'s pictures {}",
+                solution.day(),
+                failure
+            ),
+        }
+    }
+    Ok(run)
+}
+
+/// The reports as JSON, pictures included as text.
+pub fn to_json(reports: &[DayReport]) -> Value {
+    let days = reports
+        .iter()
+        .map(|report| {
+            let run = report.outcome.as_ref().ok();
+            let parts = run.map(|run| {
+                run.parts
+                    .iter()
+                    .enumerate()
+                    .map(|(i, part)| {
+                        json!({
+                            "part": part.result.part,
+                            "impl": part.result.variant,
+                            "default": is_default(&run.parts[..i], part),
+                            "answer": part.result.answer.as_ref().ok(),
+                            "error": part.result.answer.as_ref().err().map(|e| e.to_string()),
+                            "seconds": part.result.elapsed.as_secs_f64(),
+                            "memory": part.result.memory,
+                            "counts": part.result.counts,
+                            "expected": part.expected,
+                            "correct": part.correct(),
+                        })
+                    })
+                    .collect::<Vec<_>>()
+            });
+            let pictures = run.map(|run| {
+                run.pictures
+                    .iter()
+                    .map(|picture| json!({ "title": picture.title, "grid": picture.grid.to_string() }))
+                    .collect::<Vec<_>>()
+            });
+            json!({
+                "day": report.day,
+                "input": report.input.display().to_string(),
+                "error": report.outcome.as_ref().err(),
+                "parse_seconds": run.and_then(|run| run.parse).map(|parse| parse.as_secs_f64()),
+                "parse_memory": run.and_then(|run| run.parse_memory),
+                "parts": parts,
+                "pictures": pictures,
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({ "days": days })
+}
+
+/// The reports as a single HTML page that needs nothing else: no scripts, fonts or stylesheets
+/// from anywhere.
+pub fn to_html(reports: &[DayReport]) -> String {
+    let mut html = String::new();
+    html.push_str(concat!(
+        "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n",
+        "<title>Advent of Code 2024</title>\n<style>\n",
+        include_str!("report.css"),
+        "</style>\n</head>\n<body>\n<h1>Advent of Code 2024</h1>\n",
+    ));
+    summary(&mut html, reports);
+    overview(&mut html, reports);
+    for report in reports {
+        day(&mut html, report);
+    }
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn summary(html: &mut String, reports: &[DayReport]) {
+    let parts = reports
+        .iter()
+        .filter_map(|report| report.outcome.as_ref().ok())
+        .flat_map(|run| run.parts.iter())
+        .collect::<Vec<_>>();
+    let count = |correct: Option<bool>| parts.iter().filter(|p| p.correct() == correct).count();
+    let total = parts
+        .iter()
+        .map(|part| part.result.elapsed)
+        .sum::<Duration>();
+    let failed = reports.iter().filter(|r| r.outcome.is_err()).count();
+    let _ = writeln!(
+        html,
+        "<p class=\"summary\">{} of {} in {:.2?}: <span class=\"ok\">{} correct</span>, \
+         <span class=\"wrong\">{} wrong</span>, {} unchecked{}</p>",
+        plural(parts.len(), "run"),
+        plural(reports.len(), "day"),
+        total,
+        count(Some(true)),
+        count(Some(false)),
+        count(None),
+        match failed {
+            0 => String::new(),
+            n => format!(
+                ", <span class=\"wrong\">{} didn't run</span>",
+                plural(n, "day")
+            ),
+        }
+    );
+}
+
+/// A bar for every part's default implementation, on a log scale since the days range from
+/// microseconds to seconds.
+fn overview(html: &mut String, reports: &[DayReport]) {
+    let bars = reports
+        .iter()
+        .filter_map(|report| Some((report.day, report.outcome.as_ref().ok()?)))
+        .flat_map(|(day, run)| run.defaults().map(move |part| (day, part)))
+        .filter(|(_, part)| part.result.answer.is_ok())
+        .collect::<Vec<_>>();
+    let Some(slowest) = bars.iter().map(|(_, part)| part.result.elapsed).max() else {
+        return;
+    };
+    html.push_str("<h2>Timings</h2>\n<table class=\"timings\">\n");
+    for (day, part) in bars.iter() {
+        let _ = writeln!(
+            html,
+            "<tr><td><a href=\"#day{}\">Day {}</a> part {}</td><td class=\"time\">{:.2?}</td>\
+             <td class=\"bar\">{}</td></tr>",
+            day,
+            day,
+            part.result.part,
+            part.result.elapsed,
+            bar(part.result.elapsed, slowest, part.correct()),
+        );
+    }
+    html.push_str("</table>\n<p class=\"note\">Bars are on a log scale.</p>\n");
+}
+
+fn day(html: &mut String, report: &DayReport) {
+    let _ = writeln!(
+        html,
+        "<section id=\"day{}\">\n<h2>Day {}</h2>\n<p class=\"input\">{}</p>",
+        report.day,
+        report.day,
+        escape(&report.input.display().to_string())
+    );
+    let run = match &report.outcome {
+        Ok(run) => run,
+        Err(e) => {
+            let _ = writeln!(html, "<p class=\"wrong\">{}</p>\n</section>", escape(e));
+            return;
+        }
+    };
+    if let Some(parse) = run.parse {
+        let _ = writeln!(
+            html,
+            "<p>Parsed in {:.2?}{}</p>",
+            parse,
+            memory(run.parse_memory)
+        );
+    }
+
+    let slowest = run
+        .parts
+        .iter()
+        .map(|part| part.result.elapsed)
+        .max()
+        .unwrap_or_default();
+    html.push_str(
+        "<table>\n<tr><th>part</th><th>impl</th><th>answer</th><th>time</th>\
+         <th>vs default</th><th></th><th>check</th></tr>\n",
+    );
+    for (i, part) in run.parts.iter().enumerate() {
+        let default = run
+            .defaults()
+            .find(|default| default.result.part == part.result.part)
+            .unwrap_or(part);
+        let answer = match &part.result.answer {
+            Ok(answer)
+                if !is_default(&run.parts[..i], part)
+                    && default.result.answer.as_ref().is_ok_and(|d| d != answer) =>
+            {
+                format!(
+                    "{} <span class=\"wrong\">(the default says otherwise)</span>",
+                    escape(&answer.to_string())
+                )
+            }
+            Ok(answer) => escape(&answer.to_string()),
+            Err(failure) => format!(
+                "<span class=\"wrong\">{}</span>",
+                escape(&failure.to_string())
+            ),
+        };
+        let relative = if is_default(&run.parts[..i], part) {
+            "default".to_string()
+        } else {
+            format!(
+                "{:.2}×",
+                part.result.elapsed.as_secs_f64() / default.result.elapsed.as_secs_f64()
+            )
+        };
+        let _ = writeln!(
+            html,
+            "<tr><td>{}</td><td>{}</td><td class=\"answer\">{}</td><td class=\"time\">{:.2?}{}</td>\
+             <td class=\"time\">{}</td><td class=\"bar\">{}</td><td>{}</td></tr>",
+            part.result.part,
+            escape(part.result.variant),
+            answer,
+            part.result.elapsed,
+            memory(part.result.memory),
+            relative,
+            bar(part.result.elapsed, slowest, part.correct()),
+            check(part),
+        );
+    }
+    html.push_str("</table>\n");
+
+    for picture in run.pictures.iter() {
+        let _ = writeln!(
+            html,
+            "<figure>\n{}<figcaption>{}</figcaption>\n</figure>",
+            svg(&picture.grid),
+            escape(&picture.title)
+        );
+    }
+    html.push_str("</section>\n");
+}
+
+/// What was allocated, when the build counts allocations.
+fn memory(usage: Option<Usage>) -> String {
+    usage.map_or(String::new(), |usage| {
+        format!("<div class=\"note\">{}</div>", usage)
+    })
+}
+
+fn check(part: &Checked) -> String {
+    match (part.correct(), &part.expected) {
+        (Some(true), _) => "<span class=\"ok\">correct</span>".to_string(),
+        (Some(false), Some(expected)) => format!(
+            "<span class=\"wrong\">wrong, should be {}</span>",
+            escape(&expected.to_string())
+        ),
+        _ => "<span class=\"note\">unchecked</span>".to_string(),
+    }
+}
+
+/// A bar as long as `elapsed` is compared to `slowest`, on a log scale starting at a microsecond.
+fn bar(elapsed: Duration, slowest: Duration, correct: Option<bool>) -> String {
+    let scale = |d: Duration| (d.as_secs_f64() * 1e6).max(1.0).ln();
+    let width = match scale(slowest) {
+        max if max > 0.0 => 100.0 * scale(elapsed) / max,
+        _ => 100.0,
+    };
+    let class = match correct {
+        Some(true) => "ok",
+        Some(false) => "wrong",
+        None => "unchecked",
+    };
+    format!(
+        "<div class=\"{}\" style=\"width: {:.1}%\"></div>",
+        class,
+        width.max(0.5)
+    )
+}
+
+/// The grid as an SVG, one square per cell, with runs of the same character drawn as one rect.
+fn svg(grid: &CharGrid) -> String {
+    let (width, height) = (grid.width(), grid.height());
+    let scale = (PICTURE_WIDTH / width.max(1)).clamp(1, 16);
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        "<svg xmlns=\"http://www.w3.org/2000/svg\" viewBox=\"0 0 {} {}\" width=\"{}\" height=\"{}\" \
+         shape-rendering=\"crispEdges\"><rect width=\"{}\" height=\"{}\" fill=\"{}\"/>",
+        width,
+        height,
+        width * scale,
+        height * scale,
+        width,
+        height,
+        colour('.')
+    );
+    for y in 0..height {
+        let mut x = 0;
+        while x < width {
+            let c = grid.get(x, y).unwrap_or('.');
+            let run = (x..width)
+                .take_while(|&x| grid.get(x, y).unwrap_or('.') == c)
+                .count();
+            if c != '.' {
+                let _ = write!(
+                    svg,
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"1\" fill=\"{}\"/>",
+                    x,
+                    y,
+                    run,
+                    colour(c)
+                );
+            }
+            x += run;
+        }
+    }
+    svg.push_str("\n</svg>\n");
+    svg
+}
+
+/// Walls dark, empty space light, paths red, counts green, and anything else (like day 12's
+/// crops) a colour of its own.
+fn colour(c: char) -> String {
+    match c {
+        '.' => "#f4f1e8".to_string(),
+        '#' => "#3b3b3b".to_string(),
+        'X' => "#d0312d".to_string(),
+        '1'..='9' => "#2e8b3e".to_string(),
+        c => {
+            let hue = (c as u32).wrapping_mul(137) % 360;
+            format!("hsl({}, 55%, 62%)", hue)
+        }
+    }
+}
+
+fn plural(n: usize, word: &str) -> String {
+    match n {
+        1 => format!("1 {}", word),
+        n => format!("{} {}s", n, word),
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
@@ -0,0 +1,401 @@
+use crate::answer::Answer;
+use crate::cache::{Cache, Key};
+use crate::cancel::{self, CancelToken};
+use crate::memory::{Measure, Stray, Usage};
+use crate::params::Params;
+use crate::solution::{DynSolution, Parsed};
+use crate::stats::{Counts, Tally};
+use std::{
+    any::Any,
+    cell::{Cell, RefCell},
+    fmt,
+    panic::{self, AssertUnwindSafe},
+    sync::{mpsc, Arc, Once},
+    thread,
+    time::{self, Duration},
+};
+
+/// How long a timed out part gets to notice it's been cancelled before it's abandoned.
+const CANCEL_GRACE: Duration = Duration::from_secs(1);
+
+/// Parts run on their own thread, with as much stack as `main` gets.
+const WORKER_STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// The answer to one part, and how it was arrived at.
+#[derive(Debug, Clone)]
+pub struct PartResult {
+    pub part: u8,
+    pub variant: &'static str,
+    pub answer: Result<Answer, Failure>,
+    pub elapsed: Duration,
+    /// What the part allocated, if allocations are being counted and it wasn't cached.
+    pub memory: Option<Usage>,
+    /// What the part's `count!`s added up to, if `--stats` is on and it wasn't cached.
+    pub counts: Option<Counts>,
+    pub cached: bool,
+}
+
+/// Why a part didn't produce an answer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    TimedOut(Duration),
+    /// The part gave up with an error, like a plugin that couldn't parse the input.
+    Failed(String),
+    /// The part panicked. `location` is the file, line and column, when it could be found.
+    Panicked {
+        message: String,
+        location: Option<String>,
+    },
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Failure::TimedOut(limit) => write!(f, "timed out after {:?}", limit),
+            Failure::Failed(message) => write!(f, "failed: {}", message),
+            Failure::Panicked {
+                message,
+                location: Some(location),
+            } => write!(f, "panicked at {}: {}", location, message),
+            Failure::Panicked {
+                message,
+                location: None,
+            } => write!(f, "panicked at unknown location: {}", message),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Report {
+    /// How long parsing took, if the input had to be parsed at all.
+    pub parse: Option<Duration>,
+    /// What parsing allocated, if allocations are being counted.
+    pub parse_memory: Option<Usage>,
+    pub parts: Vec<PartResult>,
+    /// The parsed input, if it had to be parsed, so it can be drawn afterwards without parsing
+    /// it again.
+    pub input: Option<Arc<dyn Parsed>>,
+}
+
+/// Runs a day's parts, reusing answers from the cache where nothing has changed.
+#[derive(Debug, Clone, Default)]
+pub struct Runner {
+    params: Params,
+    cache: Option<Cache>,
+    reuse: bool,
+    timeout: Option<Duration>,
+    guard: Option<Arc<dyn Any + Send + Sync>>,
+}
+
+impl Runner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_params(mut self, params: Params) -> Self {
+        self.params = params;
+        self
+    }
+
+    /// Saves answers to `cache`, and unless `reuse` is false, gives back ones saved before.
+    pub fn with_cache(mut self, cache: Cache, reuse: bool) -> Self {
+        self.cache = Some(cache);
+        self.reuse = reuse;
+        self
+    }
+
+    pub fn params(&self) -> &Params {
+        &self.params
+    }
+
+    /// Gives up on parsing, or any part, that takes longer than `timeout`.
+    pub fn with_timeout(mut self, timeout: Option<Duration>) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Has every thread started to parse or run a part hold on to `guard` until it's finished
+    /// with it, even one that's been given up on and left running, so whoever handed it over can
+    /// tell when they've all stopped.
+    pub fn with_guard(mut self, guard: Arc<dyn Any + Send + Sync>) -> Self {
+        self.guard = Some(guard);
+        self
+    }
+
+    /// Runs `variant` (or the default) of each of `parts`, parsing `input` only if needed. If
+    /// parsing times out, so does every part that needed it.
+    pub fn run(
+        &self,
+        solution: &'static dyn DynSolution,
+        input: &str,
+        parts: &[u8],
+        variant: Option<&str>,
+    ) -> Result<Report, String> {
+        let runs = parts
+            .iter()
+            .map(|&part| (part, variant))
+            .collect::<Vec<_>>();
+        self.run_each(solution, input, &runs)
+    }
+
+    /// Runs each `(part, variant)` of `runs` in turn, like [`Runner::run`], parsing `input` once
+    /// for all of them.
+    pub fn run_each(
+        &self,
+        solution: &'static dyn DynSolution,
+        input: &str,
+        runs: &[(u8, Option<&str>)],
+    ) -> Result<Report, String> {
+        self.params.check(&solution.params())?;
+        let mut report = Report {
+            parse: None,
+            parse_memory: None,
+            parts: Vec::new(),
+            input: None,
+        };
+        let mut parsed: Option<Result<Arc<dyn Parsed>, Failure>> = None;
+
+        for &(part, variant) in runs {
+            let variant = resolve_variant(solution, part, variant)?;
+            let key = Key {
+                day: solution.day(),
+                part,
+                variant,
+                input,
+                params: &self.params,
+            };
+
+            let cacheable = solution.cacheable(variant);
+            if let Some(answer) = self.cached(&key).filter(|_| cacheable) {
+                report.parts.push(PartResult {
+                    part,
+                    variant,
+                    answer: Ok(answer),
+                    elapsed: Duration::ZERO,
+                    memory: None,
+                    counts: None,
+                    cached: true,
+                });
+                continue;
+            }
+
+            if parsed.is_none() {
+                let measure = Measure::start();
+                let input = input.to_string();
+                let (result, elapsed) =
+                    self.on_worker("parse".to_string(), move || solution.parse(&input));
+                report.parse = Some(elapsed);
+                report.parse_memory = measure.finish();
+                // A parser that times out or panics fails every part, the same way.
+                parsed = Some(match result {
+                    Ok(input) => Ok(Arc::from(input?)),
+                    Err(Failure::Panicked { message, location }) => Err(Failure::Panicked {
+                        message: format!("while parsing the input: {}", message),
+                        location,
+                    }),
+                    Err(failure) => Err(failure),
+                });
+            }
+            let parsed = match parsed.as_ref().unwrap() {
+                Ok(parsed) => parsed,
+                Err(failure) => {
+                    report.parts.push(PartResult {
+                        part,
+                        variant,
+                        answer: Err(failure.clone()),
+                        elapsed: Duration::ZERO,
+                        memory: None,
+                        counts: None,
+                        cached: false,
+                    });
+                    continue;
+                }
+            };
+
+            let (measure, tally) = (Measure::start(), Tally::start());
+            let (answer, elapsed) = self.run_part(parsed, part, variant);
+            let (memory, counts) = (measure.finish(), tally.finish());
+
+            if let (Some(cache), Ok(answer), true) = (&self.cache, &answer, cacheable) {
+                if let Err(e) = cache.put(&key, answer) {
+                    eprintln!("warning: couldn't save answer to the cache: {}", e);
+                }
+            }
+            report.parts.push(PartResult {
+                part,
+                variant,
+                answer,
+                elapsed,
+                memory,
+                counts,
+                cached: false,
+            });
+        }
+        report.input = parsed.and_then(Result::ok);
+        Ok(report)
+    }
+
+    fn run_part(
+        &self,
+        parsed: &Arc<dyn Parsed>,
+        part: u8,
+        variant: &'static str,
+    ) -> (Result<Answer, Failure>, Duration) {
+        let (parsed, params) = (Arc::clone(parsed), self.params.clone());
+        let (answer, elapsed) = self.on_worker(format!("part{}-{}", part, variant), move || {
+            parsed.run(part, Some(variant), &params)
+        });
+        let answer = answer.and_then(|answer| {
+            answer
+                .expect("variant was resolved already")
+                .map_err(Failure::Failed)
+        });
+        (answer, elapsed)
+    }
+
+    /// Runs `f` on a thread of its own, so that neither a panic nor something that never
+    /// finishes can take the caller down with it.
+    fn on_worker<T: Send + 'static>(
+        &self,
+        name: String,
+        f: impl FnOnce() -> T + Send + 'static,
+    ) -> (Result<T, Failure>, Duration) {
+        let token = CancelToken::new();
+        let (tx, rx) = mpsc::channel();
+        let (worker_token, guard) = (token.clone(), self.guard.clone());
+        let stray = Stray::default();
+        let worker_stray = stray.clone();
+        let start = time::Instant::now();
+        thread::Builder::new()
+            .name(name)
+            .stack_size(WORKER_STACK_SIZE)
+            .spawn(move || {
+                cancel::set_current(worker_token);
+                let start = time::Instant::now();
+                let result = isolate(f);
+                worker_stray.finished();
+                // Let go before saying it's done, so the guard's free by the time anyone hears.
+                drop(guard);
+                // Nobody's listening any more if it timed out.
+                let _ = tx.send((result, start.elapsed()));
+            })
+            .expect("couldn't start a worker thread");
+
+        let received = match self.timeout {
+            Some(limit) => rx.recv_timeout(limit).ok(),
+            None => rx.recv().ok(),
+        };
+        match received {
+            Some((result, elapsed)) => (result, elapsed),
+            None => {
+                let limit = self
+                    .timeout
+                    .expect("workers only go quiet when they time out");
+                token.cancel();
+                // If it never checks its token it's left running in the background, allocating
+                // behind the back of anything measured after.
+                if rx.recv_timeout(CANCEL_GRACE).is_err() {
+                    stray.abandon();
+                }
+                (Err(Failure::TimedOut(limit)), start.elapsed())
+            }
+        }
+    }
+
+    fn cached(&self, key: &Key) -> Option<Answer> {
+        match &self.cache {
+            Some(cache) if self.reuse => cache.get(key),
+            _ => None,
+        }
+    }
+}
+
+/// The name of `variant` of `part`, or of the part's default if no variant is asked for.
+fn resolve_variant(
+    solution: &dyn DynSolution,
+    part: u8,
+    variant: Option<&str>,
+) -> Result<&'static str, String> {
+    solution
+        .variants()
+        .into_iter()
+        .filter(|&(p, _)| p == part)
+        .map(|(_, name)| name)
+        .find(|&name| variant.is_none_or(|wanted| wanted == name))
+        .ok_or_else(|| match variant {
+            Some(wanted) => format!(
+                "day {} part {} has no implementation called {:?}",
+                solution.day(),
+                part,
+                wanted
+            ),
+            None => format!("day {} part {} isn't implemented", solution.day(), part),
+        })
+}
+
+thread_local! {
+    /// Whether this thread's panics are being caught by `isolate`, so needn't be printed.
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+    /// Where this thread last panicked. A panic carried over from one of rayon's threads left
+    /// its location over there, so it's reported without one rather than with another's.
+    static LOCATION: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Runs `f`, turning a panic into a `Failure` that says what went wrong and where.
+pub(crate) fn isolate<T>(f: impl FnOnce() -> T) -> Result<T, Failure> {
+    record_panic_locations();
+    let was_isolated = ISOLATED.replace(true);
+    LOCATION.set(None);
+    let result = panic::catch_unwind(AssertUnwindSafe(f));
+    ISOLATED.set(was_isolated);
+    result.map_err(|payload| Failure::Panicked {
+        message: panic_message(&*payload),
+        location: LOCATION.take(),
+    })
+}
+
+/// Panic payloads don't say where they came from, so the panic hook has to note it down.
+fn record_panic_locations() {
+    static ONCE: Once = Once::new();
+    ONCE.call_once(|| {
+        let hook = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info.location().map(|location| location.to_string());
+            LOCATION.set(location);
+            if !ISOLATED.get() {
+                hook(info);
+            }
+        }));
+    });
+}
+
+/// What a panic said, if it said anything.
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "no message".to_string()
+    }
+}
+
+/// Parses a duration like `30s`, `500ms`, `2m` or `1h`. A bare number is in seconds.
+pub fn parse_duration(s: &str) -> Result<Duration, String> {
+    let s = s.trim();
+    let split = s
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(s.len());
+    let (number, unit) = s.split_at(split);
+    let number = number
+        .parse::<f64>()
+        .map_err(|_| format!("invalid duration: {:?}", s))?;
+    let seconds = match unit.trim() {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        unit => return Err(format!("unknown unit {:?} in duration {:?}", unit, s)),
+    };
+    Duration::try_from_secs_f64(seconds).map_err(|e| format!("invalid duration {:?}: {}", s, e))
+}
@@ -0,0 +1,213 @@
+use crate::answer::Answer;
+use crate::runner::{Failure, Runner};
+use crate::solution::DynSolution;
+use std::time::Duration;
+
+/// The input's lines, `factor` times over.
+pub fn repeat_lines(input: &str, factor: usize) -> String {
+    let mut lines = input.trim_end().to_string();
+    lines.push('\n');
+    lines.repeat(factor)
+}
+
+/// Every line of a grid repeated `factor` times side by side, so the grid is `factor` times as
+/// wide.
+pub fn tile_grid(input: &str, factor: usize) -> String {
+    input
+        .lines()
+        .map(|line| line.repeat(factor) + "\n")
+        .collect()
+}
+
+/// How a part did on the input scaled up by `factor`.
+#[derive(Debug, Clone)]
+pub struct Sample {
+    pub factor: usize,
+    /// The scaled input's size in bytes.
+    pub size: usize,
+    /// The fastest of the runs.
+    pub elapsed: Duration,
+    pub answer: Answer,
+}
+
+/// `time ≈ coefficient × factor^exponent`, fitted to the samples in log-log space.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    pub exponent: f64,
+    pub coefficient: f64,
+    /// How well the line fits, from 0 (not at all) to 1 (perfectly).
+    pub r_squared: f64,
+}
+
+impl Fit {
+    /// The fit's exponent in words, e.g. "roughly quadratic".
+    pub fn describe(&self) -> &'static str {
+        match self.exponent {
+            e if e < 0.25 => "roughly constant",
+            e if e < 0.85 => "sublinear",
+            e if e < 1.25 => "roughly linear",
+            e if e < 1.75 => "between linear and quadratic",
+            e if e < 2.5 => "roughly quadratic",
+            e if e < 3.5 => "roughly cubic",
+            _ => "worse than cubic",
+        }
+    }
+}
+
+/// Runs `part` on `input` scaled up by each of `factors` in turn, keeping the fastest of `runs`
+/// runs at each size. Stops early (keeping what it has) once a size times out, as the bigger ones
+/// would too.
+pub fn run(
+    runner: &Runner,
+    solution: &'static dyn DynSolution,
+    input: &str,
+    part: u8,
+    variant: Option<&str>,
+    factors: &[usize],
+    runs: usize,
+) -> Result<Vec<Sample>, String> {
+    let mut samples = Vec::new();
+    for &factor in factors {
+        let scaled = solution.scale(input, factor).ok_or_else(|| {
+            format!(
+                "day {} doesn't know how to make its input bigger",
+                solution.day()
+            )
+        })?;
+        let mut fastest: Option<(Duration, Answer)> = None;
+        for _ in 0..runs.max(1) {
+            let report = runner.run(solution, &scaled, &[part], variant)?;
+            let result = report.parts.into_iter().next().expect("one part was run");
+            match result.answer {
+                Ok(answer) if fastest.as_ref().is_none_or(|(t, _)| result.elapsed < *t) => {
+                    fastest = Some((result.elapsed, answer))
+                }
+                Ok(_) => (),
+                Err(Failure::TimedOut(limit)) if samples.len() >= 2 => {
+                    eprintln!(
+                        "factor {} timed out after {:?}, stopping there",
+                        factor, limit
+                    );
+                    return Ok(samples);
+                }
+                Err(failure) => return Err(format!("factor {}: {}", factor, failure)),
+            }
+        }
+        let (elapsed, answer) = fastest.expect("ran at least once");
+        samples.push(Sample {
+            factor,
+            size: scaled.len(),
+            elapsed,
+            answer,
+        });
+    }
+    Ok(samples)
+}
+
+/// A least-squares fit of `log(time)` against `log(factor)`, or `None` with fewer than two sizes.
+pub fn fit(samples: &[Sample]) -> Option<Fit> {
+    let points = samples
+        .iter()
+        .map(|sample| {
+            (
+                (sample.factor as f64).ln(),
+                sample.elapsed.as_secs_f64().max(1e-9).ln(),
+            )
+        })
+        .collect::<Vec<_>>();
+    let n = points.len() as f64;
+    let mean_x = points.iter().map(|(x, _)| x).sum::<f64>() / n;
+    let mean_y = points.iter().map(|(_, y)| y).sum::<f64>() / n;
+    let sxx = points
+        .iter()
+        .map(|(x, _)| (x - mean_x).powi(2))
+        .sum::<f64>();
+    let sxy = points
+        .iter()
+        .map(|(x, y)| (x - mean_x) * (y - mean_y))
+        .sum::<f64>();
+    let syy = points
+        .iter()
+        .map(|(_, y)| (y - mean_y).powi(2))
+        .sum::<f64>();
+    if points.len() < 2 || sxx == 0.0 {
+        return None;
+    }
+    let exponent = sxy / sxx;
+    Some(Fit {
+        exponent,
+        coefficient: (mean_y - exponent * mean_x).exp(),
+        r_squared: if syy == 0.0 {
+            1.0
+        } else {
+            sxy * sxy / (sxx * syy)
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Samples taking `time(factor)` seconds at each of `factors`.
+    fn samples(factors: &[usize], time: impl Fn(f64) -> f64) -> Vec<Sample> {
+        factors
+            .iter()
+            .map(|&factor| Sample {
+                factor,
+                size: factor,
+                elapsed: Duration::from_secs_f64(time(factor as f64)),
+                answer: Answer::Int(0),
+            })
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() < 1e-3,
+            "{} isn't close to {}",
+            actual,
+            expected
+        );
+    }
+
+    #[test]
+    fn fits_a_linear_part() {
+        let fit = fit(&samples(&[1, 2, 4, 8, 16], |n| 0.003 * n)).unwrap();
+        assert_close(fit.exponent, 1.0);
+        assert_close(fit.coefficient, 0.003);
+        assert_close(fit.r_squared, 1.0);
+        assert_eq!(fit.describe(), "roughly linear");
+    }
+
+    #[test]
+    fn fits_a_quadratic_part() {
+        let fit = fit(&samples(&[1, 2, 3, 5, 8], |n| 0.5 * n * n)).unwrap();
+        assert_close(fit.exponent, 2.0);
+        assert_close(fit.coefficient, 0.5);
+        assert_close(fit.r_squared, 1.0);
+        assert_eq!(fit.describe(), "roughly quadratic");
+    }
+
+    #[test]
+    fn fits_noisy_and_degenerate_samples() {
+        // A little noise lowers r² without moving the exponent far.
+        let noise = [1.1, 0.9, 1.05, 0.95];
+        let noisy = fit(&samples(&[1, 2, 4, 8], |n| {
+            n * n * noise[n.log2() as usize]
+        }))
+        .unwrap();
+        assert!((1.8..2.2).contains(&noisy.exponent), "{:?}", noisy);
+        assert!(
+            noisy.r_squared < 1.0 && noisy.r_squared > 0.95,
+            "{:?}",
+            noisy
+        );
+
+        assert_eq!(fit(&samples(&[4], |n| n)), None);
+        assert_eq!(fit(&samples(&[4, 4], |n| n)), None);
+        let flat = fit(&samples(&[1, 2, 4], |_| 0.25)).unwrap();
+        assert_close(flat.exponent, 0.0);
+        assert_close(flat.r_squared, 1.0);
+    }
+}
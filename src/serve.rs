@@ -0,0 +1,338 @@
+//! `aoc serve`: answering puzzles over HTTP, for tools that would rather not shell out.
+//!
+//! `POST /day/{n}/part/{p}` solves a part of the input in the request body and
+//! `GET /days` lists what's solved. It listens on localhost only.
+
+use crate::config::Config;
+use crate::params::Params;
+use crate::runner::{Failure, Runner};
+use crate::solution::{self, DynSolution};
+use serde_json::{json, Value};
+use std::{
+    io::Read,
+    net::{Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    thread,
+    time::{Duration, Instant},
+};
+use tiny_http::{Header, Method, Request, Response};
+
+pub const DEFAULT_PORT: u16 = 8080;
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(30);
+/// Plenty for any puzzle input, even one made bigger for timing.
+pub const DEFAULT_MAX_BODY: usize = 1024 * 1024;
+
+/// How much one request, and all of them together, are allowed to take.
+#[derive(Debug, Clone, Copy)]
+pub struct Limits {
+    /// How long a part can run before it's given up on.
+    pub timeout: Duration,
+    /// The biggest input accepted, in bytes.
+    pub max_body: usize,
+    /// How many parts can be solved at once; requests beyond that are turned away.
+    pub max_concurrent: usize,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Self {
+            timeout: DEFAULT_TIMEOUT,
+            max_body: DEFAULT_MAX_BODY,
+            max_concurrent: thread::available_parallelism().map_or(4, |n| n.get()),
+        }
+    }
+}
+
+/// An HTTP server answering puzzles, listening on localhost.
+pub struct Server {
+    http: tiny_http::Server,
+    limits: Limits,
+    config: Arc<Config>,
+    log: bool,
+    /// How many parts are being solved right now.
+    solving: Arc<AtomicUsize>,
+}
+
+impl Server {
+    /// Starts listening on `port` on localhost (0 picks a free port, see [`Server::addr`]).
+    /// Nothing's answered until [`Server::run`].
+    pub fn bind(port: u16, limits: Limits) -> Result<Server, String> {
+        let http = tiny_http::Server::http((Ipv4Addr::LOCALHOST, port))
+            .map_err(|e| format!("couldn't listen on port {}: {}", port, e))?;
+        Ok(Server {
+            http,
+            limits,
+            config: Arc::new(Config::default()),
+            log: false,
+            solving: Arc::default(),
+        })
+    }
+
+    /// Runs days with `config`'s parameter overrides, under any the request asks for.
+    pub fn with_config(mut self, config: Config) -> Self {
+        self.config = Arc::new(config);
+        self
+    }
+
+    /// Logs every request, with its status and how long it took, to stderr.
+    pub fn with_log(mut self, log: bool) -> Self {
+        self.log = log;
+        self
+    }
+
+    pub fn addr(&self) -> SocketAddr {
+        self.http.server_addr().to_ip().expect("it listens on TCP")
+    }
+
+    /// Answers requests until [`Server::stop`] is called. Parts are solved on threads of their
+    /// own, so a slow one doesn't hold the others up.
+    pub fn run(&self) {
+        for request in self.http.incoming_requests() {
+            self.handle(request);
+        }
+    }
+
+    /// Makes `run` return, once it's finished with the request in hand.
+    pub fn stop(&self) {
+        self.http.unblock();
+    }
+
+    fn handle(&self, request: Request) {
+        let start = Instant::now();
+        let (day, part, query) = match route(request.method(), request.url()) {
+            Ok(Route::Days) => return reply(request, start, self.log, 200, days()),
+            Ok(Route::Solve { day, part, query }) => (day, part, query),
+            Err((status, message)) => {
+                return reply(request, start, self.log, status, error(message));
+            }
+        };
+        let (limits, config, log) = (self.limits, Arc::clone(&self.config), self.log);
+        let solving = Arc::clone(&self.solving);
+        thread::spawn(move || {
+            let mut request = request;
+            // The body's read before a slot's taken, so a client that stalls halfway through
+            // sending it only holds up its own thread, not one of the slots everyone shares.
+            let input = match read_input(&mut request, limits.max_body) {
+                Ok(input) => input,
+                Err((status, message)) => {
+                    return reply(request, start, log, status, error(message));
+                }
+            };
+            let Some(slot) = Slot::take(&solving, limits.max_concurrent) else {
+                let message = format!(
+                    "already solving {} parts, try again later",
+                    limits.max_concurrent
+                );
+                return reply(request, start, log, 503, error(message));
+            };
+            // Shared with the threads solving the part, so it isn't given back while one that
+            // timed out is still running.
+            let slot = Arc::new(slot);
+            let (status, body) = solve(&config, limits, &slot, day, part, &query, &input);
+            // Given back first, so it's free for whatever the client asks next.
+            drop(slot);
+            reply(request, start, log, status, body);
+        });
+    }
+}
+
+/// One of the parts being solved at once, given back when it's dropped.
+struct Slot(Arc<AtomicUsize>);
+
+impl Slot {
+    /// A slot, unless `max` parts are being solved already.
+    fn take(solving: &Arc<AtomicUsize>, max: usize) -> Option<Slot> {
+        solving
+            .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| {
+                (n < max).then_some(n + 1)
+            })
+            .ok()
+            .map(|_| Slot(Arc::clone(solving)))
+    }
+}
+
+impl Drop for Slot {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+enum Route {
+    Days,
+    Solve { day: u8, part: u8, query: String },
+}
+
+/// Where a request is going, or the status and message to turn it away with.
+fn route(method: &Method, url: &str) -> Result<Route, (u16, String)> {
+    let (path, query) = url.split_once('?').unwrap_or((url, ""));
+    let segments = path.trim_matches('/').split('/').collect::<Vec<_>>();
+    let (route, allowed) = match segments[..] {
+        ["days"] => (Route::Days, Method::Get),
+        ["day", day, "part", part] => {
+            let (Ok(day), Ok(part)) = (day.parse(), part.parse()) else {
+                return Err((404, format!("there's nothing at {}", path)));
+            };
+            let query = query.to_string();
+            (Route::Solve { day, part, query }, Method::Post)
+        }
+        _ => return Err((404, format!("there's nothing at {}", path))),
+    };
+    if *method != allowed {
+        return Err((405, format!("{} only takes {}", path, allowed)));
+    }
+    Ok(route)
+}
+
+/// The request's body, as long as it's UTF-8 and no bigger than `max` bytes.
+fn read_input(request: &mut Request, max: usize) -> Result<String, (u16, String)> {
+    let too_big = || (413, format!("the input is over the limit of {} bytes", max));
+    if request.body_length().is_some_and(|len| len > max) {
+        return Err(too_big());
+    }
+    let mut body = Vec::new();
+    request
+        .as_reader()
+        .take(max as u64 + 1)
+        .read_to_end(&mut body)
+        .map_err(|e| (400, format!("couldn't read the input: {}", e)))?;
+    if body.len() > max {
+        return Err(too_big());
+    }
+    String::from_utf8(body).map_err(|_| (400, "the input isn't UTF-8".to_string()))
+}
+
+/// Solves `part` of `day`, with the implementation (`impl=`) and parameters from `query`.
+fn solve(
+    config: &Config,
+    limits: Limits,
+    slot: &Arc<Slot>,
+    day: u8,
+    part: u8,
+    query: &str,
+    input: &str,
+) -> (u16, Value) {
+    let Some(solution) = solution::get(day) else {
+        return (404, error(format!("day {} isn't solved yet", day)));
+    };
+    let mut variant = None;
+    let mut params = Params::new();
+    for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+        let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+        let (key, value) = match (decode(key), decode(value)) {
+            (Ok(key), Ok(value)) => (key, value),
+            (Err(e), _) | (_, Err(e)) => return (400, error(e)),
+        };
+        match key.as_str() {
+            "impl" => variant = Some(value),
+            _ => params.set(&key, &value),
+        }
+    }
+    let runner = Runner::new()
+        .with_params(config.params(day, params))
+        .with_timeout(Some(limits.timeout))
+        .with_guard(Arc::clone(slot) as _);
+    let report = match runner.run(solution, input, &[part], variant.as_deref()) {
+        Ok(report) => report,
+        Err(e) => return (400, error(e)),
+    };
+    let result = &report.parts[0];
+    let mut body = json!({
+        "day": day,
+        "part": part,
+        "impl": result.variant,
+        "parse_seconds": report.parse.map(|parse| parse.as_secs_f64()),
+        "seconds": result.elapsed.as_secs_f64(),
+    });
+    let status = match &result.answer {
+        Ok(answer) => {
+            body["answer"] = json!(answer);
+            200
+        }
+        Err(failure) => {
+            body["error"] = json!(failure.to_string());
+            match failure {
+                Failure::TimedOut(_) => 504,
+                Failure::Failed(_) => 422,
+                Failure::Panicked { .. } => 500,
+            }
+        }
+    };
+    (status, body)
+}
+
+/// A query string's key or value with its `%xx` escapes and `+`s turned back into what they
+/// stand for.
+fn decode(s: &str) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(s.len());
+    let mut rest = s.bytes();
+    while let Some(b) = rest.next() {
+        match b {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let digit = |b: Option<u8>| b.and_then(|b| (b as char).to_digit(16));
+                match (digit(rest.next()), digit(rest.next())) {
+                    (Some(hi), Some(lo)) => bytes.push((hi * 16 + lo) as u8),
+                    _ => return Err(format!("bad escape in the query: {}", s)),
+                }
+            }
+            _ => bytes.push(b),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| format!("the query isn't UTF-8: {}", s))
+}
+
+/// Every day that's solved, with its implementations of each part and its parameters.
+fn days() -> Value {
+    let days = solution::all()
+        .into_iter()
+        .map(|solution| {
+            json!({
+                "day": solution.day(),
+                "parts": parts(solution),
+                "params": solution.params(),
+            })
+        })
+        .collect::<Vec<_>>();
+    json!(days)
+}
+
+/// Each part with the names of its implementations, the default first.
+fn parts(solution: &dyn DynSolution) -> Vec<Value> {
+    let variants = solution.variants();
+    [1, 2]
+        .into_iter()
+        .map(|part| {
+            let impls = variants
+                .iter()
+                .filter(|&&(p, _)| p == part)
+                .map(|&(_, name)| name)
+                .collect::<Vec<_>>();
+            json!({ "part": part, "impls": impls })
+        })
+        .collect()
+}
+
+fn error(message: impl Into<String>) -> Value {
+    json!({ "error": message.into() })
+}
+
+fn reply(request: Request, start: Instant, log: bool, status: u16, body: Value) {
+    if log {
+        eprintln!(
+            "{} {} {} ({:.2?})",
+            request.method(),
+            request.url(),
+            status,
+            start.elapsed()
+        );
+    }
+    let content_type = Header::from_bytes("Content-Type", "application/json").unwrap();
+    let response = Response::from_string(body.to_string())
+        .with_status_code(status)
+        .with_header(content_type);
+    // Nobody's listening any more if the client hung up.
+    let _ = request.respond(response);
+}
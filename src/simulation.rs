@@ -0,0 +1,235 @@
+use crate::cancel;
+use crate::grid::CharGrid;
+use std::collections::BTreeMap;
+
+/// How often the days' simulations are snapshotted: often enough that seeking back is quick,
+/// rarely enough that long runs don't eat all the memory.
+pub const SNAPSHOT_EVERY: usize = 100;
+
+/// Something that plays out one step at a time, like day 6's guard or day 14's robots. The
+/// simulation holds the rules (the map, the list of moves...) and `State` everything that changes.
+pub trait Simulation {
+    type State: Clone;
+
+    /// Moves `state` on by one step.
+    fn step(&self, state: &mut Self::State);
+
+    /// Whether there's nothing more to simulate. Some simulations go on forever.
+    fn is_done(&self, _state: &Self::State) -> bool {
+        false
+    }
+
+    /// The state drawn as a grid, for simulations where that makes sense.
+    fn render(&self, _state: &Self::State) -> Option<CharGrid> {
+        None
+    }
+
+    /// Steps `state` until the simulation is done.
+    fn run_to_end(&self, state: &mut Self::State) {
+        while !self.is_done(state) {
+            cancel::check();
+            self.step(state);
+        }
+    }
+}
+
+/// Runs a simulation, keeping a snapshot of the state every `every` steps so it can be wound
+/// back to any earlier step without starting again from the beginning.
+pub struct Driver<S: Simulation> {
+    simulation: S,
+    state: S::State,
+    time: usize,
+    every: usize,
+    /// Always has the state at step 0.
+    snapshots: BTreeMap<usize, S::State>,
+}
+
+impl<S: Simulation> Driver<S> {
+    /// Starts `simulation` at `initial`, snapshotting every `every` steps (0 for only the start).
+    pub fn new(simulation: S, initial: S::State, every: usize) -> Self {
+        Self {
+            simulation,
+            snapshots: BTreeMap::from([(0, initial.clone())]),
+            state: initial,
+            time: 0,
+            every,
+        }
+    }
+
+    pub fn simulation(&self) -> &S {
+        &self.simulation
+    }
+
+    pub fn state(&self) -> &S::State {
+        &self.state
+    }
+
+    /// How many steps have been taken.
+    pub fn time(&self) -> usize {
+        self.time
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.simulation.is_done(&self.state)
+    }
+
+    pub fn render(&self) -> Option<CharGrid> {
+        self.simulation.render(&self.state)
+    }
+
+    /// Takes one step, unless the simulation is done. Returns whether it took one.
+    pub fn step(&mut self) -> bool {
+        if self.is_done() {
+            return false;
+        }
+        self.simulation.step(&mut self.state);
+        self.time += 1;
+        if self.time.is_multiple_of(self.every) {
+            self.snapshots.insert(self.time, self.state.clone());
+        }
+        true
+    }
+
+    /// Goes to step `time`, backwards from the nearest snapshot or forwards by stepping. Stops
+    /// early if the simulation finishes first; returns the step it got to.
+    pub fn seek(&mut self, time: usize) -> usize {
+        if time < self.time {
+            let (&start, state) = self
+                .snapshots
+                .range(..=time)
+                .next_back()
+                .expect("there's always a snapshot of the start");
+            self.state = state.clone();
+            self.time = start;
+        }
+        while self.time < time {
+            cancel::check();
+            if !self.step() {
+                break;
+            }
+        }
+        self.time
+    }
+
+    /// Back to the start.
+    pub fn rewind(&mut self) {
+        self.seek(0);
+    }
+
+    /// Steps until `done` says so (checking the current state first) or the simulation finishes,
+    /// and returns the step it stopped at if `done` was satisfied.
+    pub fn run_until(&mut self, mut done: impl FnMut(&S::State) -> bool) -> Option<usize> {
+        loop {
+            if done(&self.state) {
+                return Some(self.time);
+            }
+            cancel::check();
+            if !self.step() {
+                return None;
+            }
+        }
+    }
+}
+
+/// A [`Driver`] with its simulation's type erased, so tools like the repl can play any day's
+/// simulation.
+pub trait Playback {
+    fn time(&self) -> usize;
+    fn is_done(&self) -> bool;
+    fn render(&self) -> Option<CharGrid>;
+    fn step(&mut self) -> bool;
+    fn seek(&mut self, time: usize) -> usize;
+    fn rewind(&mut self);
+}
+
+impl<S: Simulation> Playback for Driver<S> {
+    fn time(&self) -> usize {
+        Driver::time(self)
+    }
+
+    fn is_done(&self) -> bool {
+        Driver::is_done(self)
+    }
+
+    fn render(&self) -> Option<CharGrid> {
+        Driver::render(self)
+    }
+
+    fn step(&mut self) -> bool {
+        Driver::step(self)
+    }
+
+    fn seek(&mut self, time: usize) -> usize {
+        Driver::seek(self, time)
+    }
+
+    fn rewind(&mut self) {
+        Driver::rewind(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Counts up by one a step, and stops at `end`.
+    struct Counter {
+        end: usize,
+    }
+
+    impl Simulation for Counter {
+        type State = usize;
+
+        fn step(&self, n: &mut usize) {
+            *n += 1;
+        }
+
+        fn is_done(&self, n: &usize) -> bool {
+            *n == self.end
+        }
+    }
+
+    fn counter(every: usize) -> Driver<Counter> {
+        Driver::new(Counter { end: 1000 }, 0, every)
+    }
+
+    #[test]
+    fn snapshots_every_few_steps() {
+        let mut driver = counter(10);
+        driver.seek(35);
+        assert_eq!(
+            driver.snapshots.keys().copied().collect::<Vec<_>>(),
+            [0, 10, 20, 30]
+        );
+
+        let mut driver = counter(0);
+        driver.seek(35);
+        assert_eq!(driver.snapshots.keys().copied().collect::<Vec<_>>(), [0]);
+    }
+
+    #[test]
+    fn seeks_both_ways() {
+        let mut driver = counter(10);
+        assert_eq!(driver.seek(25), 25);
+        assert_eq!((driver.time(), *driver.state()), (25, 25));
+        // Back to the snapshot at 10, then forwards.
+        assert_eq!(driver.seek(13), 13);
+        assert_eq!(*driver.state(), 13);
+        // It stops where the simulation does.
+        assert_eq!(driver.seek(5000), 1000);
+        assert!(driver.is_done());
+        assert!(!driver.step());
+        driver.rewind();
+        assert_eq!((driver.time(), *driver.state()), (0, 0));
+    }
+
+    #[test]
+    fn runs_until_told_to_stop() {
+        let mut driver = counter(10);
+        assert_eq!(driver.run_until(|&n| n * n > 50), Some(8));
+        // The current state counts.
+        assert_eq!(driver.run_until(|&n| n == 8), Some(8));
+        assert_eq!(driver.run_until(|_| false), None);
+        assert_eq!(driver.time(), 1000);
+    }
+}
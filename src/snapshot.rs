@@ -0,0 +1,431 @@
+use crate::config::Config;
+use crate::grid::CharGrid;
+use std::{
+    env, fmt, fs,
+    io::{self, IsTerminal},
+    path::{Path, PathBuf},
+};
+
+/// Where the golden files live unless `snapshot_dir` says otherwise, from the directory tests
+/// (or `aoc`) run in. Cargo runs tests from the package's root.
+pub const DEFAULT_DIR: &str = "tests/snapshots";
+
+/// Set (to anything) to write snapshots straight over the golden files rather than next to them
+/// for review.
+pub const UPDATE_VAR: &str = "AOC_UPDATE_SNAPSHOTS";
+
+/// How many differing cells are listed by position before the rest are just counted.
+const CELLS_LISTED: usize = 12;
+/// How many differing frames are drawn before the rest are just counted.
+const FRAMES_DRAWN: usize = 3;
+
+/// A sequence of titled grids, like the warehouse after each of the robot's moves, that can be
+/// saved as a golden file and compared against later runs.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Snapshot {
+    frames: Vec<(String, CharGrid)>,
+}
+
+impl Snapshot {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, title: impl Into<String>, grid: CharGrid) {
+        self.frames.push((title.into(), grid));
+    }
+
+    pub fn frames(&self) -> &[(String, CharGrid)] {
+        &self.frames
+    }
+
+    /// Reads a snapshot back from the text `Display` writes.
+    pub fn parse(text: &str) -> Snapshot {
+        let mut frames = Vec::new();
+        let mut current: Option<(String, Vec<Vec<char>>)> = None;
+        for line in text.lines() {
+            if let Some(title) = line.strip_prefix("== ") {
+                frames.extend(current.take());
+                current = Some((title.to_string(), Vec::new()));
+            } else if !line.is_empty() {
+                current
+                    .get_or_insert_with(Default::default)
+                    .1
+                    .push(line.chars().collect());
+            }
+        }
+        frames.extend(current);
+        Snapshot {
+            frames: frames
+                .into_iter()
+                .map(|(title, rows)| (title, CharGrid::new(rows)))
+                .collect(),
+        }
+    }
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for (i, (title, grid)) in self.frames.iter().enumerate() {
+            if i > 0 {
+                writeln!(f)?;
+            }
+            writeln!(f, "== {}", title)?;
+            write!(f, "{}", grid)?;
+        }
+        Ok(())
+    }
+}
+
+/// Checks `actual` against the golden file `name` in the configured `snapshot_dir`, panicking
+/// with a diff if they differ. See [`check`].
+#[track_caller]
+pub fn assert_snapshot(name: &str, actual: &Snapshot) {
+    if let Err(e) = Config::load().and_then(|config| check(&config.snapshot_dir, name, actual)) {
+        panic!("{}", e);
+    }
+}
+
+/// Checks `actual` against `dir/name.snap`. If it's different (or there's no golden file yet),
+/// it's saved as `name.snap.new` for `aoc snapshots review` and the error says what changed,
+/// cell by cell. With `AOC_UPDATE_SNAPSHOTS` set it's accepted straight away instead.
+pub fn check(dir: &Path, name: &str, actual: &Snapshot) -> Result<(), String> {
+    let golden = dir.join(format!("{}.snap", name));
+    let pending = pending_path(&golden);
+    let text = actual.to_string();
+    if env::var_os(UPDATE_VAR).is_some() {
+        return write(&golden, &text).and_then(|()| remove(&pending));
+    }
+
+    let expected = match fs::read_to_string(&golden) {
+        Ok(expected) => expected.replace("\r\n", "\n"),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => {
+            write(&pending, &text)?;
+            return Err(format!(
+                "there's no snapshot {:?} yet; this run's is in {} \
+                 (`aoc snapshots review` to accept it)",
+                name,
+                pending.display()
+            ));
+        }
+        Err(e) => return Err(format!("couldn't read {}: {}", golden.display(), e)),
+    };
+    if expected == text {
+        return remove(&pending);
+    }
+    write(&pending, &text)?;
+    Err(format!(
+        "snapshot {:?} doesn't match\n{}\nthis run's is in {} \
+         (`aoc snapshots review` to accept or reject it)",
+        name,
+        diff(&Snapshot::parse(&expected), actual, colour(&io::stderr())),
+        pending.display()
+    ))
+}
+
+/// What's different between two snapshots: each differing frame drawn side by side, with the
+/// cells that changed highlighted (or marked in a third column with `colour` off), and listed by
+/// position.
+pub fn diff(expected: &Snapshot, actual: &Snapshot, colour: bool) -> String {
+    let mut out = Vec::new();
+    if expected.frames.len() != actual.frames.len() {
+        out.push(format!(
+            "expected {} frames, got {}",
+            expected.frames.len(),
+            actual.frames.len()
+        ));
+    }
+    let empty = (String::new(), CharGrid::new(Vec::new()));
+    let frames = expected.frames.len().max(actual.frames.len());
+    let mut drawn = 0;
+    let mut skipped = 0;
+    for i in 0..frames {
+        let (expected_title, expected) = expected.frames.get(i).unwrap_or(&empty);
+        let (actual_title, actual) = actual.frames.get(i).unwrap_or(&empty);
+        let cells = changed_cells(expected, actual);
+        if cells.is_empty() && expected_title == actual_title {
+            continue;
+        }
+        if drawn == FRAMES_DRAWN {
+            skipped += 1;
+            continue;
+        }
+        drawn += 1;
+
+        out.push(if expected_title == actual_title {
+            format!("frame {} ({}):", i, expected_title)
+        } else {
+            format!(
+                "frame {} (expected {:?}, got {:?}):",
+                i, expected_title, actual_title
+            )
+        });
+        out.extend(side_by_side(expected, actual, &cells, colour));
+        for &(x, y) in cells.iter().take(CELLS_LISTED) {
+            out.push(format!(
+                "  ({}, {}): expected {}, got {}",
+                x,
+                y,
+                describe(expected.get(x, y)),
+                describe(actual.get(x, y))
+            ));
+        }
+        if cells.len() > CELLS_LISTED {
+            out.push(format!(
+                "  ...and {} more cells",
+                cells.len() - CELLS_LISTED
+            ));
+        }
+    }
+    if skipped > 0 {
+        out.push(format!("...and {} more frames that differ", skipped));
+    }
+    out.join("\n")
+}
+
+/// Every position where the two grids have a different cell (or only one has a cell), row by row.
+fn changed_cells(expected: &CharGrid, actual: &CharGrid) -> Vec<(usize, usize)> {
+    let width = expected.width().max(actual.width());
+    let height = expected.height().max(actual.height());
+    (0..height)
+        .flat_map(|y| (0..width).map(move |x| (x, y)))
+        .filter(|&(x, y)| expected.get(x, y) != actual.get(x, y))
+        .collect()
+}
+
+/// The two grids next to each other, under `expected` and `actual` headings. Without colour, a
+/// third column marks the changed cells with `X`.
+fn side_by_side(
+    expected: &CharGrid,
+    actual: &CharGrid,
+    cells: &[(usize, usize)],
+    colour: bool,
+) -> Vec<String> {
+    let width = expected.width().max(actual.width()).max("expected".len());
+    let height = expected.height().max(actual.height());
+    let changed = |x, y| cells.contains(&(x, y));
+    let row = |grid: &CharGrid, y: usize, highlight: &str| {
+        (0..width)
+            .map(|x| {
+                let c = grid.get(x, y).unwrap_or(' ');
+                if colour && changed(x, y) {
+                    format!("{}{}\x1b[0m", highlight, c)
+                } else {
+                    c.to_string()
+                }
+            })
+            .collect::<String>()
+    };
+
+    let mut headings = format!("  {:<w$}  {:<w$}", "expected", "actual", w = width);
+    if !colour {
+        headings.push_str("  changed");
+    }
+    let mut lines = vec![headings.trim_end().to_string()];
+    for y in 0..height {
+        let mut line = format!(
+            "  {}  {}",
+            row(expected, y, "\x1b[1;31m"),
+            row(actual, y, "\x1b[1;32m")
+        );
+        if !colour {
+            line.push_str("  ");
+            line.extend((0..width).map(|x| if changed(x, y) { 'X' } else { '.' }));
+        }
+        lines.push(line.trim_end().to_string());
+    }
+    lines
+}
+
+fn describe(cell: Option<char>) -> String {
+    match cell {
+        Some(c) => format!("{:?}", c),
+        None => "nothing".to_string(),
+    }
+}
+
+/// Whether to colour diffs written to `stream`.
+pub fn colour(stream: &impl IsTerminal) -> bool {
+    stream.is_terminal() && env::var_os("NO_COLOR").is_none()
+}
+
+/// A run's snapshot that didn't match its golden file, waiting to be accepted or rejected.
+#[derive(Debug, Clone)]
+pub struct Pending {
+    pub name: String,
+    /// The `.snap.new` file with this run's snapshot.
+    pub path: PathBuf,
+    /// The `.snap` file it would replace, which might not exist yet.
+    pub golden: PathBuf,
+}
+
+impl Pending {
+    /// What accepting it would change: a diff against the golden file, or the whole of it for a
+    /// new snapshot.
+    pub fn describe(&self, colour: bool) -> Result<String, String> {
+        let actual = read(&self.path)?;
+        match fs::read_to_string(&self.golden) {
+            Ok(expected) => Ok(diff(
+                &Snapshot::parse(&expected),
+                &Snapshot::parse(&actual),
+                colour,
+            )),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                Ok(format!("new snapshot:\n{}", actual))
+            }
+            Err(e) => Err(format!("couldn't read {}: {}", self.golden.display(), e)),
+        }
+    }
+
+    /// Makes this run's snapshot the golden one.
+    pub fn accept(&self) -> Result<(), String> {
+        fs::rename(&self.path, &self.golden).map_err(|e| {
+            format!(
+                "couldn't move {} to {}: {}",
+                self.path.display(),
+                self.golden.display(),
+                e
+            )
+        })
+    }
+
+    /// Throws this run's snapshot away, keeping the golden one.
+    pub fn reject(&self) -> Result<(), String> {
+        remove(&self.path)
+    }
+}
+
+/// Every snapshot in `dir` waiting for review, by name.
+pub fn pending(dir: &Path) -> Result<Vec<Pending>, String> {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("couldn't read {}: {}", dir.display(), e)),
+    };
+    let mut pending = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| format!("couldn't read {}: {}", dir.display(), e))?
+            .path();
+        let Some(name) = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_suffix(".snap.new"))
+        else {
+            continue;
+        };
+        pending.push(Pending {
+            name: name.to_string(),
+            golden: dir.join(format!("{}.snap", name)),
+            path,
+        });
+    }
+    pending.sort_by(|a, b| a.name.cmp(&b.name));
+    Ok(pending)
+}
+
+fn pending_path(golden: &Path) -> PathBuf {
+    golden.with_extension("snap.new")
+}
+
+fn read(path: &Path) -> Result<String, String> {
+    fs::read_to_string(path).map_err(|e| format!("couldn't read {}: {}", path.display(), e))
+}
+
+fn write(path: &Path, text: &str) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("couldn't create {}: {}", parent.display(), e))?;
+    }
+    fs::write(path, text).map_err(|e| format!("couldn't write {}: {}", path.display(), e))
+}
+
+/// Removes `path` if it's there.
+fn remove(path: &Path) -> Result<(), String> {
+    match fs::remove_file(path) {
+        Err(e) if e.kind() != io::ErrorKind::NotFound => {
+            Err(format!("couldn't remove {}: {}", path.display(), e))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A snapshot of `frames`, each a title and the grid's rows separated by `/`.
+    fn snapshot(frames: &[(&str, &str)]) -> Snapshot {
+        let mut snapshot = Snapshot::new();
+        for &(title, rows) in frames {
+            let rows = rows.split('/').map(|row| row.chars().collect()).collect();
+            snapshot.push(title, CharGrid::new(rows));
+        }
+        snapshot
+    }
+
+    #[test]
+    fn same_snapshots_have_no_diff() {
+        let a = snapshot(&[("Initial state", "#.#/..@")]);
+        assert_eq!(diff(&a, &a.clone(), false), "");
+        assert_eq!(Snapshot::parse(&a.to_string()), a);
+    }
+
+    #[test]
+    fn marks_and_lists_changed_cells() {
+        let expected = snapshot(&[("Initial state", "#.#/..@"), ("Move <", "#.#/.@.")]);
+        let actual = snapshot(&[("Initial state", "#.#/..@"), ("Move <", "#.#/@..")]);
+        assert_eq!(
+            diff(&expected, &actual, false),
+            [
+                "frame 1 (Move <):",
+                "  expected  actual    changed",
+                "  #.#       #.#       ........",
+                "  .@.       @..       XX......",
+                "  (0, 1): expected '.', got '@'",
+                "  (1, 1): expected '@', got '.'",
+            ]
+            .join("\n")
+        );
+    }
+
+    #[test]
+    fn highlights_changed_cells_in_colour() {
+        let expected = snapshot(&[("Move >", ".@")]);
+        let actual = snapshot(&[("Move >", "@.")]);
+        let diff = diff(&expected, &actual, true);
+        let lines = diff.lines().collect::<Vec<_>>();
+        assert_eq!(lines[1], "  expected  actual");
+        assert_eq!(
+            lines[2],
+            "  \x1b[1;31m.\x1b[0m\x1b[1;31m@\x1b[0m        \x1b[1;32m@\x1b[0m\x1b[1;32m.\x1b[0m"
+        );
+        assert!(!diff.contains("changed"));
+    }
+
+    #[test]
+    fn describes_missing_frames_cells_and_new_titles() {
+        let expected = snapshot(&[("Initial state", "##"), ("Move ^", "##")]);
+        let actual = snapshot(&[("Initial state", "###"), ("Move v", "##"), ("Move v", "#")]);
+        let diff = diff(&expected, &actual, false);
+        assert!(diff.starts_with("expected 2 frames, got 3\nframe 0 (Initial state):"));
+        assert!(diff.contains("  (2, 0): expected nothing, got '#'"));
+        assert!(diff.contains("frame 1 (expected \"Move ^\", got \"Move v\"):"));
+        assert!(diff.contains("frame 2 (expected \"\", got \"Move v\"):"));
+        assert!(diff.contains("  (0, 0): expected nothing, got '#'"));
+    }
+
+    #[test]
+    fn cuts_long_diffs_short() {
+        let expected = snapshot(&[("Initial state", ".".repeat(20).as_str()); 5]);
+        let actual = snapshot(&[("Initial state", "#".repeat(20).as_str()); 5]);
+        let diff = diff(&expected, &actual, false);
+        assert_eq!(diff.matches("frame ").count(), FRAMES_DRAWN);
+        assert_eq!(
+            diff.matches(": expected '.', got '#'").count(),
+            3 * CELLS_LISTED
+        );
+        assert_eq!(diff.matches("  ...and 8 more cells").count(), 3);
+        assert!(diff.ends_with("...and 2 more frames that differ"));
+    }
+}
@@ -0,0 +1,304 @@
+use crate::answer::Answer;
+use crate::cache::{self, Cache};
+use crate::config::{Config, Layer, Source};
+#[cfg(feature = "plugins")]
+use crate::plugin;
+use crate::runner::{self, Runner};
+use crate::stats::Counts;
+use crate::{
+    day1, day10, day11, day12, day13, day14, day15, day2, day3, day4, day5, day6, day7, day8, day9,
+};
+use crate::{
+    grid::{CharGrid, Picture},
+    input::{self, InputArgs},
+    memory::Usage,
+    parallel,
+    params::{self, ParamSpec, Params},
+    progress,
+    simulation::Playback,
+    stats,
+};
+use clap::Parser;
+use std::{fmt, marker::PhantomData, path::PathBuf, process, time::Duration};
+
+/// One implementation of one part of a day.
+pub struct Variant<I> {
+    pub part: u8,
+    pub name: &'static str,
+    pub run: fn(&I, &Params) -> Answer,
+}
+
+pub trait Solution {
+    const DAY: u8;
+    type Input: Send + Sync;
+
+    fn parse(input: &str) -> Result<Self::Input, String>;
+
+    /// Every implementation of both parts. The first one listed for a part is its default.
+    fn variants() -> Vec<Variant<Self::Input>>;
+
+    /// The constants the variants read from their `Params`, and what they default to.
+    fn params() -> Vec<ParamSpec> {
+        Vec::new()
+    }
+
+    /// The parsed input drawn as a grid, for days where that makes sense.
+    fn grid(_input: &Self::Input, _params: &Params) -> Option<CharGrid> {
+        None
+    }
+
+    /// An input `factor` times the size of `input` (more rows, a wider grid...) for `aoc scale`,
+    /// for days where there's an obvious way to build one.
+    fn scale(_input: &str, _factor: usize) -> Option<String> {
+        None
+    }
+
+    /// Pictures of how the puzzle was solved (the guard's path, the tree...), given the answers
+    /// the parts came up with.
+    fn pictures(_input: &Self::Input, _params: &Params, _answers: &[(u8, Answer)]) -> Vec<Picture> {
+        Vec::new()
+    }
+
+    /// The puzzle as a step-by-step simulation, for days that are one, ready to be stepped
+    /// through and wound back.
+    fn simulation(_input: &Self::Input, _params: &Params) -> Option<Box<dyn Playback>> {
+        None
+    }
+}
+
+/// A `Solution` with its types erased, so every day can be handled the same way at runtime.
+pub trait DynSolution: Sync {
+    fn day(&self) -> u8;
+    fn variants(&self) -> Vec<(u8, &'static str)>;
+    fn params(&self) -> Vec<ParamSpec>;
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, String>;
+    fn scale(&self, input: &str, factor: usize) -> Option<String>;
+
+    /// Whether `variant`'s answers can be cached. Only ones compiled into this binary can, as the
+    /// cache is keyed on the binary.
+    fn cacheable(&self, _variant: &str) -> bool {
+        true
+    }
+}
+
+/// A day's input after parsing, ready to have its parts run any number of times.
+pub trait Parsed: Send + Sync {
+    /// Runs `variant` of `part` (or the default one). `None` if there's no such variant, and an
+    /// error if it couldn't come up with an answer (the built-in ones panic instead).
+    fn run(
+        &self,
+        part: u8,
+        variant: Option<&str>,
+        params: &Params,
+    ) -> Option<Result<Answer, String>>;
+    fn grid(&self, params: &Params) -> Option<CharGrid>;
+    fn pictures(&self, params: &Params, answers: &[(u8, Answer)]) -> Vec<Picture>;
+    fn simulation(&self, params: &Params) -> Option<Box<dyn Playback>>;
+}
+
+impl fmt::Debug for dyn Parsed {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("Parsed")
+    }
+}
+
+struct Erased<S>(PhantomData<fn() -> S>);
+
+struct ParsedInput<S: Solution>(S::Input);
+
+impl<S: Solution + 'static> DynSolution for Erased<S> {
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn variants(&self) -> Vec<(u8, &'static str)> {
+        S::variants().iter().map(|v| (v.part, v.name)).collect()
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        S::params()
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, String> {
+        Ok(Box::new(ParsedInput::<S>(S::parse(input)?)))
+    }
+
+    fn scale(&self, input: &str, factor: usize) -> Option<String> {
+        S::scale(input, factor)
+    }
+}
+
+impl<S: Solution> Parsed for ParsedInput<S> {
+    fn run(
+        &self,
+        part: u8,
+        variant: Option<&str>,
+        params: &Params,
+    ) -> Option<Result<Answer, String>> {
+        let variant = S::variants()
+            .into_iter()
+            .filter(|v| v.part == part)
+            .find(|v| variant.is_none_or(|name| name == v.name))?;
+        Some(Ok((variant.run)(&self.0, params)))
+    }
+
+    fn grid(&self, params: &Params) -> Option<CharGrid> {
+        S::grid(&self.0, params)
+    }
+
+    fn pictures(&self, params: &Params, answers: &[(u8, Answer)]) -> Vec<Picture> {
+        S::pictures(&self.0, params, answers)
+    }
+
+    fn simulation(&self, params: &Params) -> Option<Box<dyn Playback>> {
+        S::simulation(&self.0, params)
+    }
+}
+
+static DAYS: [&dyn DynSolution; 15] = [
+    &Erased::<day1::Day1>(PhantomData),
+    &Erased::<day2::Day2>(PhantomData),
+    &Erased::<day3::Day3>(PhantomData),
+    &Erased::<day4::Day4>(PhantomData),
+    &Erased::<day5::Day5>(PhantomData),
+    &Erased::<day6::Day6>(PhantomData),
+    &Erased::<day7::Day7>(PhantomData),
+    &Erased::<day8::Day8>(PhantomData),
+    &Erased::<day9::Day9>(PhantomData),
+    &Erased::<day10::Day10>(PhantomData),
+    &Erased::<day11::Day11>(PhantomData),
+    &Erased::<day12::Day12>(PhantomData),
+    &Erased::<day13::Day13>(PhantomData),
+    &Erased::<day14::Day14>(PhantomData),
+    &Erased::<day15::Day15>(PhantomData),
+];
+
+/// Every day, with any plugins that have been installed.
+pub fn all() -> Vec<&'static dyn DynSolution> {
+    DAYS.iter()
+        .filter_map(|solution| get(solution.day()))
+        .collect()
+}
+
+/// `day`'s solution, with any plugins for it that have been installed.
+pub fn get(day: u8) -> Option<&'static dyn DynSolution> {
+    #[cfg(feature = "plugins")]
+    if let Some(solution) = plugin::solution(day) {
+        return Some(solution);
+    }
+    builtin(day)
+}
+
+/// `day`'s solution as it's compiled in, without plugins.
+pub fn builtin(day: u8) -> Option<&'static dyn DynSolution> {
+    DAYS.iter().copied().find(|s| s.day() == day)
+}
+
+#[derive(Parser, Debug)]
+#[command(version, about, long_about=None)]
+struct FilenameArg {
+    #[command(flatten)]
+    input: InputArgs,
+
+    /// Where the dayN directories of inputs live (or AOC_INPUT_DIR, default input/)
+    #[arg(long)]
+    input_dir: Option<PathBuf>,
+
+    /// Work the answers out again even if they're cached
+    #[arg(long)]
+    no_cache: bool,
+
+    /// Give up on a part after this long, e.g. 30s
+    #[arg(long, value_parser = runner::parse_duration)]
+    timeout: Option<Duration>,
+
+    /// Don't show progress
+    #[arg(short, long)]
+    quiet: bool,
+
+    /// How many threads the parallel parts use (defaults to one per core)
+    #[arg(long, value_parser = clap::value_parser!(u16).range(1..))]
+    threads: Option<u16>,
+
+    /// Count the work each part does and print the totals (implies --no-cache)
+    #[arg(long)]
+    stats: bool,
+
+    /// Override one of the day's parameters, e.g. --param max_x=11
+    #[arg(long = "param", value_parser = params::parse_assignment)]
+    params: Vec<(String, String)>,
+}
+
+/// The totals of a part's counters, one per line under the part's timing.
+pub fn print_counts(counts: Option<&Counts>) {
+    for (name, count) in counts.into_iter().flatten() {
+        println!("  {}: {}", name, count);
+    }
+}
+
+/// The `main` of the `dayN` binaries: runs the default implementation of both parts on the day's
+/// input (or `--input`, or `--example N`). Settings not given as flags come from `aoc.toml` and co.
+pub fn main<S: Solution>() {
+    let args = FilenameArg::parse();
+    let mut config = Config::load().unwrap_or_else(|e| panic!("{}", e));
+    config.apply(
+        Layer {
+            input_dir: args.input_dir,
+            threads: args.threads,
+            timeout: args.timeout,
+            ..Layer::default()
+        },
+        Source::Flag,
+    );
+    let path = args.input.resolve(&config.input_dir, S::DAY);
+    let content = input::read(&path, S::DAY).unwrap_or_else(|e| panic!("{}", e));
+
+    if args.quiet {
+        progress::set_enabled(false);
+    }
+    stats::set_enabled(args.stats);
+    if let Some(threads) = config.threads {
+        parallel::set_threads(threads as usize).expect("Failed to set up threads.");
+    }
+
+    let solution = get(S::DAY).expect("every day is registered");
+    let report = Runner::new()
+        .with_params(config.params(S::DAY, args.params.into_iter().collect()))
+        .with_cache(
+            Cache::new(cache::DEFAULT_DIR),
+            !args.no_cache && !args.stats,
+        )
+        .with_timeout(config.timeout)
+        .run(solution, &content, &[1, 2], None)
+        .unwrap_or_else(|e| panic!("Failed to run day {}: {}", S::DAY, e));
+
+    let memory = |usage: Option<Usage>| usage.map_or(String::new(), |u| format!(" ({})", u));
+    if let Some(elapsed) = report.parse {
+        println!(
+            "Parsed file in {:?}{}",
+            elapsed,
+            memory(report.parse_memory)
+        );
+    }
+    for result in report.parts.iter() {
+        match &result.answer {
+            Err(failure) => println!("Part {}: {}", result.part, failure),
+            Ok(_) if result.cached => println!("Part {}: (cached)", result.part),
+            Ok(_) => println!(
+                "Part {}: {:?}{}",
+                result.part,
+                result.elapsed,
+                memory(result.memory)
+            ),
+        }
+        print_counts(result.counts.as_ref());
+    }
+    for result in report.parts.iter() {
+        if let Ok(answer) = &result.answer {
+            println!("Part {} result: {}", result.part, answer);
+        }
+    }
+    if report.parts.iter().any(|result| result.answer.is_err()) {
+        process::exit(1);
+    }
+}
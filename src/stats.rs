@@ -0,0 +1,100 @@
+use std::{
+    collections::BTreeMap,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Mutex,
+    },
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Every counter that has been counted at least once.
+static COUNTERS: Mutex<Vec<&'static Counter>> = Mutex::new(Vec::new());
+
+/// How many times each counter went up, by name.
+pub type Counts = BTreeMap<&'static str, u64>;
+
+/// Whether `count!` counts anything. Off unless `--stats` switches it on.
+pub fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+pub fn set_enabled(on: bool) {
+    ENABLED.store(on, Ordering::Relaxed);
+}
+
+/// One `count!` call site. Several can share a name; their counts are added together.
+pub struct Counter {
+    name: &'static str,
+    registered: AtomicBool,
+    count: AtomicU64,
+}
+
+impl Counter {
+    pub const fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            registered: AtomicBool::new(false),
+            count: AtomicU64::new(0),
+        }
+    }
+
+    pub fn add(&'static self, n: u64) {
+        if !self.registered.swap(true, Ordering::Relaxed) {
+            COUNTERS.lock().unwrap().push(self);
+        }
+        self.count.fetch_add(n, Ordering::Relaxed);
+    }
+}
+
+/// Every counter's total so far.
+fn totals() -> Counts {
+    let mut totals = Counts::new();
+    for counter in COUNTERS.lock().unwrap().iter() {
+        *totals.entry(counter.name).or_default() += counter.count.load(Ordering::Relaxed);
+    }
+    totals
+}
+
+/// Counts what's counted between [`Tally::start`] and [`Tally::finish`]. Like
+/// [`Measure`](crate::memory::Measure), the counts are for the whole process.
+pub struct Tally {
+    start: Counts,
+}
+
+impl Tally {
+    pub fn start() -> Self {
+        Self {
+            start: if enabled() { totals() } else { Counts::new() },
+        }
+    }
+
+    /// What went up since the start, or `None` if nothing's being counted.
+    pub fn finish(self) -> Option<Counts> {
+        if !enabled() {
+            return None;
+        }
+        Some(
+            totals()
+                .into_iter()
+                .map(|(name, total)| (name, total - self.start.get(name).unwrap_or(&0)))
+                .filter(|&(_, count)| count > 0)
+                .collect(),
+        )
+    }
+}
+
+/// Adds one (or `n`) to the counter called `name`, if `--stats` is on. Otherwise it's a single
+/// relaxed load, so it's fine in the hottest loop.
+#[macro_export]
+macro_rules! count {
+    ($name:expr) => {
+        $crate::count!($name, 1)
+    };
+    ($name:expr, $n:expr) => {
+        if $crate::stats::enabled() {
+            static COUNTER: $crate::stats::Counter = $crate::stats::Counter::new($name);
+            COUNTER.add($n as u64);
+        }
+    };
+}
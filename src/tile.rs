@@ -0,0 +1,61 @@
+use crate::grid::CharGrid;
+use std::fmt;
+
+pub use aoc2024_derive::Tile;
+
+/// One cell of a puzzle's map, written as a single character. Derive it with `#[derive(Tile)]`
+/// and a `#[tile('#')]` on each variant, which writes `from_char`, `to_char` and `Display` from
+/// the one table.
+pub trait Tile: Copy {
+    /// The type's name, for error messages.
+    const NAME: &'static str;
+
+    fn from_char(c: char) -> Result<Self, UnknownTile>;
+    fn to_char(self) -> char;
+}
+
+/// A character that isn't any of a tile type's variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnknownTile {
+    pub tile: &'static str,
+    pub found: char,
+}
+
+impl fmt::Display for UnknownTile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:?} isn't a {}", self.found, self.tile)
+    }
+}
+
+impl From<UnknownTile> for String {
+    fn from(e: UnknownTile) -> String {
+        e.to_string()
+    }
+}
+
+/// Reads a map one tile per character, row by row, saying where any character it doesn't know is.
+pub fn parse_grid<T: Tile>(input: &str) -> Result<Vec<Vec<T>>, String> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(y, line)| {
+            line.chars()
+                .enumerate()
+                .map(|(x, c)| {
+                    T::from_char(c).map_err(|e| format!("line {}, column {}: {}", y + 1, x + 1, e))
+                })
+                .collect()
+        })
+        .collect()
+}
+
+impl CharGrid {
+    /// A grid drawn from the tile at each position, with a space wherever there isn't one.
+    pub fn from_tiles<T: Tile>(
+        width: usize,
+        height: usize,
+        f: impl Fn(usize, usize) -> Option<T>,
+    ) -> Self {
+        Self::from_fn(width, height, |x, y| f(x, y).map_or(' ', T::to_char))
+    }
+}
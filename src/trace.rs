@@ -0,0 +1,37 @@
+use std::{
+    env,
+    sync::atomic::{AtomicU8, Ordering},
+};
+
+const UNSET: u8 = 0;
+const OFF: u8 = 1;
+const ON: u8 = 2;
+
+static STATE: AtomicU8 = AtomicU8::new(UNSET);
+
+/// Whether the solutions' debug output should be shown. Off unless `AOC_TRACE` is set,
+/// or it's switched on with `set_enabled`.
+pub fn enabled() -> bool {
+    match STATE.load(Ordering::Relaxed) {
+        UNSET => {
+            let on = env::var_os("AOC_TRACE").is_some();
+            set_enabled(on);
+            on
+        }
+        state => state == ON,
+    }
+}
+
+pub fn set_enabled(on: bool) {
+    STATE.store(if on { ON } else { OFF }, Ordering::Relaxed);
+}
+
+/// `eprintln!` that only prints when tracing is enabled.
+#[macro_export]
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if $crate::trace::enabled() {
+            eprintln!($($arg)*);
+        }
+    };
+}
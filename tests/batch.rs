@@ -0,0 +1,34 @@
+mod common;
+
+use aoc2024::batch::{self, FileResult};
+use aoc2024::parallel;
+use aoc2024::runner::Runner;
+use aoc2024::solution;
+use common::temp_dir;
+use std::{fs, sync::mpsc, thread, time::Duration};
+
+#[test]
+fn runs_days_that_loop_in_parallel() {
+    // Day 6's part 2 tries obstacles in parallel. With more files than threads, every thread of
+    // the pool would be waiting on a file if the files ran on it.
+    parallel::set_threads(2).unwrap();
+    let dir = temp_dir("batch");
+    for i in 0..4 {
+        fs::copy("input/day6/test.txt", dir.join(format!("test{}.txt", i))).unwrap();
+    }
+    let inputs = batch::find_inputs(dir.to_str().unwrap()).unwrap();
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let day6 = solution::get(6).unwrap();
+        tx.send(batch::run(&Runner::new(), day6, &inputs, false))
+            .unwrap();
+    });
+    let files = rx
+        .recv_timeout(Duration::from_secs(60))
+        .expect("the batch didn't finish")
+        .unwrap();
+    assert_eq!(files.len(), 4);
+    assert!(files.iter().all(FileResult::ok), "{:?}", files);
+    fs::remove_dir_all(&dir).unwrap();
+}
@@ -0,0 +1,142 @@
+mod common;
+
+use aoc2024::answer::Answer;
+use aoc2024::cache::{Cache, Key};
+use aoc2024::params::Params;
+use aoc2024::runner::Runner;
+use aoc2024::solution;
+use common::temp_dir;
+use std::fs;
+
+fn key<'a>(input: &'a str, params: &'a Params) -> Key<'a> {
+    Key {
+        day: 1,
+        part: 1,
+        variant: "default",
+        input,
+        params,
+    }
+}
+
+#[test]
+fn second_runs_come_from_the_cache() {
+    let dir = temp_dir("cache-hit");
+    let day1 = solution::get(1).unwrap();
+    let input = "3 4\n4 3\n";
+    let runner = Runner::new().with_cache(Cache::new(&dir), true);
+
+    let first = runner.run(day1, input, &[1, 2], None).unwrap();
+    assert!(first.parse.is_some());
+    assert!(first.parts.iter().all(|part| !part.cached));
+    let second = runner.run(day1, input, &[1, 2], None).unwrap();
+    // Nothing had to be parsed, let alone solved.
+    assert_eq!(second.parse, None);
+    assert!(second.parts.iter().all(|part| part.cached));
+    for (first, second) in first.parts.iter().zip(&second.parts) {
+        assert_eq!(first.answer, second.answer);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn no_cache_works_answers_out_again() {
+    let dir = temp_dir("cache-bypass");
+    let day1 = solution::get(1).unwrap();
+    let input = "3 4\n4 3\n";
+    Runner::new()
+        .with_cache(Cache::new(&dir), true)
+        .run(day1, input, &[1], None)
+        .unwrap();
+
+    // Like --no-cache: worked out again, though what it comes to is still saved.
+    let report = Runner::new()
+        .with_cache(Cache::new(&dir), false)
+        .run(day1, input, &[1], None)
+        .unwrap();
+    assert!(!report.parts[0].cached);
+    assert_eq!(report.parts[0].answer, Ok(Answer::Int(0)));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn keys_change_with_everything_an_answer_depends_on() {
+    let dir = temp_dir("cache-keys");
+    let cache = Cache::new(&dir);
+    let params = Params::default();
+    cache.put(&key("1 2\n", &params), &Answer::Int(1)).unwrap();
+    assert_eq!(cache.get(&key("1 2\n", &params)), Some(Answer::Int(1)));
+
+    assert_eq!(cache.get(&key("1 3\n", &params)), None);
+    let mut other_params = Params::default();
+    other_params.set("max_x", "11");
+    assert_eq!(cache.get(&key("1 2\n", &other_params)), None);
+    for other in [
+        Key {
+            day: 2,
+            ..key("1 2\n", &params)
+        },
+        Key {
+            part: 2,
+            ..key("1 2\n", &params)
+        },
+        Key {
+            variant: "serial",
+            ..key("1 2\n", &params)
+        },
+    ] {
+        assert_eq!(cache.get(&other), None, "{:?}", other);
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn clearing_removes_every_answer() {
+    let dir = temp_dir("cache-clear");
+    let cache = Cache::new(&dir);
+    let params = Params::default();
+    cache.put(&key("1 2\n", &params), &Answer::Int(1)).unwrap();
+    cache.put(&key("1 3\n", &params), &Answer::Int(2)).unwrap();
+    cache
+        .put(
+            &Key {
+                day: 2,
+                ..key("1 2\n", &params)
+            },
+            &Answer::Int(3),
+        )
+        .unwrap();
+
+    // Left behind by a writer that never got to rename it.
+    fs::write(
+        dir.join("day1")
+            .join("part1-default-0123456789abcdef.tmp4242-7"),
+        "4",
+    )
+    .unwrap();
+
+    assert_eq!(cache.clear().unwrap(), 3);
+    assert_eq!(cache.get(&key("1 2\n", &params)), None);
+    assert!(!dir.exists());
+    // Nothing left to clear.
+    assert_eq!(cache.clear().unwrap(), 0);
+}
+
+#[test]
+fn threads_saving_the_same_answer_dont_clash() {
+    let dir = temp_dir("cache-threads");
+    let cache = Cache::new(&dir);
+    let params = Params::default();
+    std::thread::scope(|scope| {
+        for _ in 0..8 {
+            scope.spawn(|| {
+                for _ in 0..50 {
+                    cache.put(&key("1 2\n", &params), &Answer::Int(1)).unwrap();
+                }
+            });
+        }
+    });
+    assert_eq!(cache.get(&key("1 2\n", &params)), Some(Answer::Int(1)));
+    // Every write was renamed into place, none left lying around.
+    assert_eq!(fs::read_dir(dir.join("day1")).unwrap().count(), 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
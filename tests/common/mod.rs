@@ -0,0 +1,12 @@
+//! Helpers shared by the integration tests, and by the unit tests through `crate::test_common`.
+
+use std::{env, fs, path::PathBuf, process};
+
+/// An empty directory of its own for each test, named after it. Whatever a previous run left
+/// there is cleared away first.
+pub fn temp_dir(name: &str) -> PathBuf {
+    let dir = env::temp_dir().join(format!("aoc2024-{}-{}", name, process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
@@ -0,0 +1,56 @@
+use aoc2024::params::Params;
+use aoc2024::snapshot::{assert_snapshot, Snapshot};
+use aoc2024::solution;
+
+/// The warehouse before and after each of the robot's moves, titled like the puzzle's walkthrough.
+fn moves(input: &str, params: &Params) -> Snapshot {
+    let parsed = solution::get(15).unwrap().parse(input).unwrap();
+    let mut robot = parsed.simulation(params).unwrap();
+    let mut snapshot = Snapshot::new();
+    snapshot.push("Initial state", robot.render().unwrap());
+    let (_, moves) = input.split_once("\n\n").unwrap();
+    for c in moves.chars().filter(|c| "^>v<".contains(*c)) {
+        assert!(robot.step(), "the robot stopped before its last move");
+        snapshot.push(format!("Move {}", c), robot.render().unwrap());
+    }
+    assert!(robot.is_done());
+    snapshot
+}
+
+/// The last frame only, for inputs with too many moves to keep every one.
+fn last(snapshot: Snapshot) -> Snapshot {
+    let (title, grid) = snapshot.frames().last().unwrap().clone();
+    let mut last = Snapshot::new();
+    last.push(title, grid);
+    last
+}
+
+#[test]
+fn part2_small_example_move_by_move() {
+    let input = include_str!("../input/day15/test3.txt");
+    assert_snapshot("day15_part2_small_example", &moves(input, &Params::new()));
+}
+
+#[test]
+fn part2_large_example_end_state() {
+    let input = include_str!("../input/day15/test.txt");
+    assert_snapshot(
+        "day15_part2_large_example",
+        &last(moves(input, &Params::new())),
+    );
+}
+
+#[test]
+fn part1_large_example_end_state() {
+    let input = include_str!("../input/day15/test.txt");
+    let mut params = Params::new();
+    params.set("widen", "false");
+    assert_snapshot("day15_part1_large_example", &last(moves(input, &params)));
+}
+
+#[test]
+fn rejects_unknown_tiles() {
+    let error = aoc2024::day15::parse("#####\n#@.x#\n#####\n\n<>\n").unwrap_err();
+    assert!(error.contains("line 2, column 4"), "{}", error);
+    assert!(aoc2024::day15::parse("###\n#.#\n###\n\n<>\n").is_err());
+}
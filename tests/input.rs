@@ -0,0 +1,60 @@
+mod common;
+
+use common::temp_dir;
+use serde_json::Value;
+use std::{env, fs, path::Path, process::Command};
+
+/// Runs `aoc run 1 --format json` with `args` in `dir`, away from any config files, and gives
+/// back part 1's answer.
+fn part1(dir: &Path, env_dir: Option<&str>, args: &[&str]) -> String {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_aoc"));
+    command
+        .args(["run", "1", "--part", "1", "--no-cache", "--format", "json"])
+        .args(args)
+        .current_dir(dir)
+        .env("HOME", dir);
+    for (key, _) in env::vars_os() {
+        if key.to_string_lossy().starts_with("AOC_") {
+            command.env_remove(key);
+        }
+    }
+    if let Some(env_dir) = env_dir {
+        command.env("AOC_INPUT_DIR", env_dir);
+    }
+    let output = command.output().unwrap();
+    assert!(
+        output.status.success(),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let run: Value = serde_json::from_slice(&output.stdout).unwrap();
+    run["parts"][0]["answer"].as_str().unwrap().to_string()
+}
+
+#[test]
+fn finds_inputs_in_the_input_dir() {
+    let dir = temp_dir("input");
+    // Each file's part 1 answer is the gap between its two numbers.
+    for (path, input) in [
+        ("input/day1/input.txt", "1 1\n"),
+        ("input/day1/test.txt", "1 2\n"),
+        ("input/day1/test2.txt", "1 3\n"),
+        ("mine/day1/input.txt", "1 5\n"),
+        ("theirs/day1/input.txt", "1 7\n"),
+        ("other.txt", "1 9\n"),
+    ] {
+        let path = dir.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, input).unwrap();
+    }
+
+    assert_eq!(part1(&dir, None, &[]), "0");
+    assert_eq!(part1(&dir, None, &["--example", "1"]), "1");
+    assert_eq!(part1(&dir, None, &["--example", "2"]), "2");
+    assert_eq!(part1(&dir, None, &["--input", "other.txt"]), "8");
+    assert_eq!(part1(&dir, None, &["--input-dir", "mine"]), "4");
+    assert_eq!(part1(&dir, Some("theirs"), &[]), "6");
+    // The flag wins over the environment.
+    assert_eq!(part1(&dir, Some("theirs"), &["--input-dir", "mine"]), "4");
+    fs::remove_dir_all(&dir).unwrap();
+}
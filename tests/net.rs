@@ -0,0 +1,180 @@
+#![cfg(feature = "net")]
+
+mod common;
+
+use aoc2024::answer::Answer;
+use aoc2024::net::{Client, NetError, SubmitOutcome};
+use common::temp_dir;
+use std::{
+    fs,
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    path::PathBuf,
+    sync::{Arc, Mutex},
+    thread,
+    time::Duration,
+};
+
+/// A request the stub server was sent.
+#[derive(Debug, Clone)]
+struct Seen {
+    line: String,
+    headers: Vec<String>,
+    body: String,
+}
+
+impl Seen {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.iter().find_map(|header| {
+            let (key, value) = header.split_once(':')?;
+            key.eq_ignore_ascii_case(name).then(|| value.trim())
+        })
+    }
+}
+
+/// A server on a free port answering every request with `status` and `body`, and the requests
+/// it's been sent so far.
+fn stub(status: u16, body: &'static str) -> (String, Arc<Mutex<Vec<Seen>>>) {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let seen = Arc::new(Mutex::new(Vec::new()));
+    let log = Arc::clone(&seen);
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut line = String::new();
+            reader.read_line(&mut line).unwrap();
+            let mut headers = Vec::new();
+            loop {
+                let mut header = String::new();
+                reader.read_line(&mut header).unwrap();
+                if header.trim().is_empty() {
+                    break;
+                }
+                headers.push(header.trim().to_string());
+            }
+            let mut request = Seen {
+                line: line.trim().to_string(),
+                headers,
+                body: String::new(),
+            };
+            let len = request
+                .header("Content-Length")
+                .map_or(0, |len| len.parse().unwrap());
+            let mut sent = vec![0; len];
+            reader.read_exact(&mut sent).unwrap();
+            request.body = String::from_utf8(sent).unwrap();
+            log.lock().unwrap().push(request);
+            write!(
+                stream,
+                "HTTP/1.1 {} Stub\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                status,
+                body.len(),
+                body
+            )
+            .unwrap();
+        }
+    });
+    (base_url, seen)
+}
+
+fn client(base_url: &str, state: &PathBuf) -> Client {
+    Client::new(base_url, "abc123", state).with_min_interval(Duration::ZERO)
+}
+
+#[test]
+fn fetches_an_input_once() {
+    let (base_url, seen) = stub(200, "1 2\n3 4\n");
+    let dir = temp_dir("net-fetch");
+    let client = client(&base_url, &dir.join("state"));
+    let path = dir.join("input/day1/input.txt");
+
+    assert_eq!(
+        client.fetch_input_to(1, &path).unwrap(),
+        ("1 2\n3 4\n".to_string(), false)
+    );
+    assert_eq!(fs::read_to_string(&path).unwrap(), "1 2\n3 4\n");
+    // The second time it's read from disk.
+    assert_eq!(
+        client.fetch_input_to(1, &path).unwrap(),
+        ("1 2\n3 4\n".to_string(), true)
+    );
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].line, "GET /2024/day/1/input HTTP/1.1");
+    assert_eq!(seen[0].header("Cookie"), Some("session=abc123"));
+    assert!(seen[0].header("User-Agent").unwrap().contains("aoc2024"));
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn reports_http_errors() {
+    let dir = temp_dir("net-errors");
+    let (base_url, _) = stub(404, "");
+    match client(&base_url, &dir).fetch_input(25) {
+        Err(NetError::NotFound(url)) => assert!(url.ends_with("/2024/day/25/input")),
+        other => panic!("expected NotFound, got {:?}", other),
+    }
+    let (base_url, _) = stub(500, "");
+    match client(&base_url, &dir).fetch_input(1) {
+        Err(NetError::Http(500, _)) => (),
+        other => panic!("expected HTTP 500, got {:?}", other),
+    }
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn submits_answers_and_remembers_them() {
+    let (base_url, seen) = stub(
+        200,
+        "<main><article><p>That's the right answer! You are <em>one gold star</em> closer.</p></article></main>",
+    );
+    let dir = temp_dir("net-submit");
+    let client = client(&base_url, &dir);
+
+    let answer = Answer::Int(11);
+    assert_eq!(
+        client.submit(1, 2, &answer).unwrap(),
+        SubmitOutcome::Correct
+    );
+    // Already known to be right, so it isn't sent again.
+    assert_eq!(
+        client.submit(1, 2, &answer).unwrap(),
+        SubmitOutcome::Correct
+    );
+
+    let seen = seen.lock().unwrap();
+    assert_eq!(seen.len(), 1);
+    assert_eq!(seen[0].line, "POST /2024/day/1/answer HTTP/1.1");
+    assert_eq!(seen[0].body, "level=2&answer=11");
+    fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+fn waits_out_a_lockout() {
+    let (base_url, seen) = stub(
+        200,
+        "<article><p>That's not the right answer; your answer is too low. Please wait one minute before trying again.</p></article>",
+    );
+    let dir = temp_dir("net-lockout");
+    let client = client(&base_url, &dir);
+
+    assert_eq!(
+        client.submit(3, 1, &Answer::Int(5)).unwrap(),
+        SubmitOutcome::TooLow
+    );
+    // Below a known low answer it's known to be too low without asking.
+    assert_eq!(
+        client.submit(3, 1, &Answer::Int(4)).unwrap(),
+        SubmitOutcome::TooLow
+    );
+    // Anything else has to wait for the lockout.
+    match client.submit(3, 1, &Answer::Int(6)).unwrap() {
+        SubmitOutcome::Wait(remaining) => assert!(remaining <= Duration::from_secs(60)),
+        other => panic!("expected a wait, got {:?}", other),
+    }
+    assert_eq!(seen.lock().unwrap().len(), 1);
+    fs::remove_dir_all(&dir).unwrap();
+}
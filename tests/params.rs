@@ -0,0 +1,105 @@
+use aoc2024::answer::Answer;
+use aoc2024::params::{ParamSpec, Params};
+use aoc2024::runner::Runner;
+use aoc2024::{day11, solution};
+
+fn params(pairs: &[(&str, &str)]) -> Params {
+    pairs
+        .iter()
+        .map(|&(key, value)| (key.to_string(), value.to_string()))
+        .collect()
+}
+
+#[test]
+fn checks_type_and_range() {
+    let specs = [ParamSpec::new("blinks", 25usize, "how many blinks").between(0, 40)];
+    assert!(params(&[("blinks", "40")]).check(&specs).is_ok());
+    let err = params(&[("blinks", "41")]).check(&specs).unwrap_err();
+    assert!(err.contains("isn't between 0 and 40"), "{}", err);
+    let err = params(&[("blinks", "-1")]).check(&specs).unwrap_err();
+    assert!(err.contains("isn't a valid usize"), "{}", err);
+    assert!(params(&[("blink", "1")]).check(&specs).is_err());
+}
+
+#[test]
+fn day11_blinks_are_limited() {
+    // Enough blinks to overflow the stack are turned away before anything runs.
+    let specs = solution::get(11).unwrap().params();
+    assert!(params(&[("part2_blinks", "2000000")])
+        .check(&specs)
+        .is_err());
+    let most = day11::MAX_PART2_BLINKS.to_string();
+    assert!(params(&[("part2_blinks", &most)]).check(&specs).is_ok());
+    // Native integers run out long before the stack does.
+    #[cfg(not(feature = "bigmath"))]
+    assert!(params(&[("part2_blinks", "1000")]).check(&specs).is_err());
+    #[cfg(feature = "bigmath")]
+    assert!(params(&[("part2_blinks", "1000")]).check(&specs).is_ok());
+}
+
+#[test]
+fn day13_presses_and_offset_are_limited() {
+    // Part 1 is quadratic in the presses, and a big enough offset overflows part 2.
+    let specs = solution::get(13).unwrap().params();
+    assert!(params(&[("max_presses", "1000")]).check(&specs).is_ok());
+    assert!(params(&[("max_presses", "10000000")])
+        .check(&specs)
+        .is_err());
+    assert!(params(&[("prize_offset", "1000000000000000")])
+        .check(&specs)
+        .is_ok());
+    assert!(params(&[("prize_offset", "10000000000000000")])
+        .check(&specs)
+        .is_err());
+}
+
+#[test]
+fn every_default_is_accepted() {
+    // What `aoc params` and `GET /days` show as the default has to be a value that can be given.
+    for solution in solution::all() {
+        for spec in solution.params() {
+            assert!(
+                spec.accepts(&spec.default),
+                "day {} {}: {:?}",
+                solution.day(),
+                spec.name,
+                spec.default
+            );
+        }
+    }
+}
+
+#[test]
+fn day11_blinks_sets_both_parts() {
+    let day11 = solution::get(11).unwrap();
+    let specs = day11.params();
+    assert!(params(&[("blinks", "40")]).check(&specs).is_ok());
+    assert!(params(&[("blinks", "41")]).check(&specs).is_err());
+
+    let run = |pairs: &[(&str, &str)]| {
+        Runner::new()
+            .with_params(params(pairs))
+            .run(day11, "125 17", &[1, 2], None)
+            .unwrap()
+            .parts
+            .into_iter()
+            .map(|part| part.answer.unwrap())
+            .collect::<Vec<_>>()
+    };
+    // The puzzle's example: 22 stones after 6 blinks, 55312 after 25.
+    assert_eq!(run(&[("blinks", "6")]), [Answer::Int(22), Answer::Int(22)]);
+    assert_eq!(
+        run(&[("blinks", "6"), ("part1_blinks", "25")]),
+        [Answer::Int(55312), Answer::Int(22)]
+    );
+}
+
+#[test]
+fn day11_counts_stones_exactly_up_to_its_limit() {
+    // Worked out with bigmath; a u128 that wrapped would come to something else.
+    let stones = day11::parse("0").unwrap();
+    assert_eq!(
+        day11::solve_part2(&stones, 150).to_string(),
+        "945860666074062862472779122"
+    );
+}
@@ -0,0 +1,199 @@
+use aoc2024::answer::Answer;
+use aoc2024::{
+    day1, day10, day11, day12, day13, day14, day15, day2, day3, day4, day5, day6, day7, day8, day9,
+};
+
+type Part = fn(&str) -> Result<Answer, String>;
+
+/// Each day's example with both of its parts, as the library exposes them.
+fn days() -> Vec<(u8, &'static str, Part, Part)> {
+    vec![
+        (
+            1,
+            include_str!("../input/day1/test.txt"),
+            day1::part1,
+            day1::part2,
+        ),
+        (
+            2,
+            "7 6 4 2 1\n1 2 7 8 9\n1 3 2 4 5",
+            day2::part1,
+            day2::part2,
+        ),
+        (
+            3,
+            include_str!("../input/day3/test.txt"),
+            day3::part1,
+            day3::part2,
+        ),
+        (
+            4,
+            include_str!("../input/day4/test.txt"),
+            day4::part1,
+            day4::part2,
+        ),
+        (
+            5,
+            include_str!("../input/day5/test.txt"),
+            day5::part1,
+            day5::part2,
+        ),
+        (
+            6,
+            include_str!("../input/day6/test.txt"),
+            day6::part1,
+            day6::part2,
+        ),
+        (
+            7,
+            "190: 10 19\n3267: 81 40 27\n83: 17 5",
+            day7::part1,
+            day7::part2,
+        ),
+        (
+            8,
+            include_str!("../input/day8/test.txt"),
+            day8::part1,
+            day8::part2,
+        ),
+        (
+            9,
+            include_str!("../input/day9/test.txt"),
+            day9::part1,
+            day9::part2,
+        ),
+        (
+            10,
+            include_str!("../input/day10/test.txt"),
+            day10::part1,
+            day10::part2,
+        ),
+        (
+            11,
+            include_str!("../input/day11/test.txt"),
+            day11::part1,
+            day11::part2,
+        ),
+        (
+            12,
+            include_str!("../input/day12/test.txt"),
+            day12::part1,
+            day12::part2,
+        ),
+        (
+            13,
+            include_str!("../input/day13/test.txt"),
+            day13::part1,
+            day13::part2,
+        ),
+        (
+            14,
+            include_str!("../input/day14/test.txt"),
+            day14::part1,
+            day14::part2,
+        ),
+        (
+            15,
+            include_str!("../input/day15/test.txt"),
+            day15::part1,
+            day15::part2,
+        ),
+    ]
+}
+
+#[test]
+fn trailing_newlines_are_ignored() {
+    for (day, example, part1, part2) in days() {
+        let example = example.trim_end();
+        for part in [part1, part2] {
+            let answer = part(example).unwrap_or_else(|e| panic!("day {}: {}", day, e));
+            assert_eq!(
+                part(&format!("{}\n", example)),
+                Ok(answer.clone()),
+                "day {}",
+                day
+            );
+            assert_eq!(
+                part(&format!("{}\r\n\n", example)),
+                Ok(answer),
+                "day {}",
+                day
+            );
+        }
+    }
+}
+
+#[test]
+fn garbage_is_an_error() {
+    for (day, _, part1, part2) in days() {
+        // Any character can be an antenna and any text corrupted memory, so days 3 and 8 only
+        // turn away an empty input.
+        let garbage = if day == 3 || day == 8 {
+            "\n"
+        } else {
+            "not an input\n"
+        };
+        for part in [part1, part2] {
+            assert!(part(garbage).is_err(), "day {}", day);
+        }
+    }
+    assert_eq!(
+        day1::part1("1 2\n3\n"),
+        Err("line 2 doesn't have two numbers".to_string())
+    );
+    assert_eq!(
+        day10::part1("01\n2x\n"),
+        Err("line 2, column 2: 'x' isn't a digit".to_string())
+    );
+    assert_eq!(
+        day7::part1("190:\n"),
+        Err("line 1 has no numbers after the ':'".to_string())
+    );
+    assert_eq!(
+        day13::part1("Button A: X+1, Y+2\n"),
+        Err("machine 1 has no button B".to_string())
+    );
+    assert_eq!(
+        day4::part1("XMAS\nXMAZ\n"),
+        Err("line 2, column 4: 'Z' isn't X, M, A, S or '.'".to_string())
+    );
+    assert_eq!(
+        day5::part1("1|99999999999999999999999\n\n1,2\n"),
+        Err(
+            "line 1: \"99999999999999999999999\": number too large to fit in target type"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        day5::part1("1|2\n\n1,x\n"),
+        Err("line 3: \"x\": invalid digit found in string".to_string())
+    );
+    assert_eq!(
+        day6::part1("..#\n...\n"),
+        Err("there's no guard on the map".to_string())
+    );
+    assert_eq!(
+        day12::part1("AA\nA.\n"),
+        Err("line 2, column 2: '.' isn't a plant".to_string())
+    );
+    assert_eq!(
+        day14::part1("p=0,4 v=3,-3\np=99999999999999999999,3 v=-1,-3\n"),
+        Err("line 2: number too large to fit in target type".to_string())
+    );
+    assert_eq!(
+        day14::part1("p=0,4 v=3\n"),
+        Err("line 1 isn't a robot".to_string())
+    );
+    assert_eq!(day14::part1("\n"), Err("there are no robots".to_string()));
+    assert_eq!(
+        day15::part1("#..\n#.#\n\n<>\n"),
+        Err("no robot found".to_string())
+    );
+}
+
+#[test]
+fn dots_are_filler_in_day4() {
+    let example = include_str!("../input/day4/test2.txt");
+    assert_eq!(day4::part1(example), Ok(Answer::Int(0)));
+    assert_eq!(day4::part2(example), Ok(Answer::Int(1)));
+}
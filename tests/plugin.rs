@@ -0,0 +1,76 @@
+#![cfg(feature = "plugins")]
+
+mod common;
+
+use common::temp_dir;
+use serde_json::Value;
+use std::{
+    env, fs,
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+/// Builds `examples/day1_plugin.rs` and copies the library into an empty plugin directory of its
+/// own.
+fn day1_plugin() -> PathBuf {
+    let status = Command::new(env!("CARGO"))
+        .args(["build", "--quiet", "--example", "day1_plugin"])
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .status()
+        .unwrap();
+    assert!(status.success(), "building the example plugin failed");
+    let file = format!(
+        "{}day1_plugin{}",
+        env::consts::DLL_PREFIX,
+        env::consts::DLL_SUFFIX
+    );
+    // The test binary is in target/debug/deps, next to target/debug/examples.
+    let exe = env::current_exe().unwrap();
+    let library = exe.parent().unwrap().with_file_name("examples").join(&file);
+    let dir = temp_dir("plugin");
+    fs::copy(&library, dir.join(&file)).unwrap();
+    dir
+}
+
+fn aoc(plugin_dir: &Path, args: &[&str]) -> (bool, String) {
+    let output = Command::new(env!("CARGO_BIN_EXE_aoc"))
+        .arg("--plugin-dir")
+        .arg(plugin_dir)
+        .args(["--quiet", "--format", "json"])
+        .args(args)
+        .current_dir(env!("CARGO_MANIFEST_DIR"))
+        .output()
+        .unwrap();
+    (
+        output.status.success(),
+        String::from_utf8(output.stdout).unwrap(),
+    )
+}
+
+#[test]
+fn example_plugin_agrees_with_day1() {
+    let dir = day1_plugin();
+
+    let (ok, stdout) = aoc(&dir, &["plugins"]);
+    assert!(ok);
+    let plugins: Value = serde_json::from_str(&stdout).unwrap();
+    assert_eq!(plugins[0]["day"], 1);
+    assert_eq!(plugins[0]["impl"], "counting");
+
+    let (ok, stdout) = aoc(&dir, &["run", "1", "--compare", "--example", "1"]);
+    assert!(ok, "{}", stdout);
+    let run: Value = serde_json::from_str(&stdout).unwrap();
+    let parts = run["parts"].as_array().unwrap();
+    for part in [1, 2] {
+        let answers = parts
+            .iter()
+            .filter(|result| result["part"] == part)
+            .map(|result| (result["impl"].as_str().unwrap(), &result["answer"]))
+            .collect::<Vec<_>>();
+        assert!(answers.iter().any(|&(variant, _)| variant == "counting"));
+        assert!(answers.len() >= 2);
+        assert!(answers.iter().all(|(_, answer)| *answer == answers[0].1));
+        assert!(!answers[0].1.is_null());
+    }
+    fs::remove_dir_all(&dir).unwrap();
+}
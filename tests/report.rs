@@ -0,0 +1,67 @@
+use aoc2024::batch::Checked;
+use aoc2024::report::{self, DayReport, DayRun};
+use aoc2024::runner::{Failure, PartResult, Runner};
+use aoc2024::solution;
+use std::{
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+#[test]
+fn reports_every_implementation_with_pictures() {
+    let day6 = solution::get(6).unwrap();
+    let report = report::run_day(&Runner::new(), day6, Path::new("input/day6/test.txt"));
+    let run = report.outcome.unwrap();
+    assert_eq!(run.parts.len(), day6.variants().len());
+    assert!(run.parts.iter().all(|part| part.result.answer.is_ok()));
+    assert_eq!(run.pictures.len(), 1);
+}
+
+#[test]
+fn failing_implementations_dont_fail_the_day() {
+    let runner = Runner::new().with_timeout(Some(Duration::from_nanos(1)));
+    let day6 = solution::get(6).unwrap();
+    let report = report::run_day(&runner, day6, Path::new("input/day6/test.txt"));
+    let run = report.outcome.unwrap();
+    assert_eq!(run.parts.len(), day6.variants().len());
+    for part in &run.parts {
+        assert!(
+            matches!(part.result.answer, Err(Failure::TimedOut(_))),
+            "{:?}",
+            part.result
+        );
+    }
+}
+
+#[test]
+fn failures_are_escaped_in_html() {
+    let failed = Checked {
+        result: PartResult {
+            part: 1,
+            variant: "default",
+            answer: Err(Failure::Failed("expected <b> & got </b>".to_string())),
+            elapsed: Duration::ZERO,
+            memory: None,
+            counts: None,
+            cached: false,
+        },
+        expected: None,
+    };
+    let report = DayReport {
+        day: 1,
+        input: PathBuf::from("input/day1/test.txt"),
+        outcome: Ok(DayRun {
+            parse: None,
+            parse_memory: None,
+            parts: vec![failed],
+            pictures: Vec::new(),
+        }),
+    };
+    let html = report::to_html(&[report]);
+    assert!(
+        html.contains("expected &lt;b&gt; &amp; got &lt;/b&gt;"),
+        "{}",
+        html
+    );
+    assert!(!html.contains("<b>"));
+}
@@ -0,0 +1,215 @@
+use aoc2024::answer::Answer;
+use aoc2024::cancel;
+use aoc2024::grid::{CharGrid, Picture};
+use aoc2024::params::{ParamSpec, Params};
+use aoc2024::runner::{Failure, Runner};
+use aoc2024::simulation::Playback;
+use aoc2024::solution::{self, DynSolution, Parsed};
+use std::{
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::{Duration, Instant},
+};
+
+/// A day 25 with one part, whose parser and part do whatever a test needs.
+struct Fake {
+    parse: fn(&str),
+    part1: fn(&str) -> Answer,
+}
+
+struct FakeInput(String, fn(&str) -> Answer);
+
+impl DynSolution for Fake {
+    fn day(&self) -> u8 {
+        25
+    }
+
+    fn variants(&self) -> Vec<(u8, &'static str)> {
+        vec![(1, "default")]
+    }
+
+    fn params(&self) -> Vec<ParamSpec> {
+        Vec::new()
+    }
+
+    fn parse(&self, input: &str) -> Result<Box<dyn Parsed>, String> {
+        (self.parse)(input);
+        Ok(Box::new(FakeInput(input.to_string(), self.part1)))
+    }
+
+    fn scale(&self, _input: &str, _factor: usize) -> Option<String> {
+        None
+    }
+}
+
+impl Parsed for FakeInput {
+    fn run(
+        &self,
+        part: u8,
+        variant: Option<&str>,
+        _params: &Params,
+    ) -> Option<Result<Answer, String>> {
+        (part == 1 && variant.is_none_or(|name| name == "default")).then(|| Ok((self.1)(&self.0)))
+    }
+
+    fn grid(&self, _params: &Params) -> Option<CharGrid> {
+        None
+    }
+
+    fn pictures(&self, _params: &Params, _answers: &[(u8, Answer)]) -> Vec<Picture> {
+        Vec::new()
+    }
+
+    fn simulation(&self, _params: &Params) -> Option<Box<dyn Playback>> {
+        None
+    }
+}
+
+fn fake(parse: fn(&str), part1: fn(&str) -> Answer) -> &'static dyn DynSolution {
+    Box::leak(Box::new(Fake { parse, part1 }))
+}
+
+/// Checks `answer` is a panic with `message` from this file.
+fn assert_panicked(answer: &Result<Answer, Failure>, message: &str) {
+    match answer {
+        Err(Failure::Panicked {
+            message: panicked,
+            location: Some(location),
+        }) => {
+            assert_eq!(panicked, message);
+            assert!(location.starts_with("tests/runner.rs:"), "{}", location);
+        }
+        other => panic!("expected a panic, got {:?}", other),
+    }
+}
+
+#[test]
+fn part_panics_are_caught_with_where_they_happened() {
+    let day = fake(|_| (), |_| panic!("no answer"));
+    let report = Runner::new().run(day, "", &[1], None).unwrap();
+    assert_panicked(&report.parts[0].answer, "no answer");
+}
+
+/// A panic carried over from another thread can't say where it happened, rather than borrowing
+/// wherever some other thread last panicked.
+#[cfg(feature = "parallel")]
+#[test]
+fn panics_from_the_pool_have_no_location() {
+    use aoc2024::parallel::prelude::*;
+
+    let day = fake(
+        |_| (),
+        |_| {
+            (0..2).into_par_iter().for_each(|_| panic!("over there"));
+            Answer::Int(0)
+        },
+    );
+    let report = Runner::new().run(day, "", &[1], None).unwrap();
+    let answer = &report.parts[0].answer;
+    assert_eq!(
+        answer,
+        &Err(Failure::Panicked {
+            message: "over there".to_string(),
+            location: None,
+        })
+    );
+    assert_eq!(
+        answer.as_ref().unwrap_err().to_string(),
+        "panicked at unknown location: over there"
+    );
+}
+
+#[test]
+fn a_day_whose_parser_panics_doesnt_stop_the_next() {
+    let runner = Runner::new();
+    let day = fake(|_| panic!("bad input"), |_| Answer::Int(0));
+    let report = runner.run(day, "", &[1], None).unwrap();
+    assert_panicked(
+        &report.parts[0].answer,
+        "while parsing the input: bad input",
+    );
+
+    let day1 = solution::get(1).unwrap();
+    let report = runner.run(day1, "3 4\n4 3\n", &[1, 2], None).unwrap();
+    assert_eq!(report.parts[0].answer, Ok(Answer::Int(0)));
+    assert_eq!(report.parts[1].answer, Ok(Answer::Int(7)));
+}
+
+#[test]
+fn parts_past_the_timeout_are_cancelled() {
+    static STOPPED: AtomicBool = AtomicBool::new(false);
+    /// Notices the part unwinding once it's been cancelled.
+    struct Stop;
+    impl Drop for Stop {
+        fn drop(&mut self) {
+            STOPPED.store(true, Ordering::Relaxed);
+        }
+    }
+
+    let day = fake(
+        |_| (),
+        |_| {
+            let _stop = Stop;
+            loop {
+                cancel::check();
+                thread::sleep(Duration::from_millis(1));
+            }
+        },
+    );
+    let timeout = Duration::from_millis(50);
+    let start = Instant::now();
+    let report = Runner::new()
+        .with_timeout(Some(timeout))
+        .run(day, "", &[1], None)
+        .unwrap();
+    assert_eq!(report.parts[0].answer, Err(Failure::TimedOut(timeout)));
+    // It stopped well within the grace it's given, rather than being left behind.
+    assert!(STOPPED.load(Ordering::Relaxed));
+    assert!(
+        start.elapsed() < Duration::from_millis(900),
+        "{:?}",
+        start.elapsed()
+    );
+}
+
+#[test]
+fn parts_that_ignore_cancelling_are_left_behind() {
+    let day = fake(
+        |_| (),
+        |_| {
+            thread::sleep(Duration::from_secs(5));
+            Answer::Int(0)
+        },
+    );
+    let timeout = Duration::from_millis(50);
+    let start = Instant::now();
+    let report = Runner::new()
+        .with_timeout(Some(timeout))
+        .run(day, "", &[1], None)
+        .unwrap();
+    assert_eq!(report.parts[0].answer, Err(Failure::TimedOut(timeout)));
+    assert!(
+        start.elapsed() < Duration::from_secs(4),
+        "{:?}",
+        start.elapsed()
+    );
+}
+
+#[test]
+fn day14_part2_stops_at_the_timeout() {
+    let day14 = solution::get(14).unwrap();
+    let input = include_str!("../input/day14/test.txt");
+    let timeout = Duration::from_millis(100);
+    let start = Instant::now();
+    let report = Runner::new()
+        .with_timeout(Some(timeout))
+        .run(day14, input, &[1, 2], None)
+        .unwrap();
+    assert_eq!(report.parts[0].answer, Ok(Answer::Int(21)));
+    assert_eq!(report.parts[1].answer, Err(Failure::TimedOut(timeout)));
+    assert!(
+        start.elapsed() < Duration::from_secs(1),
+        "{:?}",
+        start.elapsed()
+    );
+}
@@ -0,0 +1,180 @@
+#![cfg(feature = "serve")]
+
+use aoc2024::serve::{Limits, Server};
+use serde_json::Value;
+use std::{
+    io::{Read, Write},
+    net::{SocketAddr, TcpStream},
+    sync::Arc,
+    thread,
+    time::Duration,
+};
+
+/// A server on a free port, answering requests on a thread of its own until the test ends.
+fn start(limits: Limits) -> SocketAddr {
+    let server = Arc::new(Server::bind(0, limits).unwrap());
+    let addr = server.addr();
+    thread::spawn(move || server.run());
+    addr
+}
+
+/// Sends one request and returns the response's status and JSON body.
+fn request(addr: SocketAddr, method: &str, path: &str, body: &str) -> (u16, Value) {
+    let mut stream = TcpStream::connect(addr).unwrap();
+    write!(
+        stream,
+        "{} {} HTTP/1.1\r\nHost: localhost\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        method,
+        path,
+        body.len(),
+        body
+    )
+    .unwrap();
+    let mut response = String::new();
+    stream.read_to_string(&mut response).unwrap();
+    let (head, body) = response.split_once("\r\n\r\n").unwrap();
+    let status = head.split(' ').nth(1).unwrap().parse().unwrap();
+    (status, serde_json::from_str(body).unwrap())
+}
+
+#[test]
+fn solves_a_part() {
+    let addr = start(Limits::default());
+    let input = include_str!("../input/day1/test.txt");
+    let (status, body) = request(addr, "POST", "/day/1/part/2", input);
+    assert_eq!(status, 200, "{}", body);
+    assert_eq!(body["answer"], "7");
+    assert_eq!(body["impl"], "default");
+    assert!(body["seconds"].is_f64());
+}
+
+#[test]
+fn takes_the_implementation_and_parameters_from_the_query() {
+    let addr = start(Limits::default());
+    let input = include_str!("../input/day14/test.txt");
+    let (status, body) = request(
+        addr,
+        "POST",
+        "/day/14/part/1?impl=default&max_x=11&max_y=7",
+        input,
+    );
+    assert_eq!((status, &body["answer"]), (200, &Value::from("12")));
+    let (status, body) = request(addr, "POST", "/day/14/part/1?bogus=1", input);
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("bogus"));
+}
+
+#[test]
+fn lists_the_days() {
+    let addr = start(Limits::default());
+    let (status, body) = request(addr, "GET", "/days", "");
+    assert_eq!(status, 200);
+    let day2 = body
+        .as_array()
+        .unwrap()
+        .iter()
+        .find(|day| day["day"] == 2)
+        .unwrap();
+    assert_eq!(day2["parts"][1]["impls"][1], "recursive");
+    assert_eq!(day2["params"][0]["name"], "max_step");
+}
+
+#[test]
+fn turns_bad_requests_away() {
+    let addr = start(Limits {
+        max_body: 16,
+        ..Limits::default()
+    });
+    assert_eq!(request(addr, "GET", "/nowhere", "").0, 404);
+    assert_eq!(request(addr, "GET", "/day/1/part/1", "").0, 405);
+    assert_eq!(request(addr, "POST", "/day/25/part/1", "").0, 404);
+    assert_eq!(request(addr, "POST", "/day/1/part/1", "x y").0, 400);
+    assert_eq!(
+        request(addr, "POST", "/day/1/part/1", &"1 2\n".repeat(5)).0,
+        413
+    );
+}
+
+#[test]
+fn enforces_the_timeout_and_concurrency_limits() {
+    let addr = start(Limits {
+        timeout: Duration::from_nanos(1),
+        ..Limits::default()
+    });
+    let input = include_str!("../input/day14/test.txt");
+    let (status, body) = request(addr, "POST", "/day/14/part/2?max_x=11&max_y=7", input);
+    assert_eq!(status, 504, "{}", body);
+
+    let addr = start(Limits {
+        max_concurrent: 0,
+        ..Limits::default()
+    });
+    assert_eq!(request(addr, "POST", "/day/1/part/1", "1 2\n").0, 503);
+}
+
+#[test]
+fn decodes_the_query_and_checks_parameter_ranges() {
+    let addr = start(Limits::default());
+    let input = include_str!("../input/day14/test.txt");
+    let (status, body) = request(
+        addr,
+        "POST",
+        "/day/14/part/1?impl=def%61ult&max_x=1%31&max_y=%37",
+        input,
+    );
+    assert_eq!(
+        (status, &body["answer"]),
+        (200, &Value::from("12")),
+        "{}",
+        body
+    );
+    let (status, body) = request(addr, "POST", "/day/14/part/1?impl=no+such", input);
+    assert_eq!(status, 400);
+    assert!(body["error"].as_str().unwrap().contains("\"no such\""));
+    assert_eq!(
+        request(addr, "POST", "/day/14/part/1?max_x=%1", input).0,
+        400
+    );
+
+    // Enough blinks to overflow the stack are turned away rather than taking the server down.
+    let (status, body) = request(
+        addr,
+        "POST",
+        "/day/11/part/2?part2_blinks=2000000",
+        "125 17",
+    );
+    assert_eq!(status, 400, "{}", body);
+    assert_eq!(request(addr, "POST", "/day/11/part/2", "125 17").0, 200);
+}
+
+#[test]
+fn keeps_the_slot_until_a_timed_out_part_stops() {
+    let addr = start(Limits {
+        timeout: Duration::from_millis(1),
+        max_concurrent: 1,
+        ..Limits::default()
+    });
+    // Part 1 never checks for cancellation, so it's still running after the reply.
+    let (status, body) = request(addr, "POST", "/day/11/part/1?part1_blinks=40", "125 17");
+    assert_eq!(status, 504, "{}", body);
+    assert_eq!(request(addr, "POST", "/day/1/part/1", "1 2\n").0, 503);
+}
+
+#[test]
+fn a_stalled_upload_doesnt_hold_a_slot() {
+    let addr = start(Limits {
+        max_concurrent: 1,
+        ..Limits::default()
+    });
+    // Promises a body and never finishes sending it.
+    let mut stalled = TcpStream::connect(addr).unwrap();
+    write!(
+        stalled,
+        "POST /day/1/part/1 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 100000\r\n\r\n1 2"
+    )
+    .unwrap();
+    thread::sleep(Duration::from_millis(100));
+    let (status, body) = request(addr, "POST", "/day/1/part/1", "1 2\n");
+    assert_eq!(status, 200, "{}", body);
+    drop(stalled);
+}